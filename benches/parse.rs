@@ -0,0 +1,45 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use mlb_parser::parser::{options::ParserOptions, Parser};
+
+const GAMES: [&str; 3] = ["747060", "747063", "748231"];
+
+fn parse_games(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse_input");
+    for id in GAMES {
+        let text = std::fs::read_to_string(format!("test_data/{id}.txt")).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(id), &text, |b, text| b.iter(|| {
+            let mut parser = Parser::new(false);
+            parser.parse_input(text).unwrap();
+            parser.complete().unwrap()
+        }));
+    }
+    group.finish();
+}
+
+fn parse_games_without_validation(c: &mut Criterion) {
+    let mut options = ParserOptions::default();
+    options.set_skip_validation(true);
+
+    let mut group = c.benchmark_group("parse_input_skip_validation");
+    for id in GAMES {
+        let text = std::fs::read_to_string(format!("test_data/{id}.txt")).unwrap();
+        group.bench_with_input(BenchmarkId::from_parameter(id), &text, |b, text| b.iter(|| {
+            let mut parser = Parser::with_options(options.clone());
+            parser.parse_input(text).unwrap();
+            parser.complete().unwrap()
+        }));
+    }
+    group.finish();
+}
+
+fn valid_regex(c: &mut Criterion) {
+    let text = std::fs::read_to_string("test_data/748231.txt").unwrap();
+    let cut = text.find("[INNING] 5 top").unwrap();
+
+    let mut parser = Parser::new(false);
+    parser.parse_input(&text[..cut]).unwrap();
+    c.bench_function("valid_regex", |b| b.iter(|| parser.valid_regex()));
+}
+
+criterion_group!(benches, parse_games, parse_games_without_validation, valid_regex);
+criterion_main!(benches);
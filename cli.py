@@ -0,0 +1,145 @@
+from mlb_parser import Parser, ParserOptions, get_next_valid_chars
+
+import argparse
+import sys
+import time
+
+
+def report_error(parser, text, message):
+    line = text.count("\n") + 1
+    column = len(text) - text.rfind("\n")
+    print(f"\nerror at line {line}, column {column}: {message}", file=sys.stderr)
+    print(f"expected: {', '.join(parser.expected_tokens())}", file=sys.stderr)
+
+
+def check(stream, strict):
+    """Parse a whole game at once, reporting where it stops being valid."""
+    parser = Parser.with_options(ParserOptions(strict=strict))
+    text = stream.read()
+
+    try:
+        parser.parse_input(text)
+    except ValueError as e:
+        report_error(parser, text, str(e))
+        return False
+
+    if not parser.finished:
+        report_error(parser, text, "game is incomplete or contains invalid text")
+        return False
+
+    for warning in parser.warnings:
+        print(f"warning: {warning}", file=sys.stderr)
+
+    return True
+
+
+def check_interactive(stream, strict):
+    """Feed a game to the parser one character at a time, skipping and reporting any invalid character."""
+    parser = Parser.with_options(ParserOptions(strict=strict))
+    text = ""
+    valid = True
+
+    while not parser.finished:
+        char = stream.read(1)
+        if not char:
+            report_error(parser, text, "unexpected end of input")
+            return False
+
+        valid_chars = get_next_valid_chars(parser.remaining_input(), parser.remaining_regex())
+        if char not in valid_chars:
+            report_error(parser, text, f"unexpected {char!r}")
+            valid = False
+            continue
+
+        try:
+            parser.parse_input(char)
+        except ValueError as e:
+            # the parser can't recover from an invalid game state
+            report_error(parser, text, str(e))
+            return False
+
+        text += char
+
+    return valid
+
+
+def render_scoreboard(state):
+    def base(runner):
+        return runner if runner is not None else "-"
+
+    return "\n".join([
+        f"inning {state.inning} {state.top_bottom}, {state.outs} out",
+        f"away {state.away_team_score}  home {state.home_team_score}",
+        f"1st: {base(state.first)}  2nd: {base(state.second)}  3rd: {base(state.third)}",
+        f"batter: {base(state.batter)}  pitcher: {base(state.pitcher)}",
+    ])
+
+
+def watch(stream, strict, follow):
+    """Redraw a scoreboard from the live parser state whenever a line of the game arrives."""
+    parser = Parser.with_options(ParserOptions(strict=strict))
+
+    while not parser.finished:
+        line = stream.readline()
+        if not line:
+            if not follow:
+                print("\nunexpected end of input", file=sys.stderr)
+                return False
+
+            # wait for more of the game to be written
+            time.sleep(0.5)
+            continue
+
+        try:
+            plays = parser.feed(line)
+        except ValueError as e:
+            print(f"\nerror: {e}", file=sys.stderr)
+            return False
+
+        # only redraw once a play has been completed
+        if not plays:
+            continue
+
+        # clear the terminal and move the cursor to the top left
+        print("\033[H\033[J" + render_scoreboard(parser.game_state()), flush=True)
+
+    print("final")
+    return True
+
+
+def main():
+    arg_parser = argparse.ArgumentParser(description="Check games written in the mlb-parser format.")
+    subparsers = arg_parser.add_subparsers(dest="command", required=True)
+
+    check_parser = subparsers.add_parser("check", help="check a game file, or stdin if no file is given")
+    check_parser.add_argument("file", nargs="?", type=argparse.FileType("r"), default=sys.stdin)
+    check_parser.add_argument("--interactive", action="store_true", help="check one character at a time")
+    check_parser.add_argument("--strict", action="store_true")
+
+    repl_parser = subparsers.add_parser("repl", help="check a game typed into stdin one character at a time")
+    repl_parser.add_argument("--strict", action="store_true")
+
+    watch_parser = subparsers.add_parser("watch", help="show a live scoreboard for a game file as it is written, or stdin")
+    watch_parser.add_argument("file", nargs="?", type=argparse.FileType("r"), default=sys.stdin)
+    watch_parser.add_argument("--strict", action="store_true")
+
+    args = arg_parser.parse_args()
+
+    if args.command == "watch":
+        # a file may still be being written, but stdin ends when the writer closes it
+        sys.exit(0 if watch(args.file, args.strict, follow=args.file is not sys.stdin) else 1)
+
+    if args.command == "repl":
+        valid = check_interactive(sys.stdin, args.strict)
+    elif args.interactive:
+        valid = check_interactive(args.file, args.strict)
+    else:
+        valid = check(args.file, args.strict)
+
+    if valid:
+        print("valid game")
+    sys.exit(0 if valid else 1)
+
+
+if __name__ == "__main__":
+    main()
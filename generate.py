@@ -3,13 +3,13 @@ from mlb_parser import Parser, get_next_valid_chars
 import random
 
 
-parser = Parser(False)
+parser = Parser()
 
 game = ""
 while not parser.finished:
-    full_pattern = parser.valid_regex()
-    print(repr(full_pattern))
-    valid_chars = get_next_valid_chars(game, full_pattern)
+    remaining_pattern = parser.remaining_regex()
+    print(repr(remaining_pattern))
+    valid_chars = get_next_valid_chars(parser.remaining_input(), remaining_pattern)
     print(f"\n\nValid chars: {valid_chars}")
     char = random.choice(valid_chars)
     game += char
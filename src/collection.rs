@@ -0,0 +1,610 @@
+#[cfg(feature = "parquet")]
+mod dataset;
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+#[cfg(feature = "parquet")]
+use std::path::PathBuf;
+
+use pyo3::{prelude::{pyclass, pymethods, PyResult}, exceptions::PyValueError};
+
+use crate::parser::game::{Base, Game, PlayContent, TopBottom};
+use crate::parser::scoring::charged_runs;
+#[cfg(feature = "parquet")]
+use crate::parser::game::UnitSystem;
+
+/// A team's win/loss record across a collection.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct TeamRecord {
+    #[pyo3(get)]
+    team_id: u64,
+    #[pyo3(get)]
+    games: u64,
+    #[pyo3(get)]
+    wins: u64,
+    #[pyo3(get)]
+    losses: u64,
+    #[pyo3(get)]
+    ties: u64,
+    #[pyo3(get)]
+    runs_scored: u64,
+    #[pyo3(get)]
+    runs_allowed: u64,
+}
+
+impl TeamRecord {
+    fn new(team_id: u64) -> Self {
+        Self { team_id, ..Default::default() }
+    }
+
+    fn add_result(&mut self, scored: u64, allowed: u64) {
+        self.games += 1;
+        self.runs_scored += scored;
+        self.runs_allowed += allowed;
+        match scored.cmp(&allowed) {
+            Ordering::Greater => self.wins += 1,
+            Ordering::Less => self.losses += 1,
+            Ordering::Equal => self.ties += 1,
+        }
+    }
+}
+
+#[pymethods]
+impl TeamRecord {
+    /// Return runs scored minus runs allowed.
+    pub fn run_differential(&self) -> i64 {
+        self.runs_scored as i64 - self.runs_allowed as i64
+    }
+
+    /// Return the fraction of decided games that were won.
+    pub fn win_percentage(&self) -> f64 {
+        let decided = self.wins + self.losses;
+        if decided == 0 {
+            0.0
+        } else {
+            self.wins as f64 / decided as f64
+        }
+    }
+}
+
+/// A team's place in the standings, with its record against each opponent.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct Standing {
+    #[pyo3(get)]
+    team_id: u64,
+    #[pyo3(get)]
+    record: TeamRecord,
+    /// The team's own record in its games against each opponent, keyed by the opponent's id.
+    #[pyo3(get)]
+    head_to_head: HashMap<u64, TeamRecord>,
+}
+
+/// A player's batting line across a collection, with the errors charged to them as a fielder and the runs charged to
+/// them as a pitcher.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct PlayerLine {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    games: u64,
+    #[pyo3(get)]
+    plate_appearances: u64,
+    #[pyo3(get)]
+    hits: u64,
+    #[pyo3(get)]
+    doubles: u64,
+    #[pyo3(get)]
+    triples: u64,
+    #[pyo3(get)]
+    home_runs: u64,
+    #[pyo3(get)]
+    walks: u64,
+    #[pyo3(get)]
+    strikeouts: u64,
+    #[pyo3(get)]
+    runs: u64,
+    #[pyo3(get)]
+    errors: u64,
+    #[pyo3(get)]
+    runs_allowed: u64,
+    #[pyo3(get)]
+    earned_runs: u64,
+}
+
+/// A player who appears for more than one team, either in the same game or in different games on the same date.
+/// Usually a sign that two players were mapped to the same name or ID when the games were converted.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerConflict {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    date: String,
+    /// The games the player appears in on that date, by game pk.
+    #[pyo3(get)]
+    game_pks: Vec<u64>,
+    #[pyo3(get)]
+    team_ids: Vec<u64>,
+    /// Whether the player appears for both teams within one game, rather than for different teams across games.
+    #[pyo3(get)]
+    same_game: bool,
+}
+
+/// Return the teams each player appears for in a game: on a roster, at bat or on base for the batting team, or in the
+/// field for the fielding team.
+fn player_teams(game: &Game) -> HashMap<&str, BTreeSet<u64>> {
+    let mut teams = HashMap::<&str, BTreeSet<u64>>::new();
+    for team in [&game.home_team, &game.away_team] {
+        for player in &team.players {
+            teams.entry(player.name.as_str()).or_default().insert(team.team_id);
+        }
+    }
+
+    for play in &game.plays {
+        // the home team fields in the top of the inning
+        let (batting, fielding) = match play.inning.top_bottom {
+            TopBottom::Top => (game.away_team.team_id, game.home_team.team_id),
+            TopBottom::Bottom => (game.home_team.team_id, game.away_team.team_id),
+        };
+        let content = &play.play_content;
+        let batting_players = content.batter().into_iter()
+            .chain(content.runner())
+            .chain(content.scoring_runner())
+            .chain(play.movements.iter().map(|movement| &movement.runner));
+        for name in batting_players {
+            teams.entry(name.as_str()).or_default().insert(batting);
+        }
+        let fielding_players = content.pitcher().into_iter()
+            .chain(content.catcher())
+            .chain(content.fielders())
+            .chain(content.error_by());
+        for name in fielding_players {
+            teams.entry(name.as_str()).or_default().insert(fielding);
+        }
+    }
+
+    teams
+}
+
+/// Criteria for selecting games from a collection. Unset criteria match every game.
+#[derive(Clone, Debug, Default)]
+pub struct GameFilter {
+    pub team_id: Option<u64>,
+    pub opponent_id: Option<u64>,
+    pub home: Option<bool>,
+    pub venue: Option<String>,
+    pub date_range: Option<(String, String)>,
+}
+
+impl GameFilter {
+    pub fn matches(&self, game: &Game) -> bool {
+        let (home_team_id, away_team_id) = (game.home_team.team_id, game.away_team.team_id);
+
+        // the team and opponent may be on either side, unless `home` picks one
+        let sides = match self.home {
+            Some(true) => vec![(home_team_id, away_team_id)],
+            Some(false) => vec![(away_team_id, home_team_id)],
+            None => vec![(home_team_id, away_team_id), (away_team_id, home_team_id)],
+        };
+        let teams_match = sides.iter().any(|(team_id, opponent_id)| {
+            self.team_id.is_none_or(|id| id == *team_id) && self.opponent_id.is_none_or(|id| id == *opponent_id)
+        });
+        if !teams_match {
+            return false;
+        }
+
+        if let Some(venue) = &self.venue {
+            if &game.context.venue != venue {
+                return false;
+            }
+        }
+
+        // dates are always YYYY-MM-DD, so comparing them as strings orders them correctly
+        if let Some((start, end)) = &self.date_range {
+            if &game.context.date < start || &game.context.date > end {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Many parsed games, with aggregate queries computed in Rust.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct GameCollection {
+    pub games: Vec<Game>,
+}
+
+impl GameCollection {
+    pub fn from_games(games: Vec<Game>) -> Self {
+        Self { games }
+    }
+
+    /// Return a new collection of the games matching the filter.
+    pub fn filtered(&self, filter: &GameFilter) -> Self {
+        Self::from_games(self.games.iter().filter(|game| filter.matches(game)).cloned().collect())
+    }
+
+    /// Return the final score of every game as (home, away).
+    pub fn final_scores(&self) -> Result<Vec<(u64, u64)>, String> {
+        self.games.iter()
+            .enumerate()
+            .map(|(index, game)| {
+                let state = game.replay().map_err(|e| format!("Game {}: {}", index, e))?;
+                Ok((state.home_team_score, state.away_team_score))
+            })
+            .collect()
+    }
+
+    pub fn records(&self) -> Result<HashMap<u64, TeamRecord>, String> {
+        let mut records = HashMap::new();
+        for (game, (home_score, away_score)) in self.games.iter().zip(self.final_scores()?) {
+            records.entry(game.home_team.team_id)
+                .or_insert_with(|| TeamRecord::new(game.home_team.team_id))
+                .add_result(home_score, away_score);
+            records.entry(game.away_team.team_id)
+                .or_insert_with(|| TeamRecord::new(game.away_team.team_id))
+                .add_result(away_score, home_score);
+        }
+
+        Ok(records)
+    }
+
+    /// Return every team's standing, best record first.
+    pub fn standings_table(&self) -> Result<Vec<Standing>, String> {
+        let mut head_to_head: HashMap<u64, HashMap<u64, TeamRecord>> = HashMap::new();
+        for (game, (home_score, away_score)) in self.games.iter().zip(self.final_scores()?) {
+            let (home_team_id, away_team_id) = (game.home_team.team_id, game.away_team.team_id);
+            head_to_head.entry(home_team_id).or_default()
+                .entry(away_team_id)
+                .or_insert_with(|| TeamRecord::new(home_team_id))
+                .add_result(home_score, away_score);
+            head_to_head.entry(away_team_id).or_default()
+                .entry(home_team_id)
+                .or_insert_with(|| TeamRecord::new(away_team_id))
+                .add_result(away_score, home_score);
+        }
+
+        let mut standings = self.records()?
+            .into_values()
+            .map(|record| Standing {
+                team_id: record.team_id,
+                head_to_head: head_to_head.remove(&record.team_id).unwrap_or_default(),
+                record,
+            })
+            .collect::<Vec<_>>();
+        standings.sort_by(|a, b| {
+            b.record.win_percentage().total_cmp(&a.record.win_percentage())
+                .then(b.record.run_differential().cmp(&a.record.run_differential()))
+                .then(a.team_id.cmp(&b.team_id))
+        });
+
+        Ok(standings)
+    }
+
+    pub fn lines(&self) -> HashMap<String, PlayerLine> {
+        fn line<'a>(lines: &'a mut HashMap<String, PlayerLine>, name: &str) -> &'a mut PlayerLine {
+            lines.entry(name.to_string()).or_insert_with(|| PlayerLine { name: name.to_string(), ..Default::default() })
+        }
+
+        let mut lines = HashMap::new();
+        for game in &self.games {
+            let roster = game.home_team.players.iter()
+                .chain(game.away_team.players.iter())
+                .map(|player| player.name.as_str())
+                .collect::<HashSet<_>>();
+            for name in roster {
+                line(&mut lines, name).games += 1;
+            }
+
+            for play in &game.plays {
+                if let Some(batter) = play.play_content.batter() {
+                    let batter_line = line(&mut lines, batter);
+                    batter_line.plate_appearances += 1;
+                    match play.play_content {
+                        PlayContent::Single { .. } => batter_line.hits += 1,
+                        PlayContent::Double { .. } => {
+                            batter_line.hits += 1;
+                            batter_line.doubles += 1;
+                        },
+                        PlayContent::Triple { .. } => {
+                            batter_line.hits += 1;
+                            batter_line.triples += 1;
+                        },
+                        PlayContent::HomeRun { .. } => {
+                            batter_line.hits += 1;
+                            batter_line.home_runs += 1;
+                        },
+                        PlayContent::Walk { .. } | PlayContent::IntentWalk { .. } => batter_line.walks += 1,
+                        PlayContent::Strikeout { .. } | PlayContent::StrikeoutDoublePlay { .. } => batter_line.strikeouts += 1,
+                        _ => (),
+                    }
+                }
+
+                for movement in &play.movements {
+                    if movement.to == Base::Home && !movement.out {
+                        line(&mut lines, &movement.runner).runs += 1;
+                    }
+                }
+
+                if let Some(fielder) = play.play_content.error_by() {
+                    line(&mut lines, fielder).errors += 1;
+                }
+            }
+
+            for run in charged_runs(game) {
+                let pitcher_line = line(&mut lines, &run.pitcher);
+                pitcher_line.runs_allowed += 1;
+                if run.earned {
+                    pitcher_line.earned_runs += 1;
+                }
+            }
+        }
+
+        lines
+    }
+
+    /// Return every player who appears for both teams in a game, or for different teams in games on the same date,
+    /// ordered by date and then name.
+    pub fn player_conflicts(&self) -> Vec<PlayerConflict> {
+        let mut conflicts = Vec::new();
+        // the teams each player appears for on each date, with the games they appear in for each team
+        let mut by_date = BTreeMap::<(&str, &str), BTreeMap<u64, BTreeSet<u64>>>::new();
+        for game in &self.games {
+            let (date, game_pk) = (game.context.date.as_str(), game.context.game_pk);
+            for (name, team_ids) in player_teams(game) {
+                if team_ids.len() > 1 {
+                    conflicts.push(PlayerConflict {
+                        name: name.to_string(),
+                        date: date.to_string(),
+                        game_pks: vec![game_pk],
+                        team_ids: team_ids.into_iter().collect(),
+                        same_game: true,
+                    });
+                    continue;
+                }
+
+                for team_id in team_ids {
+                    by_date.entry((date, name)).or_default().entry(team_id).or_default().insert(game_pk);
+                }
+            }
+        }
+
+        for ((date, name), teams) in by_date {
+            if teams.len() > 1 {
+                conflicts.push(PlayerConflict {
+                    name: name.to_string(),
+                    date: date.to_string(),
+                    game_pks: teams.values().flatten().copied().collect::<BTreeSet<_>>().into_iter().collect(),
+                    team_ids: teams.into_keys().collect(),
+                    same_game: false,
+                });
+            }
+        }
+        conflicts.sort_by(|a, b| (&a.date, &a.name, !a.same_game).cmp(&(&b.date, &b.name, !b.same_game)));
+
+        conflicts
+    }
+}
+
+#[pymethods]
+impl GameCollection {
+    #[new]
+    #[pyo3(signature = (games=Vec::new()))]
+    fn new(games: Vec<Game>) -> Self {
+        Self::from_games(games)
+    }
+
+    /// Add a game to the collection.
+    pub fn add(&mut self, game: Game) {
+        self.games.push(game);
+    }
+
+    fn __len__(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Return the games matching every given criterion, e.g. `filter(team_id=147, venue="Yankee Stadium")`.
+    /// `date_range` is an inclusive pair of YYYY-MM-DD dates, and `home` requires `team_id`.
+    #[pyo3(signature = (*, team_id=None, opponent_id=None, home=None, venue=None, date_range=None))]
+    pub fn filter(
+        &self,
+        team_id: Option<u64>,
+        opponent_id: Option<u64>,
+        home: Option<bool>,
+        venue: Option<String>,
+        date_range: Option<(String, String)>,
+    ) -> PyResult<GameCollection> {
+        if home.is_some() && team_id.is_none() {
+            return Err(PyValueError::new_err("Filtering by home or away requires a team_id"));
+        }
+
+        let filter = GameFilter { team_id, opponent_id, home, venue, date_range };
+        Ok(self.filtered(&filter))
+    }
+
+    /// Return the total number of plays across all games.
+    pub fn total_plays(&self) -> usize {
+        self.games.iter().map(|game| game.plays.len()).sum()
+    }
+
+    /// Return the win/loss record of every team, keyed by team ID.
+    pub fn team_records(&self) -> PyResult<HashMap<u64, TeamRecord>> {
+        self.records().map_err(PyValueError::new_err)
+    }
+
+    /// Return the standings of every team, best record first.
+    pub fn standings(&self) -> PyResult<Vec<Standing>> {
+        self.standings_table().map_err(PyValueError::new_err)
+    }
+
+    /// Return the batting, fielding and pitching line of every player, keyed by name.
+    pub fn player_lines(&self) -> HashMap<String, PlayerLine> {
+        self.lines()
+    }
+
+    /// Return every player who appears for both teams in a game, or for different teams in games on the same date.
+    /// Either is usually a sign of a bad name or ID mapping in converted data.
+    pub fn check_player_continuity(&self) -> Vec<PlayerConflict> {
+        self.player_conflicts()
+    }
+
+    /// Write `games` and `plays` Parquet datasets under `dir`, partitioned by a shared column
+    /// such as `"date"`, `"venue"` or `"home_team_id"`, or not at all if `partition_by` is `None`.
+    /// Weather is converted to `"imperial"` or `"metric"` units if `units` is given.
+    #[cfg(feature = "parquet")]
+    #[pyo3(signature = (dir, partition_by=Some("date".to_string()), units=None))]
+    pub fn write_parquet(&self, dir: PathBuf, partition_by: Option<String>, units: Option<&str>) -> PyResult<()> {
+        let units = units
+            .map(|units| units.parse::<UnitSystem>())
+            .transpose()
+            .map_err(PyValueError::new_err)?;
+        let final_scores = self.final_scores().map_err(PyValueError::new_err)?;
+
+        dataset::write_dataset(&self.games, &final_scores, &dir, partition_by.as_deref(), units).map_err(PyValueError::new_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn parse_game(input: &str) -> Game {
+        let mut parser = Parser::new(false);
+        parser.parse_input(input).unwrap();
+        parser.complete().unwrap()
+    }
+
+    #[test]
+    fn aggregate_over_games() {
+        let game = parse_game(include_str!("../test_data/748231.txt"));
+        let (home_score, away_score) = {
+            let state = game.replay().unwrap();
+            (state.home_team_score, state.away_team_score)
+        };
+        let home_team_id = game.home_team.team_id;
+        let plays = game.plays.len();
+
+        let collection = GameCollection::from_games(vec![game.clone(), game]);
+        assert_eq!(collection.total_plays(), plays * 2);
+
+        let records = collection.records().unwrap();
+        let home_record = &records[&home_team_id];
+        assert_eq!(home_record.games, 2);
+        if home_score > away_score {
+            assert_eq!(home_record.wins, 2);
+        } else if home_score < away_score {
+            assert_eq!(home_record.losses, 2);
+        }
+    }
+
+    #[test]
+    fn player_lines() {
+        let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home, Person D 1 -> home;\n[INNING] 1 top [PLAY] Strikeout [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> home [out];\n[GAME_END]";
+        let collection = GameCollection::from_games(vec![parse_game(input)]);
+
+        let lines = collection.lines();
+        let d = &lines["Person D"];
+        assert_eq!(d.plate_appearances, 2);
+        assert_eq!(d.hits, 1);
+        assert_eq!(d.strikeouts, 1);
+        assert_eq!(d.runs, 1);
+
+        let e = &lines["Person E"];
+        assert_eq!(e.home_runs, 1);
+        assert_eq!(e.runs, 1);
+
+        assert_eq!(lines["Person A"].games, 1);
+        assert_eq!(lines["Person A"].runs_allowed, 2);
+        assert_eq!(lines["Person A"].earned_runs, 2);
+
+        let records = collection.records().unwrap();
+        assert_eq!(records[&2].wins, 1);
+        assert_eq!(records[&1].losses, 1);
+    }
+
+    #[test]
+    fn standings() {
+        let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home;\n[GAME_END]";
+        let mut collection = GameCollection::from_games(vec![parse_game(input), parse_game(input)]);
+        collection.add(parse_game(&input.replace("[TEAM] 2", "[TEAM] 3")));
+
+        let standings = collection.standings_table().unwrap();
+        assert_eq!(standings.iter().map(|standing| standing.team_id).collect::<Vec<_>>(), vec![2, 3, 1]);
+
+        let first = &standings[0];
+        assert_eq!(first.record.wins, 2);
+        assert_eq!(first.record.run_differential(), 2);
+        assert_eq!(first.head_to_head[&1].wins, 2);
+        assert_eq!(first.head_to_head[&1].team_id, 2);
+
+        let last = &standings[2];
+        assert_eq!(last.record.losses, 3);
+        assert_eq!(last.record.win_percentage(), 0.0);
+        assert_eq!(last.head_to_head[&2].losses, 2);
+        assert_eq!(last.head_to_head[&3].losses, 1);
+        assert!(last.head_to_head.values().all(|record| record.team_id == 1));
+    }
+
+    #[test]
+    fn filter_games() {
+        let input = "[GAME] 0 [DATE] 2024-04-01 [VENUE] Example Park [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home;\n[GAME_END]";
+        let collection = GameCollection::from_games(vec![
+            parse_game(input),
+            parse_game(&input.replace("[TEAM] 2", "[TEAM] 3")),
+            parse_game(&input.replace("2024-04-01", "2024-05-01").replace("Example Park", "Other Park")),
+        ]);
+
+        let filter = |filter: GameFilter| collection.filtered(&filter).games.len();
+        assert_eq!(filter(GameFilter::default()), 3);
+        assert_eq!(filter(GameFilter { team_id: Some(2), ..Default::default() }), 2);
+        assert_eq!(filter(GameFilter { team_id: Some(1), opponent_id: Some(3), ..Default::default() }), 1);
+        assert_eq!(filter(GameFilter { team_id: Some(2), home: Some(true), ..Default::default() }), 0);
+        assert_eq!(filter(GameFilter { venue: Some("Other Park".to_string()), ..Default::default() }), 1);
+        assert_eq!(filter(GameFilter {
+            date_range: Some(("2024-03-01".to_string(), "2024-04-30".to_string())),
+            ..Default::default()
+        }), 2);
+    }
+
+    #[test]
+    fn player_conflicts() {
+        let input = "[GAME] 1 [DATE] 2024-04-01 [VENUE] Example Park [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home;\n[GAME_END]";
+        let collection = GameCollection::from_games(vec![
+            parse_game(input),
+            // a doubleheader for the same teams is fine
+            parse_game(&input.replace("[GAME] 1", "[GAME] 2")),
+            parse_game(&input.replace("[GAME] 1", "[GAME] 3").replace("2024-04-01", "2024-04-02")),
+        ]);
+        assert!(collection.player_conflicts().is_empty());
+
+        let collection = GameCollection::from_games(vec![
+            parse_game(&input.replace("Person E", "Person A")),
+            parse_game(&input.replace("[GAME] 1", "[GAME] 2")),
+            parse_game(&input.replace("[GAME] 1", "[GAME] 3").replace("Person E", "Person F").replace("Person A", "Person E")),
+        ]);
+        let conflicts = collection.player_conflicts();
+        assert_eq!(conflicts, vec![
+            PlayerConflict {
+                name: "Person A".to_string(),
+                date: "2024-04-01".to_string(),
+                game_pks: vec![1],
+                team_ids: vec![1, 2],
+                same_game: true,
+            },
+            PlayerConflict {
+                name: "Person E".to_string(),
+                date: "2024-04-01".to_string(),
+                game_pks: vec![2, 3],
+                team_ids: vec![1, 2],
+                same_game: false,
+            },
+        ]);
+    }
+}
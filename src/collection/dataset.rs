@@ -0,0 +1,320 @@
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::Path;
+use std::sync::Arc;
+
+use parquet::{
+    data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type},
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::parser::parse_message_type,
+};
+
+use crate::parser::game::{Game, UnitSystem};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Int,
+    Float,
+    Text,
+    OptionalText,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    Text(Option<String>),
+}
+
+impl Value {
+    /// Return the value as it appears in a partition directory name.
+    fn partition_name(&self) -> String {
+        let value = match self {
+            Value::Int(value) => value.to_string(),
+            Value::Float(value) => value.to_string(),
+            Value::Text(Some(value)) => value.clone(),
+            Value::Text(None) => "__HIVE_DEFAULT_PARTITION__".to_string(),
+        };
+
+        // escape the characters that would change the meaning of the path
+        value.chars()
+            .map(|c| match c {
+                '/' | '\\' | '=' | '%' => format!("%{:02X}", c as u32),
+                _ => c.to_string(),
+            })
+            .collect()
+    }
+}
+
+struct Table {
+    name: &'static str,
+    columns: Vec<(&'static str, ColumnType)>,
+    rows: Vec<Vec<Value>>,
+}
+
+impl Table {
+    fn games(games: &[Game], final_scores: &[(u64, u64)], units: Option<UnitSystem>) -> Self {
+        let columns = vec![
+            ("game_pk", ColumnType::Int),
+            ("date", ColumnType::Text),
+            ("venue", ColumnType::Text),
+            ("weather_condition", ColumnType::Text),
+            ("temperature", ColumnType::Float),
+            ("temperature_unit", ColumnType::Text),
+            ("wind_speed", ColumnType::Float),
+            ("wind_speed_unit", ColumnType::Text),
+            ("home_team_id", ColumnType::Int),
+            ("away_team_id", ColumnType::Int),
+            ("home_score", ColumnType::Int),
+            ("away_score", ColumnType::Int),
+            ("plays", ColumnType::Int),
+        ];
+
+        let rows = games.iter()
+            .zip(final_scores)
+            .map(|(game, (home_score, away_score))| {
+                let weather = match units {
+                    Some(units) => game.context.weather.in_units(units),
+                    None => game.context.weather.clone(),
+                };
+
+                vec![
+                    Value::Int(game.context.game_pk as i64),
+                    Value::Text(Some(game.context.date.clone())),
+                    Value::Text(Some(game.context.venue.to_string())),
+                    Value::Text(Some(weather.condition.to_string())),
+                    Value::Float(weather.temperature),
+                    Value::Text(Some(weather.temperature_unit.to_string())),
+                    Value::Float(weather.wind_speed),
+                    Value::Text(Some(weather.wind_speed_unit.to_string())),
+                    Value::Int(game.home_team.team_id as i64),
+                    Value::Int(game.away_team.team_id as i64),
+                    Value::Int(*home_score as i64),
+                    Value::Int(*away_score as i64),
+                    Value::Int(game.plays.len() as i64),
+                ]
+            })
+            .collect();
+
+        Self { name: "games", columns, rows }
+    }
+
+    fn plays(games: &[Game]) -> Self {
+        let columns = vec![
+            ("game_pk", ColumnType::Int),
+            ("date", ColumnType::Text),
+            ("venue", ColumnType::Text),
+            ("home_team_id", ColumnType::Int),
+            ("away_team_id", ColumnType::Int),
+            ("play_index", ColumnType::Int),
+            ("inning", ColumnType::Int),
+            ("top_bottom", ColumnType::Text),
+            ("play_type", ColumnType::Text),
+            ("batter", ColumnType::OptionalText),
+            ("pitcher", ColumnType::OptionalText),
+            ("movements", ColumnType::Text),
+        ];
+
+        let mut rows = Vec::new();
+        for game in games {
+            for (play_index, play) in game.plays.iter().enumerate() {
+                let movements = play.movements.iter()
+                    .map(|movement| movement.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                rows.push(vec![
+                    Value::Int(game.context.game_pk as i64),
+                    Value::Text(Some(game.context.date.clone())),
+                    Value::Text(Some(game.context.venue.to_string())),
+                    Value::Int(game.home_team.team_id as i64),
+                    Value::Int(game.away_team.team_id as i64),
+                    Value::Int(play_index as i64),
+                    Value::Int(play.inning.number as i64),
+                    Value::Text(Some(play.inning.top_bottom.to_string())),
+                    Value::Text(Some(play.play_content.play_type().to_string())),
+                    Value::Text(play.play_content.batter().map(|batter| batter.to_string())),
+                    Value::Text(play.play_content.pitcher().map(|pitcher| pitcher.to_string())),
+                    Value::Text(Some(movements)),
+                ]);
+            }
+        }
+
+        Self { name: "plays", columns, rows }
+    }
+
+    /// Return the index of the column to partition the table by.
+    fn partition_index(&self, partition_by: &str) -> Result<usize, String> {
+        self.columns.iter()
+            .position(|(name, _)| *name == partition_by)
+            .ok_or(format!("Cannot partition {} by unknown column: {}", self.name, partition_by))
+    }
+
+    /// Write the table under `dir/<name>`, split into one directory per value of the partition column.
+    fn write(&self, dir: &Path, partition_by: Option<&str>) -> Result<(), String> {
+        let table_dir = dir.join(self.name);
+
+        let Some(partition_by) = partition_by else {
+            fs::create_dir_all(&table_dir).map_err(|e| e.to_string())?;
+            return write_file(&table_dir.join("part-0.parquet"), &self.columns, &self.rows.iter().collect::<Vec<_>>());
+        };
+
+        let partition_index = self.partition_index(partition_by)?;
+
+        // the partition value is stored in the directory name, so it's left out of the files
+        let columns = self.columns.iter()
+            .enumerate()
+            .filter(|(i, _)| *i != partition_index)
+            .map(|(_, column)| *column)
+            .collect::<Vec<_>>();
+
+        let mut partitions: BTreeMap<String, Vec<Vec<Value>>> = BTreeMap::new();
+        for row in &self.rows {
+            let values = row.iter()
+                .enumerate()
+                .filter(|(i, _)| *i != partition_index)
+                .map(|(_, value)| value.clone())
+                .collect();
+            partitions.entry(row[partition_index].partition_name()).or_default().push(values);
+        }
+
+        for (partition_name, rows) in partitions {
+            let partition_dir = table_dir.join(format!("{}={}", partition_by, partition_name));
+            fs::create_dir_all(&partition_dir).map_err(|e| e.to_string())?;
+            write_file(&partition_dir.join("part-0.parquet"), &columns, &rows.iter().collect::<Vec<_>>())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_file(path: &Path, columns: &[(&'static str, ColumnType)], rows: &[&Vec<Value>]) -> Result<(), String> {
+    let fields = columns.iter()
+        .map(|(name, column_type)| match column_type {
+            ColumnType::Int => format!("REQUIRED INT64 {};", name),
+            ColumnType::Float => format!("REQUIRED DOUBLE {};", name),
+            ColumnType::Text => format!("REQUIRED BYTE_ARRAY {} (UTF8);", name),
+            ColumnType::OptionalText => format!("OPTIONAL BYTE_ARRAY {} (UTF8);", name),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let schema = parse_message_type(&format!("message schema {{ {} }}", fields)).map_err(|e| e.to_string())?;
+
+    let file = File::create(path).map_err(|e| format!("Failed to create {}: {}", path.display(), e))?;
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(file, Arc::new(schema), properties).map_err(|e| e.to_string())?;
+    let mut row_group = writer.next_row_group().map_err(|e| e.to_string())?;
+
+    for (i, (_, column_type)) in columns.iter().enumerate() {
+        let mut column = row_group.next_column()
+            .map_err(|e| e.to_string())?
+            .ok_or("Schema has fewer columns than the table")?;
+
+        match column_type {
+            ColumnType::Int => {
+                let values = rows.iter()
+                    .map(|row| match &row[i] {
+                        Value::Int(value) => *value,
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
+                column.typed::<Int64Type>().write_batch(&values, None, None).map_err(|e| e.to_string())?;
+            },
+            ColumnType::Float => {
+                let values = rows.iter()
+                    .map(|row| match &row[i] {
+                        Value::Float(value) => *value,
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
+                column.typed::<DoubleType>().write_batch(&values, None, None).map_err(|e| e.to_string())?;
+            },
+            ColumnType::Text | ColumnType::OptionalText => {
+                let mut values = Vec::new();
+                let mut definition_levels = Vec::new();
+                for row in rows {
+                    match &row[i] {
+                        Value::Text(Some(value)) => {
+                            values.push(ByteArray::from(value.as_str()));
+                            definition_levels.push(1);
+                        },
+                        Value::Text(None) => definition_levels.push(0),
+                        _ => unreachable!(),
+                    }
+                }
+
+                let definition_levels = (*column_type == ColumnType::OptionalText).then_some(definition_levels.as_slice());
+                column.typed::<ByteArrayType>().write_batch(&values, definition_levels, None).map_err(|e| e.to_string())?;
+            },
+        }
+
+        column.close().map_err(|e| e.to_string())?;
+    }
+
+    row_group.close().map_err(|e| e.to_string())?;
+    writer.close().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Write `games` and `plays` Parquet datasets under `dir`, optionally partitioned by a column they share
+/// and with the weather converted to a single unit system.
+pub fn write_dataset(
+    games: &[Game],
+    final_scores: &[(u64, u64)],
+    dir: &Path,
+    partition_by: Option<&str>,
+    units: Option<UnitSystem>,
+) -> Result<(), String> {
+    let tables = [Table::games(games, final_scores, units), Table::plays(games)];
+    // check the column is in every table before writing any, so a bad column doesn't leave a partial dataset
+    if let Some(partition_by) = partition_by {
+        for table in &tables {
+            table.partition_index(partition_by)?;
+        }
+    }
+    for table in &tables {
+        table.write(dir, partition_by)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    use super::*;
+    use crate::collection::GameCollection;
+    use crate::parser::Parser;
+
+    #[test]
+    fn write_partitioned_dataset() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+        let collection = GameCollection::from_games(vec![game.clone()]);
+        let final_scores = collection.final_scores().unwrap();
+
+        let dir = std::env::temp_dir().join(format!("mlb-parser-dataset-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        write_dataset(&collection.games, &final_scores, &dir, Some("date"), Some(UnitSystem::Metric)).unwrap();
+
+        let partition = format!("date={}", game.context.date);
+        let games_file = File::open(dir.join("games").join(&partition).join("part-0.parquet")).unwrap();
+        let games_reader = SerializedFileReader::new(games_file).unwrap();
+        assert_eq!(games_reader.metadata().file_metadata().num_rows(), 1);
+
+        let plays_file = File::open(dir.join("plays").join(&partition).join("part-0.parquet")).unwrap();
+        let plays_reader = SerializedFileReader::new(plays_file).unwrap();
+        assert_eq!(plays_reader.metadata().file_metadata().num_rows(), game.plays.len() as i64);
+        assert!(plays_reader.metadata().file_metadata().schema_descr().columns().iter().all(|column| column.name() != "date"));
+
+        assert!(write_dataset(&collection.games, &final_scores, &dir, Some("batter"), None).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+
+        // a column only the games table has is rejected before the games are written
+        assert!(write_dataset(&collection.games, &final_scores, &dir, Some("home_score"), None).is_err());
+        assert!(!dir.exists());
+    }
+}
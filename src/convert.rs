@@ -0,0 +1,170 @@
+use std::path::{Path, PathBuf};
+
+use pyo3::{prelude::{pyclass, pyfunction, Bound, PyAny, PyAnyMethods, PyResult, Python}, exceptions::PyValueError};
+
+#[cfg(feature = "parquet")]
+use crate::collection::GameCollection;
+use crate::corpus::{game_paths, parse_mapped_files_with};
+use crate::parser::export::{export_value, ExportOptions};
+use crate::parser::game::Game;
+use crate::parser::retrosheet::to_retrosheet;
+
+/// The formats games can be converted to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// `games` and `plays` datasets, partitioned by date.
+    Parquet,
+    /// One `.json` file per game.
+    Json,
+    /// One `.evn` event file per game.
+    Retrosheet,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parquet" => Ok(OutputFormat::Parquet),
+            "json" => Ok(OutputFormat::Json),
+            "retrosheet" => Ok(OutputFormat::Retrosheet),
+            _ => Err(format!("Invalid output format: {}", s)),
+        }
+    }
+}
+
+/// The outcome of `convert`: how many games were written, and why each of the others wasn't.
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConversionReport {
+    #[pyo3(get)]
+    pub converted: usize,
+    /// The path of each game file that couldn't be converted, with the reason.
+    #[pyo3(get)]
+    pub failures: Vec<(String, String)>,
+}
+
+/// Write a parsed game to `output_dir` in a per-game format, named after the file it came from.
+fn write_game(game: &Game, path: &Path, output_dir: &Path, format: OutputFormat) -> Result<(), String> {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let (text, extension) = match format {
+        OutputFormat::Json => (export_value(game, ExportOptions::default())?.to_string(), "json"),
+        OutputFormat::Retrosheet => (to_retrosheet(game), "evn"),
+        OutputFormat::Parquet => unreachable!("Parquet datasets are written once every game is parsed"),
+    };
+    let output_path = output_dir.join(format!("{}.{}", stem, extension));
+
+    std::fs::write(&output_path, text).map_err(|e| format!("{}: {}", output_path.display(), e))
+}
+
+/// Parse every game file in `paths` and write it to `output_dir`, `workers` files at a time, calling
+/// `progress(done, total)` after each batch. A file that can't be parsed or written is recorded as a failure rather
+/// than stopping the conversion.
+pub fn convert_files(
+    py: Python<'_>,
+    paths: &[PathBuf],
+    output_dir: &Path,
+    format: OutputFormat,
+    workers: usize,
+    progress: Option<&Bound<'_, PyAny>>,
+) -> PyResult<ConversionReport> {
+    std::fs::create_dir_all(output_dir).map_err(|e| PyValueError::new_err(format!("{}: {}", output_dir.display(), e)))?;
+
+    let workers = workers.max(1);
+    let mut report = ConversionReport::default();
+    let mut games = Vec::new();
+    let mut done = 0;
+    // a few files per worker in each batch, so progress is reported often without leaving workers idle
+    for batch in paths.chunks(workers * 4) {
+        let results = py.allow_threads(|| parse_mapped_files_with(batch, workers));
+        for (path, result) in batch.iter().zip(results) {
+            let written = match result {
+                Ok(game) if format == OutputFormat::Parquet => {
+                    games.push(game);
+                    Ok(())
+                },
+                Ok(game) => write_game(&game, path, output_dir, format),
+                // formatting a Python exception needs the GIL, which is held here
+                Err(e) => Err(e.to_string()),
+            };
+            match written {
+                Ok(()) => report.converted += 1,
+                Err(e) => report.failures.push((path.display().to_string(), e)),
+            }
+        }
+
+        done += batch.len();
+        if let Some(progress) = progress {
+            progress.call1((done, paths.len()))?;
+        }
+    }
+
+    if format == OutputFormat::Parquet {
+        write_parquet(games, output_dir)?;
+    }
+
+    Ok(report)
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet(games: Vec<Game>, output_dir: &Path) -> PyResult<()> {
+    GameCollection::from_games(games).write_parquet(output_dir.to_path_buf(), Some("date".to_string()), None)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_parquet(_games: Vec<Game>, _output_dir: &Path) -> PyResult<()> {
+    Err(PyValueError::new_err("Converting to Parquet requires the parquet feature"))
+}
+
+/// Convert every `.txt` game file in `input_dir` to `"parquet"`, `"json"` or `"retrosheet"` files in `output_dir`,
+/// parsing `workers` files at a time (one per CPU by default). `progress`, if given, is called with the number of files
+/// done and the total after each batch. Returns a report of the games converted and the files that failed.
+#[pyfunction(name = "convert")]
+#[pyo3(signature = (input_dir, output_dir, from_="text", to="parquet", workers=None, progress=None))]
+pub fn py_convert(
+    py: Python<'_>,
+    input_dir: PathBuf,
+    output_dir: PathBuf,
+    from_: &str,
+    to: &str,
+    workers: Option<usize>,
+    progress: Option<Bound<'_, PyAny>>,
+) -> PyResult<ConversionReport> {
+    if from_ != "text" {
+        return Err(PyValueError::new_err(format!("Invalid input format: {}", from_)));
+    }
+    let format = to.parse::<OutputFormat>().map_err(PyValueError::new_err)?;
+    let workers = workers.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+    let paths = game_paths(&input_dir).map_err(|e| PyValueError::new_err(format!("{}: {}", input_dir.display(), e)))?;
+
+    convert_files(py, &paths, &output_dir, format, workers, progress.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_to_per_game_files() {
+        pyo3::prepare_freethreaded_python();
+        let output_dir = std::env::temp_dir().join(format!("mlb-parser-convert-{}", std::process::id()));
+        let paths = ["747060", "748231", "missing"].map(|id| PathBuf::from(format!("test_data/{id}.txt")));
+
+        Python::with_gil(|py| {
+            let report = convert_files(py, &paths, &output_dir, OutputFormat::Json, 2, None).unwrap();
+            assert_eq!(report.converted, 2);
+            assert_eq!(report.failures.len(), 1);
+            assert_eq!(report.failures[0].0, "test_data/missing.txt");
+            let json = std::fs::read_to_string(output_dir.join("748231.json")).unwrap();
+            let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+            assert_eq!(value["context"]["game_pk"], 748231);
+
+            let report = convert_files(py, &paths[..2], &output_dir, OutputFormat::Retrosheet, 1, None).unwrap();
+            assert_eq!(report.converted, 2);
+            assert!(std::fs::read_to_string(output_dir.join("747060.evn")).unwrap().starts_with("id,"));
+        });
+
+        std::fs::remove_dir_all(&output_dir).unwrap();
+        assert!("csv".parse::<OutputFormat>().is_err());
+    }
+}
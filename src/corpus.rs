@@ -0,0 +1,94 @@
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use memmap2::Mmap;
+use pyo3::{prelude::PyResult, exceptions::PyValueError};
+
+use crate::parser::{game::Game, Parser};
+
+/// Parse a game file by memory-mapping it and handing the parser the whole file as one slice, rather than reading
+/// it into a string first. This isn't zero-copy: the parser copies the text into its buffer once, normalising line
+/// endings, and then consumes it in place. The file must not be modified or truncated while it's being parsed.
+pub fn parse_mapped_file(path: &Path) -> PyResult<Game> {
+    let file = File::open(path).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    // SAFETY: the map is only sound while no one else changes the file. a write would change the text behind the `&str`
+    // borrowed from it, and truncating the file makes reading the pages past its new end raise SIGBUS. game files are
+    // treated as read-only inputs, so callers must not modify or truncate them until they've been parsed
+    let mmap = unsafe { Mmap::map(&file) }.map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let input = std::str::from_utf8(&mmap).map_err(|e| PyValueError::new_err(format!("Invalid UTF-8 at byte {}", e.valid_up_to())))?;
+
+    let mut parser = Parser::new(false);
+    parser.parse_input(input)?;
+
+    parser.complete().ok_or_else(|| PyValueError::new_err("The file doesn't contain a complete game"))
+}
+
+/// Return the game files at `path`: the file itself, or every `.txt` file in a directory, sorted by name.
+pub fn game_paths(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut paths = Vec::new();
+    for entry in std::fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|extension| extension == "txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    Ok(paths)
+}
+
+/// Parse each game file with `parse_mapped_file`, splitting the files across threads. The results are in the same
+/// order as the paths.
+pub fn parse_mapped_files(paths: &[PathBuf]) -> Vec<PyResult<Game>> {
+    parse_mapped_files_with(paths, std::thread::available_parallelism().map_or(1, |n| n.get()))
+}
+
+/// Like `parse_mapped_files`, but splitting the files across at most `threads` threads.
+pub fn parse_mapped_files_with(paths: &[PathBuf], threads: usize) -> Vec<PyResult<Game>> {
+    if paths.is_empty() {
+        return Vec::new();
+    }
+
+    let chunk_size = paths.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let handles = paths.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(|path| parse_mapped_file(path)).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+
+        handles.into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_corpus_files() {
+        let paths = game_paths(Path::new("test_data")).unwrap();
+        assert_eq!(paths.len(), std::fs::read_dir("test_data").unwrap().count());
+        assert!(paths.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let paths = ["747060", "747063", "748231"].map(|id| PathBuf::from(format!("test_data/{id}.txt")));
+        let games = parse_mapped_files(&paths);
+        assert_eq!(games.len(), paths.len());
+        for (path, game) in paths.iter().zip(games) {
+            let mut parser = Parser::new(false);
+            parser.parse_input(&std::fs::read_to_string(path).unwrap()).unwrap();
+            assert_eq!(game.unwrap().plays.len(), parser.complete().unwrap().plays.len());
+        }
+    }
+
+    #[test]
+    fn single_file_is_its_own_corpus() {
+        let path = Path::new("test_data/748231.txt");
+        assert_eq!(game_paths(path).unwrap(), vec![path.to_path_buf()]);
+        assert!(parse_mapped_file(Path::new("test_data/missing.txt")).is_err());
+    }
+}
@@ -1,29 +1,250 @@
-mod parser;
+mod collection;
+mod convert;
+pub mod corpus;
+pub mod parser;
+mod simulator;
+#[cfg(feature = "testing")]
+pub mod testing;
 
+use collection::{GameCollection, PlayerConflict, PlayerLine, Standing, TeamRecord};
+use convert::ConversionReport;
 use parser::Parser;
-use pyo3::prelude::*;
+use parser::diagnostics::{Diagnostic, DiagnosticKind, ParseError, Severity};
+use parser::events::ParserEvent;
+use parser::indexed::IndexedGame;
+use parser::options::{DebugSink, ParserOptions};
+use parser::half_innings::HalfInningSummary;
+use parser::scoring::Decisions;
+use parser::state::DefensiveAlignment;
+use parser::play_by_play::PlayByPlayRow;
+use parser::validation::ValidationReport;
+use parser::venues::MLB_VENUES;
+use parser::game::{Base, Context, Extension, Game, GameBuilder, Inning, Movement, Play, PlayBuilder, PlayContent, PlayType, Player, Position, SpeedUnit, Team, TemperatureUnit, TopBottom, Weather};
+use simulator::{GameSimulator, GameState};
+use pyo3::{prelude::*, exceptions::PyValueError};
 use rzozowski::Regex;
+use std::path::PathBuf;
+use std::time::Instant;
+use strum::IntoEnumIterator;
 
-#[pyfunction]
-fn get_next_valid_chars(prefix: &str, pattern: &str) -> PyResult<Vec<char>> {
-    let mut regex = Regex::new(pattern).unwrap();
+/// Return the characters that can follow the prefix, probing only those in `alphabet` if it's given and the first 256
+/// code points otherwise.
+fn next_valid_chars(regex: &Regex, prefix: &str, alphabet: Option<&[char]>) -> Vec<char> {
+    let mut derived: Option<Regex> = None;
     for c in prefix.chars() {
-        regex = regex.derivative(c);
+        derived = Some(derived.as_ref().unwrap_or(regex).derivative(c));
+    }
+    let regex = derived.as_ref().unwrap_or(regex);
+
+    let is_valid = |c: &char| regex.derivative(*c) != Regex::Empty;
+    match alphabet {
+        Some(alphabet) => alphabet.iter().copied().filter(is_valid).collect(),
+        None => (0..=255u8).map(char::from).filter(is_valid).collect(),
+    }
+}
+
+/// Turn the characters of a string into a sorted alphabet to probe, without duplicates.
+pub(crate) fn alphabet_chars(alphabet: Option<&str>) -> Option<Vec<char>> {
+    alphabet.map(|alphabet| {
+        let mut chars = alphabet.chars().collect::<Vec<_>>();
+        chars.sort();
+        chars.dedup();
+        chars
+    })
+}
+
+/// Compute the valid next characters for each prefix, splitting the prefixes across threads.
+pub(crate) fn next_valid_chars_batch(prefixes: &[String], regex: &Regex, alphabet: Option<&[char]>) -> Vec<Vec<char>> {
+    if prefixes.is_empty() {
+        return Vec::new();
     }
 
-    let mut valid_chars = Vec::new();
-    for c in 0..=255u8 {
-        if regex.derivative(c as char) != Regex::Empty {
-            valid_chars.push(c as char);
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let chunk_size = prefixes.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let handles = prefixes.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || {
+                chunk.iter().map(|prefix| next_valid_chars(regex, prefix, alphabet)).collect::<Vec<_>>()
+            }))
+            .collect::<Vec<_>>();
+
+        handles.into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/// Return the characters that can follow `prefix` under `pattern`. If `alphabet` is given, e.g. every character a
+/// tokenizer can produce, only its characters are checked, which is faster and covers characters past the first 256.
+#[pyfunction]
+#[pyo3(signature = (prefix, pattern, alphabet=None))]
+fn get_next_valid_chars(prefix: &str, pattern: &str, alphabet: Option<&str>) -> PyResult<Vec<char>> {
+    let regex = compile_pattern(pattern)?;
+    Ok(next_valid_chars(&regex, prefix, alphabet_chars(alphabet).as_deref()))
+}
+
+/// Like `get_next_valid_chars`, but for many prefixes at once, e.g. every hypothesis in a beam search.
+#[pyfunction]
+#[pyo3(signature = (prefixes, pattern, alphabet=None))]
+fn get_next_valid_chars_batch(py: Python<'_>, prefixes: Vec<String>, pattern: &str, alphabet: Option<&str>) -> PyResult<Vec<Vec<char>>> {
+    // compiled once, and shared by every thread
+    let regex = compile_pattern(pattern)?;
+    let alphabet = alphabet_chars(alphabet);
+    Ok(py.allow_threads(|| next_valid_chars_batch(&prefixes, &regex, alphabet.as_deref())))
+}
+
+/// Compile a pattern given from Python, raising `ValueError` if it isn't a valid regex.
+pub(crate) fn compile_pattern(pattern: &str) -> PyResult<Regex> {
+    Regex::new(pattern).map_err(|e| PyValueError::new_err(format!("Invalid pattern: {}", e)))
+}
+
+/// Parse `text` as a whole game `iterations` times, returning the mean number of seconds per parse.
+/// Used to measure parsing speed on your own games, e.g. to compare releases.
+#[pyfunction]
+fn bench_parse(py: Python<'_>, text: &str, iterations: u32) -> PyResult<f64> {
+    if iterations == 0 {
+        return Err(PyValueError::new_err("iterations must be at least 1"));
+    }
+
+    py.allow_threads(|| {
+        let start = Instant::now();
+        for _ in 0..iterations {
+            let mut parser = Parser::new(false);
+            parser.parse_input(text)?;
+            if parser.complete().is_none() {
+                return Err(PyValueError::new_err("text is not a complete game"));
+            }
         }
+
+        Ok(start.elapsed().as_secs_f64() / iterations as f64)
+    })
+}
+
+/// Check that `text` is a complete, valid game without keeping it. Same as `Parser.validate`.
+#[pyfunction]
+#[pyo3(signature = (text, options=None))]
+fn validate_game_text(py: Python<'_>, text: &str, options: Option<ParserOptions>) -> ValidationReport {
+    Parser::validate(py, text, options)
+}
+
+/// Parse the game file at `path`, or every `.txt` game file in the directory at `path`, memory-mapping each file
+/// instead of reading it into a string. The parser still copies each file's text into its buffer once, and the files
+/// must not be modified or truncated while they're being parsed. Files are parsed in parallel and collected in name
+/// order.
+#[pyfunction]
+fn parse_path_mmap(py: Python<'_>, path: PathBuf) -> PyResult<GameCollection> {
+    let paths = corpus::game_paths(&path).map_err(|e| PyValueError::new_err(format!("{}: {}", path.display(), e)))?;
+    let results = py.allow_threads(|| corpus::parse_mapped_files(&paths));
+
+    let mut games = Vec::new();
+    for (path, result) in paths.iter().zip(results) {
+        games.push(result.map_err(|e| PyValueError::new_err(format!("{}: {}", path.display(), e)))?);
     }
-    Ok(valid_chars)
+
+    Ok(GameCollection::from_games(games))
+}
+
+/// Return the names of recent MLB venues, for use as a parser's known-venue registry.
+#[pyfunction]
+fn mlb_venues() -> Vec<String> {
+    MLB_VENUES.iter().map(|venue| venue.to_string()).collect()
 }
 
 #[pymodule]
 fn mlb_parser(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Parser>()?;
+    m.add_class::<ParserEvent>()?;
+    m.add_class::<ParserOptions>()?;
+    m.add_class::<ValidationReport>()?;
+    m.add_class::<Diagnostic>()?;
+    m.add_class::<DiagnosticKind>()?;
+    m.add_class::<Severity>()?;
+    m.add_class::<ParseError>()?;
+    m.add_class::<DebugSink>()?;
+    m.add_class::<GameBuilder>()?;
+    m.add_class::<PlayBuilder>()?;
+    m.add_class::<Context>()?;
+    m.add_class::<Weather>()?;
+    m.add_class::<TemperatureUnit>()?;
+    m.add_class::<SpeedUnit>()?;
+    m.add_class::<Game>()?;
+    m.add_class::<Team>()?;
+    m.add_class::<Player>()?;
+    m.add_class::<IndexedGame>()?;
+    m.add_class::<Decisions>()?;
+    m.add_class::<HalfInningSummary>()?;
+    m.add_class::<PlayByPlayRow>()?;
+    m.add_class::<Play>()?;
+    m.add_class::<PlayContent>()?;
+    // expose each play content variant as a top-level class, e.g. `mlb_parser.Single`
+    let play_content = m.getattr("PlayContent")?;
+    for play_type in PlayType::iter() {
+        let name = format!("{:?}", play_type);
+        m.add(name.as_str(), play_content.getattr(name.as_str())?)?;
+    }
+    m.add_class::<Inning>()?;
+    m.add_class::<TopBottom>()?;
+    m.add_class::<Base>()?;
+    m.add_class::<PlayType>()?;
+    m.add_class::<Position>()?;
+    m.add_class::<Movement>()?;
+    m.add_class::<Extension>()?;
+    m.add_class::<GameSimulator>()?;
+    m.add_class::<GameState>()?;
+    m.add_class::<DefensiveAlignment>()?;
+    m.add_class::<GameCollection>()?;
+    m.add_class::<TeamRecord>()?;
+    m.add_class::<Standing>()?;
+    m.add_class::<PlayerLine>()?;
+    m.add_class::<PlayerConflict>()?;
+    m.add_class::<ConversionReport>()?;
     m.add_function(wrap_pyfunction!(get_next_valid_chars, m)?)?;
+    m.add_function(wrap_pyfunction!(get_next_valid_chars_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(mlb_venues, m)?)?;
+    m.add_function(wrap_pyfunction!(bench_parse, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_path_mmap, m)?)?;
+    m.add_function(wrap_pyfunction!(convert::py_convert, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_game_text, m)?)?;
+    m.add_function(wrap_pyfunction!(parser::canonical::py_canonicalize, m)?)?;
+    m.add_function(wrap_pyfunction!(parser::spec::py_format_spec, m)?)?;
+    #[cfg(feature = "testing")]
+    {
+        m.add_function(wrap_pyfunction!(testing::py_random_game_text, m)?)?;
+        m.add_function(wrap_pyfunction!(testing::py_random_game, m)?)?;
+    }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_matches_single_prefixes() {
+        let pattern = "abc|abd|b+";
+        let prefixes = ["", "a", "ab", "b", "bb", "c"].map(String::from).to_vec();
+        let regex = Regex::new(pattern).unwrap();
+
+        let batch = next_valid_chars_batch(&prefixes, &regex, None);
+        assert_eq!(batch.len(), prefixes.len());
+        for (prefix, valid_chars) in prefixes.iter().zip(&batch) {
+            assert_eq!(valid_chars, &next_valid_chars(&regex, prefix, None));
+        }
+    }
+
+    #[test]
+    fn alphabets_limit_the_chars_probed() {
+        let pattern = "abc|abd|b+";
+        let regex = Regex::new(pattern).unwrap();
+        let alphabet = alphabet_chars(Some("dcbaéd")).unwrap();
+        assert_eq!(alphabet, vec!['a', 'b', 'c', 'd', 'é']);
+
+        for prefix in ["", "a", "ab", "b"] {
+            let expected = next_valid_chars(&regex, prefix, None).into_iter()
+                .filter(|c| alphabet.contains(c))
+                .collect::<Vec<_>>();
+            assert_eq!(next_valid_chars(&regex, prefix, Some(&alphabet)), expected);
+        }
+    }
+}
@@ -1,10 +1,40 @@
-mod game;
-
-use std::collections::HashSet;
-
-use game::{Base, BaseComparison, Game, GameBuilder, Inning, Movement, PlayType, Player, Position, TopBottom};
+pub mod canonical;
+pub mod diagnostics;
+pub mod events;
+pub mod export;
+pub mod game;
+pub mod gumbo;
+pub mod half_innings;
+pub mod indexed;
+pub mod input_buffer;
+pub mod options;
+#[cfg(feature = "perf-stats")]
+pub mod perf;
+pub mod play_by_play;
+pub mod play_list;
+pub mod retrosheet;
+pub mod scoring;
+pub mod small_string;
+pub mod spec;
+pub mod state;
+pub mod validation;
+pub mod venues;
+pub mod visitor;
+
+use game::{Base, Extension, Game, GameBuilder, Inning, Play, PlayType, Player, Position, SpeedUnit, TemperatureUnit, TopBottom, Weather, WeatherCondition};
+use diagnostics::{Diagnostic, DiagnosticKind, ParseError};
+use events::ParserEvent;
+use input_buffer::InputBuffer;
+use options::{DebugSink, ParserOptions};
+use small_string::SmallString;
+use state::{LiveGameState, PinchRunners, RunnerPositions};
+use validation::ValidationReport;
+use venues::VenueRegistry;
+use crate::simulator::GameState;
 use once_cell::sync::Lazy;
-use pyo3::{prelude::{pyclass, pymethods, PyResult}, exceptions::PyValueError};
+use std::collections::HashSet;
+use std::sync::Arc;
+use pyo3::{prelude::{pyclass, pymethods, Bound, PyAnyMethods, PyRef, PyResult, Python}, types::PyDict};
 use fancy_regex::Regex;
 use strum::IntoEnumIterator;
 
@@ -43,6 +73,7 @@ enum MovementsSection {
     StartBase,
     Arrow,
     EndBase,
+    OnError,
     Out,
     CommaSpace,
     MovementEnd,
@@ -52,6 +83,7 @@ enum MovementsSection {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum PlaySection {
     GameStart(),
+    Overturned(),
     Inning(),
     Play(),
     Base(),
@@ -61,7 +93,10 @@ enum PlaySection {
     Fielders(FieldersSection),
     Runner(),
     ScoringRunner(),
+    ErrorBy(),
+    Position(),
     Movements(MovementsSection),
+    Meta(),
     PlayEnd(),
     GameEnd(),
 }
@@ -74,28 +109,106 @@ enum GameSection {
     Plays(PlaySection),
 }
 
+impl GameSection {
+    /// Describe the token that starts this section, for error messages.
+    fn expected_token(&self) -> String {
+        match self {
+            GameSection::Context(ContextSection::Game) => "[GAME]",
+            GameSection::Context(ContextSection::Date) => "[DATE]",
+            GameSection::Context(ContextSection::Venue) => "[VENUE]",
+            GameSection::Context(ContextSection::Weather) => "[WEATHER]",
+            GameSection::HomeTeam(TeamSection::Team) | GameSection::AwayTeam(TeamSection::Team) => "[TEAM]",
+            GameSection::HomeTeam(TeamSection::Player) | GameSection::AwayTeam(TeamSection::Player) => "a player position tag, e.g. [PITCHER]",
+            GameSection::Plays(play_section) => match play_section {
+                PlaySection::GameStart() => PLAY_SECTION_GAME_START,
+                PlaySection::Overturned() => PLAY_SECTION_OVERTURNED,
+                PlaySection::Inning() => "[INNING]",
+                PlaySection::Play() => "[PLAY]",
+                PlaySection::Base() => "[BASE]",
+                PlaySection::Batter() => "[BATTER]",
+                PlaySection::Pitcher() => "[PITCHER]",
+                PlaySection::Catcher() => "[CATCHER]",
+                PlaySection::Fielders(FieldersSection::Tag) => PLAY_SECTION_FIELDERS_TAG,
+                PlaySection::Fielders(FieldersSection::Name) => "a fielder's name",
+                PlaySection::Fielders(FieldersSection::CommaSpace) | PlaySection::Movements(MovementsSection::CommaSpace) => COMMA_SPACE,
+                PlaySection::Runner() => "[RUNNER]",
+                PlaySection::ScoringRunner() => "[SCORING_RUNNER]",
+                PlaySection::ErrorBy() => "[ERROR_BY]",
+                PlaySection::Position() => "[POSITION]",
+                PlaySection::Movements(MovementsSection::Tag) => PLAY_SECTION_MOVEMENTS_TAG,
+                PlaySection::Movements(MovementsSection::Name) => "a runner's name",
+                PlaySection::Movements(MovementsSection::StartBase) => "a starting base",
+                PlaySection::Movements(MovementsSection::Arrow) => PLAY_SECTION_ARROW,
+                PlaySection::Movements(MovementsSection::EndBase) => "an ending base",
+                PlaySection::Movements(MovementsSection::OnError) => PLAY_SECTION_ON_ERROR,
+                PlaySection::Movements(MovementsSection::Out) => PLAY_SECTION_OUT,
+                PlaySection::Movements(MovementsSection::MovementEnd) => "the end of a movement",
+                PlaySection::Meta() => "[META]",
+                PlaySection::PlayEnd() => PLAY_SECTION_PLAY_END,
+                PlaySection::GameEnd() => PLAY_SECTION_GAME_END,
+            },
+        }.to_string()
+    }
+
+    /// Whether this section is a tag followed by a name, which can only be matched once the text after it has arrived.
+    fn ends_with_name(&self) -> bool {
+        matches!(
+            self,
+            GameSection::Context(ContextSection::Venue)
+            | GameSection::HomeTeam(TeamSection::Player)
+            | GameSection::AwayTeam(TeamSection::Player)
+            | GameSection::Plays(PlaySection::Batter() | PlaySection::Pitcher() | PlaySection::Catcher() | PlaySection::Runner() | PlaySection::ScoringRunner() | PlaySection::ErrorBy()),
+        )
+    }
+}
+
+/// Whether the buffer holds a `[`, `;` or line break after its first character, which is where a name section ends.
+fn has_name_terminator(buffer: &str) -> bool {
+    memchr::memchr3(b'[', b';', b'\n', buffer.as_bytes().get(1..).unwrap_or_default()).is_some()
+}
+
 const BASE_NAME: &str = r" ?(1|2|3|4|home) ?";
 static BASE_NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
     r"^({})",
     BASE_NAME,
 ).as_str()).unwrap());
 const PLAYER_NAME: &str = r"[a-zA-ZÀ-ÖØ-öø-ÿ.'\- ]+";
-static PLAYER_NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
+pub(crate) static PLAYER_NAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
     r"^{}",
     PLAYER_NAME,
 ).as_str()).unwrap());
+// `[out]`, optionally with the base where the out was made and the fielders credited with it, e.g. `[out at 2 6-4]`
+const MOVEMENT_OUT: &str = r"\[out( at (?P<base>1|2|3|4|home))?( (?P<credits>[1-9](-[1-9])*))?\]";
+static MOVEMENT_OUT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(&format!("^{}", MOVEMENT_OUT)).unwrap());
 static PLAYER_NAME_BASE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
     r"^({}?)(?= ?({})\b)",
     PLAYER_NAME,
     BASE_NAME,
 ).as_str()).unwrap());
 
-static CONTEXT_SECTION_GAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[GAME\] (?P<game_pk>\d{1,6})").unwrap());
-static CONTEXT_SECTION_DATE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[DATE\] (?P<date>\d{4}-\d{2}-\d{2})").unwrap());
-static CONTEXT_SECTION_VENUE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[VENUE\] (?P<venue>[a-zA-ZÀ-ÖØ-öø-ÿ ]+)").unwrap());
-static CONTEXT_SECTION_WEATHER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[WEATHER\] (?P<weather>[a-zA-ZÀ-ÖØ-öø-ÿ ]+) (?P<temperature>\d{1,3}) (?P<wind_speed>\d{1,3})").unwrap());
+// wide enough for future game pks and minor-league team ids
+const GAME_PK_MAX_DIGITS: usize = 9;
+const TEAM_ID_MAX_DIGITS: usize = 6;
+
+pub(crate) static CONTEXT_SECTION_GAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
+    r"^\[GAME\] (?P<game_pk>\d{{1,{}}})",
+    GAME_PK_MAX_DIGITS,
+).as_str()).unwrap());
+pub(crate) static CONTEXT_SECTION_DATE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[DATE\] (?P<date>\d{4}-\d{2}-\d{2})").unwrap());
+// letters, digits, spaces and the punctuation found in real stadium names, e.g. "George M. Steinbrenner Field"
+const VENUE_NAME_CHARS: &str = r"a-zA-ZÀ-ÖØ-öø-ÿ0-9&.,'\- ";
+pub(crate) static CONTEXT_SECTION_VENUE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
+    r"^\[VENUE\] (?P<venue>[{}]+)",
+    VENUE_NAME_CHARS,
+).as_str()).unwrap());
+pub(crate) static CONTEXT_SECTION_WEATHER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[WEATHER\] (?P<weather>[a-zA-ZÀ-ÖØ-öø-ÿ ]+) (?P<temperature>-?\d{1,3}(\.\d+)?)(?P<temperature_unit>F|C)? (?P<wind_speed>\d{1,3}(\.\d+)?)(?P<wind_speed_unit>mph|kph)?").unwrap());
+// text that could still extend a weather section that looks complete, e.g. "5" -> "5.5" or "5mph"
+const WEATHER_SUFFIXES: [&str; 3] = [".", "mph", "kph"];
 
-static TEAM_SECTION_TEAM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[TEAM\] (?P<team_id>\d{1,3})").unwrap());
+pub(crate) static TEAM_SECTION_TEAM_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
+    r"^\[TEAM\] (?P<team_id>\d{{1,{}}})",
+    TEAM_ID_MAX_DIGITS,
+).as_str()).unwrap());
 static ALL_POSITIONS: Lazy<String> = Lazy::new(|| {
     let mut positions = Vec::new();
     for position in Position::iter() {
@@ -104,7 +217,7 @@ static ALL_POSITIONS: Lazy<String> = Lazy::new(|| {
 
     positions.join("|")
 });
-static TEAM_SECTION_PLAYER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
+pub(crate) static TEAM_SECTION_PLAYER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
     r"^\[(?P<position>{})\] (?P<player_name>{})",
     ALL_POSITIONS.as_str(),
     PLAYER_NAME,
@@ -150,156 +263,229 @@ static PLAY_SECTION_SCORING_RUNNER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(
     r"^\[SCORING_RUNNER\] (?P<scoring_runner>{})",
     PLAYER_NAME,
 ).as_str()).unwrap());
+static PLAY_SECTION_ERROR_BY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
+    r"^\[ERROR_BY\] (?P<error_by>{})",
+    PLAYER_NAME,
+).as_str()).unwrap());
+// the number of the position a fielder takes over, from 1 for the pitcher to 9 for right field
+static PLAY_SECTION_POSITION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[POSITION\] (?P<position>[1-9])").unwrap());
 
 const PLAY_SECTION_MOVEMENTS_TAG: &str = "[MOVEMENTS]";
 const PLAY_SECTION_ARROW: &str = "->";
+const PLAY_SECTION_ON_ERROR: &str = "[on error]";
 const PLAY_SECTION_OUT: &str = "[out]";
+// custom annotations after a play's movements, e.g. `[META] confidence=0.92 source=model-v3`
+const META_KEY: &str = r"[A-Za-z_][A-Za-z0-9_.\-]*";
+const META_VALUE: &str = r"[^\s\[\];=]+";
+static PLAY_SECTION_META_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(format!(
+    r"^\[META\](?P<pairs>( {}={})+)",
+    META_KEY,
+    META_VALUE,
+).as_str()).unwrap());
+const PLAY_SECTION_OVERTURNED: &str = "[OVERTURNED]";
 const PLAY_SECTION_PLAY_END: &str = ";";
 const PLAY_SECTION_GAME_END: &str = "[GAME_END]";
 
-static INITIAL_NEWLINES_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\n+").unwrap());
-
-#[derive(Clone, Debug)]
-struct RunnerPositions {
-    pub home: Option<String>,
-    pub first: Option<String>,
-    pub second: Option<String>,
-    pub third: Option<String>,
-}
-
-impl RunnerPositions {
-    pub fn empty() -> Self {
-        Self {
-            home: None,
-            first: None,
-            second: None,
-            third: None,
-        }
-    }
-
-    /// Group any chains of movements by the same runner into a single movement.
-    fn simplify_movements(&self, movements: &Vec<Movement>) -> Vec<Movement> {
-        let runners = HashSet::<String>::from_iter(movements.iter().map(|m| m.runner.clone()));
-        // println!("runners: {:#?}", runners);
-
-        let mut simplified_movements = Vec::new();
-        for runner in runners {
-            // println!("runner: {:#?}", runner);
+// any tag, used to keep tags the parser doesn't recognise when they're allowed
+static UNKNOWN_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\[(?P<tag>[A-Z][A-Z0-9_]*)\](?P<value>[^\[\r\n;]*)").unwrap());
+static KNOWN_TAGS: Lazy<Vec<String>> = Lazy::new(|| {
+    let mut tags = [
+        "GAME", "DATE", "VENUE", "WEATHER", "TEAM", "GAME_START", "INNING", "PLAY", "BASE", "BATTER",
+        "PITCHER", "CATCHER", "FIELDERS", "RUNNER", "SCORING_RUNNER", "ERROR_BY", "POSITION", "MOVEMENTS", "META", "OVERTURNED", "GAME_END",
+    ].map(String::from).to_vec();
+    tags.extend(Position::iter().map(|position| position.to_string()));
 
-            let froms = movements.iter().filter(|m| m.runner == runner).map(|m| m.from).collect::<Vec<_>>();
-            let tos = movements.iter().filter(|m| m.runner == runner).map(|m| m.to).collect::<Vec<_>>();
+    tags
+});
 
-            let from = froms.iter().min_by(|a, b| a.compare(b, BaseComparison::From)).unwrap();
-            let to = tos.iter().max_by(|a, b| a.compare(b, BaseComparison::To)).unwrap();
+// lines may end with "\r\n" as well as "\n"
+const LINE_BREAK: &str = "\r?\n";
 
-            let out = movements.iter().any(|m| m.runner == runner && m.out);
+/// Check that a whole section of text is accepted by the regex for that section.
+pub(crate) fn section_matches(regex: &Regex, section: &str) -> bool {
+    matches!(regex.find(section), Ok(Some(m)) if m.end() == section.len())
+}
 
-            simplified_movements.push(Movement { runner, from: *from, to: *to, out });
+/// Return the text every match of a regex starts with, e.g. `"[INNING] "` for `^\[INNING\] (?P<number>\d{1,2})`.
+fn literal_prefix(pattern: &str) -> String {
+    let mut prefix = String::new();
+    let mut chars = pattern.trim_start_matches('^').chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // an escaped letter or digit is a class such as `\d`, not a literal
+            '\\' => match chars.next() {
+                Some(escaped) if !escaped.is_ascii_alphanumeric() => prefix.push(escaped),
+                _ => break,
+            },
+            '(' | ')' | '[' | ']' | '{' | '}' | '.' | '*' | '+' | '?' | '|' | '$' => break,
+            _ => prefix.push(c),
         }
-
-        simplified_movements
     }
 
-    pub fn process_movements(&mut self, movements: &Vec<Movement>, pinch_runners: &Vec<String>) -> Result<(), String> {
-        let movements = self.simplify_movements(movements);
-        // println!("movements: {:#?}", movements);
-
-        let mut new_runner_positions = self.clone();
-        // println!("movements: {:#?}", movements);
-        for movement in movements {
-            // check the bases are in the correct order
-            match (movement.from.clone(), movement.to.clone()) {
-                (Base::Third, Base::Second) => return Err("Cannot move runner from third to second".to_string()),
-                (Base::Third, Base::First) => return Err("Cannot move runner from third to first".to_string()),
-                (Base::Second, Base::First) => return Err("Cannot move runner from second to first".to_string()),
-                _ => (),
-            }
-
-            // check the runner does exist on the starting base, or that it is a pinch runner
-            // println!("movement: {:#?}", movement);
-            match movement.from {
-                Base::First => match &self.first {
-                    Some(runner) => if &movement.runner != runner && !pinch_runners.contains(&movement.runner) {
-                        return Err(format!("Runner {} is not on first base and is not a pinch runner", movement.runner));
-                    },
-                    None => return Err("No runner is on first base".to_string()),
-                },
-                Base::Second => match &self.second {
-                    Some(runner) => if &movement.runner != runner && !pinch_runners.contains(&movement.runner) {
-                        return Err(format!("Runner {} is not on second base and is not a pinch runner", movement.runner));
-                    },
-                    None => return Err("No runner is on second base".to_string()),
-                },
-                Base::Third => match &self.third {
-                    Some(runner) => if &movement.runner != runner && !pinch_runners.contains(&movement.runner) {
-                        return Err(format!("Runner {} is not on third base and is not a pinch runner", movement.runner));
-                    },
-                    None => return Err("No runner is on third base".to_string()),
-                },
-                Base::Home => (),
-            }
+    prefix
+}
 
-            // if the runner is not out, move the runner to the new base
-            if !movement.out {
-                match movement.to {
-                    Base::First => new_runner_positions.first = Some(movement.runner.clone()),
-                    Base::Second => new_runner_positions.second = Some(movement.runner.clone()),
-                    Base::Third => new_runner_positions.third = Some(movement.runner.clone()),
-                    Base::Home => new_runner_positions.home = Some(movement.runner.clone()),
-                }
-            }
+/// Escape the characters in a player name that have a special meaning in a regex, e.g. the `.` in "J.D. Martinez".
+fn escape_regex(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if r"\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
         }
-
-        // update the runner positions
-        *self = new_runner_positions;
-        // println!("runner positions: {:#?}", self);
-
-        Ok(())
+        escaped.push(c);
     }
-}
 
-struct LiveGameState {
-    pub runner_positions: RunnerPositions,
-    pub inning: Inning,
-    pub home_team_score: u64,
-    pub away_team_score: u64,
+    escaped
 }
 
-impl LiveGameState {
-    pub fn new() -> Self {
-        Self {
-            runner_positions: RunnerPositions::empty(),
-            inning: Inning { number: 1, top_bottom: TopBottom::Top },
-            home_team_score: 0,
-            away_team_score: 0,
-        }
-    }
+/// Show the bytes around `index` in hex, with the byte at `index` in brackets.
+fn hex_window(bytes: &[u8], index: usize) -> String {
+    const WINDOW: usize = 8;
+
+    let start = index.saturating_sub(WINDOW);
+    let end = (index + WINDOW + 1).min(bytes.len());
+    bytes[start..end].iter()
+        .enumerate()
+        .map(|(i, byte)| if start + i == index {
+            format!("[{:02x}]", byte)
+        } else {
+            format!("{:02x}", byte)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
 }
 
 #[pyclass]
+#[derive(Clone)]
 pub struct Parser {
-    input_buffer: String,
+    input_buffer: InputBuffer,
     possible_sections: Vec<GameSection>,
     game_builder: GameBuilder,
     #[pyo3(get)]
     finished: bool,
-    print_debug: bool,
-    live_game_state: LiveGameState,
-    pinch_runners: Vec<String>,
+    #[pyo3(get)]
+    options: ParserOptions,
+    // built from `options.venues`
+    venue_registry: Option<Arc<VenueRegistry>>,
+    // undeclared players already reported, so each is only reported once
+    undeclared_players: HashSet<SmallString>,
+    pub(crate) live_game_state: LiveGameState,
+    pinch_runners: PinchRunners,
+    // bytes of input received so far, for error offsets
+    bytes_received: usize,
+    // bytes removed from the front of the buffer so far, as the buffer counts them after normalising the input
+    consumed_bytes: usize,
+    // (buffer offset, input offset) wherever normalising the input shifted one against the other
+    source_offsets: Vec<(usize, usize)>,
+    // the buffer offset where the play in progress started
+    play_start: Option<usize>,
+    // the start of a UTF-8 character split across calls to `parse_bytes`
+    pending_bytes: Vec<u8>,
+    // events not yet drained by `poll_events`
+    events: Vec<ParserEvent>,
+    #[cfg(feature = "perf-stats")]
+    perf_stats: perf::PerfStats,
 }
 
 impl Parser {
+    pub fn new(print_debug: bool) -> Self {
+        // the whole game is kept for `remaining_input` until its first play ends
+        let mut input_buffer = InputBuffer::new();
+        input_buffer.keep_from(0);
+
+        Self {
+            input_buffer,
+            possible_sections: vec![GameSection::Context(ContextSection::Game)],
+            game_builder: GameBuilder::new(),
+            finished: false,
+            options: ParserOptions { debug_sink: print_debug.then_some(DebugSink::Stdout), ..ParserOptions::default() },
+            venue_registry: None,
+            undeclared_players: HashSet::new(),
+            live_game_state: LiveGameState::new(),
+            pinch_runners: PinchRunners::default(),
+            bytes_received: 0,
+            consumed_bytes: 0,
+            source_offsets: Vec::new(),
+            play_start: None,
+            pending_bytes: Vec::new(),
+            events: Vec::new(),
+            #[cfg(feature = "perf-stats")]
+            perf_stats: perf::PerfStats::default(),
+        }
+    }
+
     fn print_debug_message(&self) {
-        println!("possible_sections: {:#?}", self.possible_sections);
-        println!("input_buffer.take(100): {:?}", self.input_buffer.chars().take(100).collect::<String>());
+        let message = format!(
+            "possible_sections: {:#?}\ninput_buffer.take(100): {:?}",
+            self.possible_sections,
+            self.input_buffer.chars().take(100).collect::<String>(),
+        );
         // println!("movement_builder: {:#?}\n", self.game_builder.play_builder.movement_builder);
+
+        match self.options.debug_sink {
+            Some(DebugSink::Stdout) => println!("{}", message),
+            Some(DebugSink::Stderr) => eprintln!("{}", message),
+            None => {},
+        }
+    }
+
+    /// Add input to the buffer, remembering where each character came from in the input. Line endings become `"\n"`,
+    /// newlines at the start of the input are dropped, and with lenient whitespace every run of whitespace, even across
+    /// chunks, is collapsed to a single space, dropping leading whitespace as it would be when the previous section was
+    /// consumed.
+    fn push_input(&mut self, input: &str) {
+        self.input_buffer.compact();
+        let input_start = self.bytes_received - input.len();
+        // a "\r\n" split between this input and the last
+        if input.starts_with('\n') && self.input_buffer.ends_with('\r') {
+            self.input_buffer.pop();
+        }
+
+        for (index, c) in input.char_indices() {
+            if c == '\r' && input[index + 1..].starts_with('\n') {
+                continue;
+            }
+
+            let c = match c.is_whitespace() && self.options.lenient_whitespace {
+                true if self.input_buffer.is_empty() || self.input_buffer.ends_with(' ') => continue,
+                true => ' ',
+                false => c,
+            };
+            let offset = self.consumed_bytes + self.input_buffer.len();
+            if self.source_offset(offset) != input_start + index {
+                self.source_offsets.push((offset, input_start + index));
+            }
+            self.input_buffer.push(c);
+            // a line break with nothing before it to parse would hold up the next section, so it's consumed straight
+            // away, though `remaining_input` keeps it
+            if c == '\n' && &*self.input_buffer == "\n" {
+                self.consume_input(1);
+            }
+        }
+    }
+
+    /// Return the offset in the input of a character at an offset in the buffer, counting from the start of the game.
+    fn source_offset(&self, offset: usize) -> usize {
+        let index = self.source_offsets.partition_point(|(buffer_offset, _)| *buffer_offset <= offset);
+        match index.checked_sub(1).map(|index| self.source_offsets[index]) {
+            Some((buffer_offset, input_offset)) => input_offset + offset - buffer_offset,
+            None => offset,
+        }
+    }
+
+    /// Record the input offsets of the play in progress, which ends `end` bytes into the buffer.
+    fn set_play_span(&mut self, end: usize) {
+        if let Some(start) = self.play_start.take() {
+            let end = self.consumed_bytes + end;
+            // the end of the play's last character, rather than the start of whatever follows it
+            self.game_builder.play_builder.source_span = Some((self.source_offset(start), self.source_offset(end - 1) + 1));
+        }
     }
 
     fn consume_input(&mut self, index: usize) {
-        self.input_buffer = self.input_buffer
-            .split_off(index)
-            .trim_start()
-            .to_string();
+        let length = self.input_buffer.len();
+        self.input_buffer.consume(index);
+        self.consumed_bytes += length - self.input_buffer.len();
     }
 
     fn parse_context_section(&mut self, context_section: ContextSection) -> PyResult<bool> {
@@ -343,12 +529,17 @@ impl Parser {
                 if let Ok(Some(captures)) = captures {
                     let venue_match = captures.name("venue").unwrap();
                     let venue = venue_match.as_str().trim().to_string();
-                    self.game_builder.set_venue(venue);
 
                     if venue_match.end() == self.input_buffer.len() {
+                        self.game_builder.set_venue(venue);
                         return Ok(false);
                     }
 
+                    if let (true, Some(venue_registry)) = (self.options.strict, &self.venue_registry) {
+                        venue_registry.validate(&venue).map_err(|e| Diagnostic::new(DiagnosticKind::UnknownVenue, e))?;
+                    }
+                    self.game_builder.set_venue(venue);
+
                     self.consume_input(venue_match.end());
                     self.possible_sections = vec![GameSection::Context(ContextSection::Weather)];
 
@@ -359,21 +550,37 @@ impl Parser {
                 let captures = CONTEXT_SECTION_WEATHER_REGEX.captures(&self.input_buffer);
                 if let Ok(Some(captures)) = captures {
                     let weather_match = captures.name("weather").unwrap();
-                    let weather = weather_match.as_str().to_string();
+                    let weather = WeatherCondition::from(weather_match.as_str());
+                    if self.options.strict {
+                        weather.validate().map_err(|e| Diagnostic::new(DiagnosticKind::UnknownWeatherCondition, e))?;
+                    }
 
                     let temperature_match = captures.name("temperature").unwrap();
-                    let temperature = temperature_match.as_str().parse::<u64>().unwrap();
+                    let temperature = temperature_match.as_str().parse::<f64>().unwrap();
+                    let temperature_unit = captures.name("temperature_unit")
+                        .map_or(TemperatureUnit::Fahrenheit, |unit| unit.as_str().parse().unwrap());
 
                     let wind_speed_match = captures.name("wind_speed").unwrap();
-                    let wind_speed = wind_speed_match.as_str().parse::<u64>().unwrap();
+                    let wind_speed = wind_speed_match.as_str().parse::<f64>().unwrap();
+                    let wind_speed_unit = captures.name("wind_speed_unit")
+                        .map_or(SpeedUnit::MilesPerHour, |unit| unit.as_str().parse().unwrap());
 
-                    self.game_builder.set_weather(weather, temperature, wind_speed);
+                    self.game_builder.set_weather(weather.clone(), temperature, temperature_unit, wind_speed, wind_speed_unit);
 
-                    if wind_speed_match.end() == self.input_buffer.len() {
+                    let section_end = captures.get(0).unwrap().end();
+                    let rest = &self.input_buffer[section_end..];
+                    if rest.is_empty() || WEATHER_SUFFIXES.iter().any(|suffix| suffix.starts_with(rest)) {
                         return Ok(false);
                     }
 
-                    self.consume_input(wind_speed_match.end());
+                    // real weather can be unusual, so these are only ever warnings
+                    if self.options.check_weather_ranges {
+                        let weather = Weather { condition: weather, temperature, temperature_unit, wind_speed, wind_speed_unit };
+                        self.live_game_state.warnings.extend(weather.implausible_values().into_iter()
+                            .map(|problem| Diagnostic::new(DiagnosticKind::ImplausibleWeather, problem).as_warning()));
+                    }
+
+                    self.consume_input(section_end);
                     self.possible_sections = vec![GameSection::HomeTeam(TeamSection::Team)];
 
                     return Ok(true);
@@ -424,11 +631,14 @@ impl Parser {
 
                     let player = Player {
                         position,
-                        name: player_name.clone(),
+                        name: player_name.clone().into(),
                     };
 
                     if position == Position::PinchRunner {
-                        self.pinch_runners.push(player_name);
+                        match home_team {
+                            true => self.pinch_runners.home.push(player_name.into()),
+                            false => self.pinch_runners.away.push(player_name.into()),
+                        }
                     }
 
                     if player_name_match.end() == self.input_buffer.len() {
@@ -464,6 +674,25 @@ impl Parser {
             PlaySection::GameStart() => {
                 if self.input_buffer.starts_with(PLAY_SECTION_GAME_START) {
                     self.consume_input(PLAY_SECTION_GAME_START.len());
+                    self.game_builder.game_started = true;
+                    self.live_game_state.set_rosters(&self.game_builder.home_team_players, &self.game_builder.away_team_players);
+                    self.possible_sections = vec![
+                        GameSection::Plays(PlaySection::Overturned()),
+                        GameSection::Plays(PlaySection::Inning()),
+                    ];
+                    self.check_roster_plausibility()?;
+                    if self.options.require_declared_players && !self.options.skip_validation {
+                        self.check_buffered_plays_declared()?;
+                    }
+
+                    return Ok(true);
+                }
+            },
+            PlaySection::Overturned() => {
+                if self.input_buffer.starts_with(PLAY_SECTION_OVERTURNED) {
+                    self.play_start = Some(self.consumed_bytes);
+                    self.consume_input(PLAY_SECTION_OVERTURNED.len());
+                    self.game_builder.play_builder.overturned_call = true;
                     self.possible_sections = vec![GameSection::Plays(PlaySection::Inning())];
 
                     return Ok(true);
@@ -489,11 +718,10 @@ impl Parser {
                         return Ok(false);
                     }
 
-                    if self.live_game_state.inning.top_bottom != top_bottom {
-                        self.live_game_state.runner_positions = RunnerPositions::empty();
-                    }
-                    self.live_game_state.inning = inning;
+                    self.live_game_state.set_inning(inning);
 
+                    // an overturned call starts at its [OVERTURNED] tag
+                    self.play_start.get_or_insert(self.consumed_bytes);
                     self.consume_input(top_bottom_match.end());
                     self.possible_sections = vec![GameSection::Plays(PlaySection::Play())];
 
@@ -512,11 +740,16 @@ impl Parser {
                         return Ok(false);
                     }
 
-                    self.consume_input(play_type_match.end());
+                    let end = play_type_match.end();
+                    if play_type == PlayType::GameAdvisory {
+                        self.set_play_span(end);
+                    }
+                    self.consume_input(end);
 
                     if play_type == PlayType::GameAdvisory {
                         self.game_builder.build_play();
                         self.possible_sections = vec![
+                            GameSection::Plays(PlaySection::Overturned()),
                             GameSection::Plays(PlaySection::Inning()),
                             GameSection::Plays(PlaySection::GameEnd()),
                         ];
@@ -540,6 +773,10 @@ impl Parser {
                         self.possible_sections = vec![
                             GameSection::Plays(PlaySection::Fielders(FieldersSection::Tag)),
                         ];
+                        // a runner out may name the runner before the fielders
+                        if play_type.requires_runner() {
+                            self.possible_sections.push(GameSection::Plays(PlaySection::Runner()));
+                        }
                     } else if play_type.requires_runner() {
                         self.possible_sections = vec![
                             GameSection::Plays(PlaySection::Runner()),
@@ -553,6 +790,9 @@ impl Parser {
                             GameSection::Plays(PlaySection::Movements(MovementsSection::Tag)),
                         ];
                     }
+                    if play_type.allows_base() {
+                        self.possible_sections.push(GameSection::Plays(PlaySection::Base()));
+                    }
 
                     return Ok(true);
                 }
@@ -584,6 +824,12 @@ impl Parser {
                         self.possible_sections = vec![
                             GameSection::Plays(PlaySection::Catcher()),
                         ];
+                    } else if play_type.allows_base() {
+                        // an optional base comes before the fielders or the runner, as they would without it
+                        self.possible_sections = vec![
+                            GameSection::Plays(PlaySection::Fielders(FieldersSection::Tag)),
+                            GameSection::Plays(PlaySection::Runner()),
+                        ];
                     } else if play_type.requires_runner() {
                         self.possible_sections = vec![
                             GameSection::Plays(PlaySection::Runner()),
@@ -721,6 +967,9 @@ impl Parser {
                             GameSection::Plays(PlaySection::Movements(MovementsSection::Tag)),
                         ];
                     }
+                    if play_type.allows_error_by() {
+                        self.possible_sections.push(GameSection::Plays(PlaySection::ErrorBy()));
+                    }
 
                     return Ok(true);
                 }
@@ -745,7 +994,10 @@ impl Parser {
                                 return Ok(false);
                             }
 
-                            self.game_builder.play_builder.add_fielder(player_name);
+                            let repeated = self.game_builder.play_builder.fielders.iter().any(|fielder| *fielder == player_name);
+                            if !(self.options.dedupe_fielders && repeated) {
+                                self.game_builder.play_builder.add_fielder(player_name);
+                            }
                             self.consume_input(player_name_match.end());
 
                             self.possible_sections = vec![
@@ -754,9 +1006,16 @@ impl Parser {
                             let play_type = self.game_builder.play_builder.play_type.unwrap();
                             if play_type.requires_scoring_runner() {
                                 self.possible_sections.push(GameSection::Plays(PlaySection::ScoringRunner()));
+                            } else if play_type.requires_runner() && self.game_builder.play_builder.runner.is_none() {
+                                self.possible_sections.push(GameSection::Plays(PlaySection::Runner()));
+                            } else if play_type.requires_position() {
+                                self.possible_sections.push(GameSection::Plays(PlaySection::Position()));
                             } else {
                                 self.possible_sections.push(GameSection::Plays(PlaySection::Movements(MovementsSection::Tag)));
                             }
+                            if play_type.allows_error_by() {
+                                self.possible_sections.push(GameSection::Plays(PlaySection::ErrorBy()));
+                            }
 
                             return Ok(true);
                         }
@@ -788,7 +1047,7 @@ impl Parser {
                     let play_type = self.game_builder.play_builder.play_type.unwrap();
                     if play_type.requires_scoring_runner() {
                         self.possible_sections = vec![GameSection::Plays(PlaySection::ScoringRunner())];
-                    } else if play_type.requires_fielders() {
+                    } else if play_type.requires_fielders() && self.game_builder.play_builder.fielders.is_empty() {
                         self.possible_sections = vec![GameSection::Plays(PlaySection::Fielders(FieldersSection::Tag))];
                     } else {
                         self.possible_sections = vec![GameSection::Plays(PlaySection::Movements(MovementsSection::Tag))];
@@ -815,12 +1074,47 @@ impl Parser {
                     return Ok(true);
                 }
             },
+            PlaySection::ErrorBy() => {
+                let captures = PLAY_SECTION_ERROR_BY_REGEX.captures(&self.input_buffer);
+                if let Ok(Some(captures)) = captures {
+                    let error_by_match = captures.name("error_by").unwrap();
+                    let error_by = error_by_match.as_str().trim().to_string();
+
+                    self.game_builder.play_builder.set_error_by(error_by);
+
+                    if error_by_match.end() == self.input_buffer.len() {
+                        return Ok(false);
+                    }
+
+                    self.consume_input(error_by_match.end());
+                    self.possible_sections = vec![GameSection::Plays(PlaySection::Movements(MovementsSection::Tag))];
+
+                    return Ok(true);
+                }
+            },
+            PlaySection::Position() => {
+                let captures = PLAY_SECTION_POSITION_REGEX.captures(&self.input_buffer);
+                if let Ok(Some(captures)) = captures {
+                    let position_match = captures.name("position").unwrap();
+                    let position = position_match.as_str().parse::<u8>().unwrap();
+
+                    self.game_builder.play_builder.set_position(position);
+
+                    self.consume_input(position_match.end());
+                    self.possible_sections = vec![GameSection::Plays(PlaySection::Movements(MovementsSection::Tag))];
+
+                    return Ok(true);
+                }
+            },
             PlaySection::Movements(movements_section) => {
                 match movements_section {
                     MovementsSection::Tag => {
                         if self.input_buffer.starts_with(PLAY_SECTION_MOVEMENTS_TAG) {
                             self.consume_input(PLAY_SECTION_MOVEMENTS_TAG.len());
                             self.possible_sections = vec![GameSection::Plays(PlaySection::Movements(MovementsSection::Name))];
+                            if self.game_builder.play_builder.play_type.is_some_and(|play_type| play_type.allows_no_movements()) {
+                                self.possible_sections.push(GameSection::Plays(PlaySection::PlayEnd()));
+                            }
 
                             return Ok(true);
                         }
@@ -884,6 +1178,7 @@ impl Parser {
 
                             self.consume_input(base_match.end());
                             self.possible_sections = vec![
+                                GameSection::Plays(PlaySection::Movements(MovementsSection::OnError)),
                                 GameSection::Plays(PlaySection::Movements(MovementsSection::Out)),
                                 GameSection::Plays(PlaySection::Movements(MovementsSection::MovementEnd)),
                             ];
@@ -891,15 +1186,40 @@ impl Parser {
                             return Ok(true);
                         }
                     },
+                    MovementsSection::OnError => {
+                        if self.input_buffer.starts_with(PLAY_SECTION_ON_ERROR) {
+                            self.game_builder.play_builder.movement_builder.set_on_error();
+
+                            if self.input_buffer.len() == PLAY_SECTION_ON_ERROR.len() {
+                                return Ok(false);
+                            }
+
+                            self.consume_input(PLAY_SECTION_ON_ERROR.len());
+
+                            self.possible_sections = vec![
+                                GameSection::Plays(PlaySection::Movements(MovementsSection::MovementEnd)),
+                            ];
+
+                            return Ok(true);
+                        }
+                    },
                     MovementsSection::Out => {
-                        if self.input_buffer.starts_with(PLAY_SECTION_OUT) {
-                            self.game_builder.play_builder.movement_builder.set_out();
+                        if let Ok(Some(captures)) = MOVEMENT_OUT_REGEX.captures(&self.input_buffer) {
+                            let out_match = captures.get(0).unwrap();
+                            match captures.name("base") {
+                                Some(base) => self.game_builder.play_builder.movement_builder.set_out_at(base.as_str().parse::<Base>().unwrap()),
+                                None => self.game_builder.play_builder.movement_builder.set_out(),
+                            };
+                            if let Some(credits) = captures.name("credits") {
+                                let credits = credits.as_str().split('-').map(|position| position.parse::<u8>().unwrap()).collect();
+                                self.game_builder.play_builder.movement_builder.set_credits(credits);
+                            }
 
-                            if self.input_buffer.len() == PLAY_SECTION_OUT.len() {
+                            if self.input_buffer.len() == out_match.end() {
                                 return Ok(false);
                             }
 
-                            self.consume_input(PLAY_SECTION_OUT.len());
+                            self.consume_input(out_match.end());
 
                             self.possible_sections = vec![
                                 GameSection::Plays(PlaySection::Movements(MovementsSection::MovementEnd)),
@@ -922,6 +1242,7 @@ impl Parser {
                         self.possible_sections = vec![
                             GameSection::Plays(PlaySection::Movements(MovementsSection::Out)),
                             GameSection::Plays(PlaySection::Movements(MovementsSection::CommaSpace)),
+                            GameSection::Plays(PlaySection::Meta()),
                             GameSection::Plays(PlaySection::PlayEnd()),
                         ];
 
@@ -929,27 +1250,93 @@ impl Parser {
                     },
                 }
             },
+            PlaySection::Meta() => {
+                let captures = PLAY_SECTION_META_REGEX.captures(&self.input_buffer);
+                if let Ok(Some(captures)) = captures {
+                    let pairs_match = captures.name("pairs").unwrap();
+                    // another pair may still be arriving, so wait for the end of the play
+                    if !self.input_buffer[pairs_match.end()..].trim_start().starts_with(PLAY_SECTION_PLAY_END) {
+                        return Ok(false);
+                    }
+
+                    for pair in pairs_match.as_str().split_whitespace() {
+                        let (key, value) = pair.split_once('=').unwrap();
+                        self.game_builder.play_builder.add_meta(key.to_string(), value.to_string());
+                    }
+
+                    self.consume_input(pairs_match.end());
+                    self.possible_sections = vec![GameSection::Plays(PlaySection::PlayEnd())];
+
+                    return Ok(true);
+                }
+            },
             PlaySection::PlayEnd() => {
                 if self.input_buffer.starts_with(PLAY_SECTION_PLAY_END) {
                     let _ = self.game_builder.play_builder.build_movement();
 
+                    self.set_play_span(PLAY_SECTION_PLAY_END.len());
+                    let play_end = self.input_buffer.position() + PLAY_SECTION_PLAY_END.len();
                     self.consume_input(PLAY_SECTION_PLAY_END.len());
 
+                    if !self.options.skip_validation {
+                        self.check_fielding_team()?;
+                        self.check_runner_base()?;
+                        if self.options.require_declared_players {
+                            let play_builder = &self.game_builder.play_builder;
+                            let names = play_builder.batter.iter()
+                                .chain(play_builder.pitcher.iter())
+                                .chain(play_builder.catcher.iter())
+                                .chain(play_builder.fielders.iter())
+                                .chain(play_builder.runner.iter())
+                                .chain(play_builder.scoring_runner.iter())
+                                .chain(play_builder.error_by.iter())
+                                .chain(play_builder.movements.iter().map(|movement| &movement.runner))
+                                .cloned()
+                                .collect::<Vec<_>>();
+                            self.check_declared(names, play_builder.inning)?;
+                        }
+                    }
+                    let overturned_call = self.game_builder.play_builder.overturned_call;
                     self.game_builder.build_play();
 
-                    let movements = &self.game_builder.plays.last().unwrap().movements;
-                    if let Err(e) = self.live_game_state.runner_positions.process_movements(movements, &self.pinch_runners) {
-                        // println!("error while processing movements");
-                        return Err(PyValueError::new_err(format!(
-                            "Inning {}: {}",
-                            &self.game_builder.plays.last().unwrap().inning.to_string(),
-                            e,
-                        )));
-                    } else {
-                        // println!("no error while processing movements.");
+                    // the original call doesn't count, so wait for the play that replaced it
+                    if overturned_call {
+                        self.possible_sections = vec![
+                            GameSection::Plays(PlaySection::Overturned()),
+                            GameSection::Plays(PlaySection::Inning()),
+                        ];
+
+                        return Ok(true);
+                    }
+
+                    let play = self.game_builder.plays.last().unwrap();
+                    if !self.options.skip_validation {
+                        if let Err(e) = self.live_game_state.process_play(play, &self.pinch_runners, self.options.strict) {
+                            return Err(e.in_inning(play.inning).into());
+                        }
+                    }
+
+                    // only a play the game state accepts is reported
+                    let previous_inning = self.game_builder.plays.iter().rev().nth(1).map(|play| play.inning);
+                    if previous_inning != Some(play.inning) {
+                        self.events.push(ParserEvent::InningChanged { inning: play.inning });
+                    }
+                    if let Some(pitcher) = play.play_content.pitcher() {
+                        // the last pitcher for the same fielding team
+                        let replaced = self.game_builder.plays.iter().rev().skip(1)
+                            .filter(|previous| previous.inning.top_bottom == play.inning.top_bottom)
+                            .find_map(|previous| previous.play_content.pitcher());
+                        if let Some(replaced) = replaced.filter(|replaced| *replaced != pitcher) {
+                            self.events.push(ParserEvent::PitchingChange { pitcher: pitcher.to_string(), replaced: replaced.to_string() });
+                        }
                     }
+                    self.events.push(ParserEvent::PlayParsed { play: play.clone() });
+
+                    // what's left of the game starts after this play
+                    self.input_buffer.keep_from(play_end);
 
                     self.possible_sections = vec![
+                        GameSection::Plays(PlaySection::Overturned()),
                         GameSection::Plays(PlaySection::Inning()),
                         GameSection::Plays(PlaySection::GameEnd()),
                     ];
@@ -961,8 +1348,20 @@ impl Parser {
             },
             PlaySection::GameEnd() => {
                 if self.input_buffer.starts_with(PLAY_SECTION_GAME_END) {
+                    // strict mode only ends the game where `remaining_regex()` would
+                    let state = &self.live_game_state;
+                    if self.options.strict && !self.options.skip_validation && !state.could_end() {
+                        return Err(Diagnostic::new(DiagnosticKind::IncompleteGame, format!(
+                            "The game could not legally end in inning {} with the score {}-{}",
+                            state.inning.to_string(),
+                            state.away_team_score,
+                            state.home_team_score,
+                        )).into());
+                    }
+
                     self.consume_input(PLAY_SECTION_GAME_END.len());
                     self.finished = true;
+                    self.events.push(ParserEvent::GameFinished());
 
                     return Ok(true);
                 }
@@ -976,30 +1375,39 @@ impl Parser {
 
     fn parse_input_buffer(&mut self) -> PyResult<bool> {
         for section in self.possible_sections.clone() {
+            // names can contain spaces, so a name section isn't complete until the next tag, play end or line
+            // break, and there's no need to run its regex on every character before then
+            if section.ends_with_name() && !has_name_terminator(&self.input_buffer) {
+                continue;
+            }
+
+            #[cfg(feature = "perf-stats")]
+            let start = std::time::Instant::now();
+
             let success = match section {
                 GameSection::Context(context_section) => {
-                    if self.print_debug {
+                    if self.options.debug_sink.is_some() {
                         self.print_debug_message();
                     }
 
                     self.parse_context_section(context_section)
                 },
                 GameSection::HomeTeam(team_section) => {
-                    if self.print_debug {
+                    if self.options.debug_sink.is_some() {
                         self.print_debug_message();
                     }
 
                     self.parse_team_section(team_section, true)
                 },
                 GameSection::AwayTeam(team_section) => {
-                    if self.print_debug {
+                    if self.options.debug_sink.is_some() {
                         self.print_debug_message();
                     }
 
                     self.parse_team_section(team_section, false)
                 },
                 GameSection::Plays(play_section) => {
-                    if self.print_debug {
+                    if self.options.debug_sink.is_some() {
                         self.print_debug_message();
                     }
 
@@ -1007,154 +1415,692 @@ impl Parser {
                 },
             }?;
 
+            #[cfg(feature = "perf-stats")]
+            {
+                let kind = match section {
+                    GameSection::Context(_) => "Context",
+                    GameSection::HomeTeam(_) => "HomeTeam",
+                    GameSection::AwayTeam(_) => "AwayTeam",
+                    GameSection::Plays(_) => "Plays",
+                };
+                self.perf_stats.record(kind, format!("{:?}", section), start);
+            }
+
             if success {
                 return Ok(success);
             }
         }
 
+        if self.options.allow_unknown_tags {
+            return self.parse_unknown_tag();
+        }
+
         Ok(false)
     }
 
-    /// Return a regex that matches the inner part of a play of a given type.
-    fn inner_pattern_from_play_type(&self, play_type: &PlayType) -> String {
-        let mut s = format!(r"\[PLAY\] {} ", play_type.to_string());
+    /// Keep an unrecognised `[TAG] value` entry on the current play, or on the game if no play is in progress.
+    fn parse_unknown_tag(&mut self) -> PyResult<bool> {
+        let Ok(Some(captures)) = UNKNOWN_TAG_REGEX.captures(&self.input_buffer) else {
+            return Ok(false);
+        };
 
-        if play_type.requires_base() {
-            let base = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_BASE_REGEX.as_str(), "");
-            s.push_str(&base);
-            s.push_str(" ");
-        }
-        if play_type.requires_batter() {
-            let batter = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_BATTER_REGEX.as_str(), "");
-            s.push_str(&batter);
-            s.push_str(" ");
+        let tag = captures.name("tag").unwrap().as_str().to_string();
+        if KNOWN_TAGS.contains(&tag) {
+            return Ok(false);
         }
-        if play_type.requires_pitcher() {
-            let pitcher = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_PITCHER_REGEX.as_str(), "");
-            s.push_str(&pitcher);
-            s.push_str(" ");
-        }
-        if play_type.requires_catcher() {
-            let catcher = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_CATCHER_REGEX.as_str(), "");
-            s.push_str(&catcher);
-            s.push_str(" ");
-        }
-        if play_type.requires_fielders() {
-            let fielders = format!(
-                "{tag} {name}(, {name})*",
-                tag=PLAY_SECTION_FIELDERS_TAG.replace("[", r"\[").replace("]", r"\]"),
-                name=PLAYER_NAME,
-            );
 
-            s.push_str(&fielders);
-            s.push_str(" ");
-        }
-        if play_type.requires_runner() {
-            let runner = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_RUNNER_REGEX.as_str(), "");
-            s.push_str(&runner);
-            s.push_str(" ");
+        // the value may not have been fully received yet
+        let section_end = captures.get(0).unwrap().end();
+        if section_end == self.input_buffer.len() {
+            return Ok(false);
         }
-        if play_type.requires_scoring_runner() {
-            let scoring_runner = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_SCORING_RUNNER_REGEX.as_str(), "");
-            s.push_str(&scoring_runner);
-            s.push_str(" ");
+
+        let extension = Extension {
+            tag,
+            value: captures.name("value").unwrap().as_str().trim().to_string(),
+        };
+        if self.game_builder.play_builder.inning.is_some() {
+            self.game_builder.play_builder.extensions.push(extension);
+        } else {
+            self.game_builder.extensions.push(extension);
         }
 
-        s.trim().replace("^", "")
-    }
+        self.consume_input(section_end);
 
-    /// Return a regex that matches the movements part of a play.
-    fn movements_regex(&self) -> String {
-        let mut s = PLAY_SECTION_MOVEMENTS_TAG.replace("[", r"\[").replace("]", r"\]");
-        s.push_str(" ");
+        Ok(true)
+    }
 
-        let pinch_runners = self.pinch_runners.join("|");
+    /// Return the players on the batting or fielding team in the given half-inning.
+    fn team_players(&self, top_bottom: TopBottom, batting: bool) -> &[Player] {
+        // the away team bats in the top of an inning
+        match (top_bottom, batting) {
+            (TopBottom::Top, true) | (TopBottom::Bottom, false) => &self.game_builder.away_team_players,
+            (TopBottom::Top, false) | (TopBottom::Bottom, true) => &self.game_builder.home_team_players,
+        }
+    }
 
-        let mut valid_movement_patterns = Vec::new();
-        let home_or_pinch_runner = if pinch_runners.is_empty() {
-            PLAYER_NAME.to_string()
-        } else {
-            format!(r"({}|{})", PLAYER_NAME, pinch_runners)
+    /// Return an example of text the section accepts, taken from the start of its regex where it has one.
+    fn section_example(&self, section: &GameSection) -> String {
+        // a name in the play in progress, from the fielding or batting team's roster
+        let roster_name = |batting: bool| {
+            let top_bottom = self.game_builder.play_builder.inning.map(|inning| inning.top_bottom)?;
+            self.team_players(top_bottom, batting).first().map(|player| player.name.to_string())
         };
-        let home_to_any = format!(r"{home_or_pinch_runner} home -> (1|2|3|4|home)( \[out\])?");
-        valid_movement_patterns.push(home_to_any);
 
-        if let Some(first) = &self.live_game_state.runner_positions.first {
-            let first_or_pinch_runner = if pinch_runners.is_empty() {
-                first.to_string()
-            } else {
-                format!(r"({}|{})", first, pinch_runners)
-            };
-            let first_to_any = format!(r"{first_or_pinch_runner} 1 -> (2|3|4|home)( \[out\])?");
-            valid_movement_patterns.push(first_to_any);
+        match section {
+            GameSection::Context(ContextSection::Game) => literal_prefix(CONTEXT_SECTION_GAME_REGEX.as_str()),
+            GameSection::Context(ContextSection::Date) => literal_prefix(CONTEXT_SECTION_DATE_REGEX.as_str()),
+            GameSection::Context(ContextSection::Venue) => literal_prefix(CONTEXT_SECTION_VENUE_REGEX.as_str()),
+            GameSection::Context(ContextSection::Weather) => literal_prefix(CONTEXT_SECTION_WEATHER_REGEX.as_str()),
+            GameSection::HomeTeam(TeamSection::Team) | GameSection::AwayTeam(TeamSection::Team) => literal_prefix(TEAM_SECTION_TEAM_REGEX.as_str()),
+            GameSection::HomeTeam(TeamSection::Player) | GameSection::AwayTeam(TeamSection::Player) => format!("[{}] ", Position::Pitcher.to_string()),
+            GameSection::Plays(play_section) => match play_section {
+                PlaySection::Inning() => literal_prefix(PLAY_SECTION_INNING_REGEX.as_str()),
+                PlaySection::Play() => literal_prefix(PLAY_SECTION_PLAY_REGEX.as_str()),
+                PlaySection::Base() => literal_prefix(PLAY_SECTION_BASE_REGEX.as_str()),
+                PlaySection::Batter() => literal_prefix(PLAY_SECTION_BATTER_REGEX.as_str()),
+                PlaySection::Pitcher() => literal_prefix(PLAY_SECTION_PITCHER_REGEX.as_str()),
+                PlaySection::Catcher() => literal_prefix(PLAY_SECTION_CATCHER_REGEX.as_str()),
+                PlaySection::Runner() => literal_prefix(PLAY_SECTION_RUNNER_REGEX.as_str()),
+                PlaySection::ScoringRunner() => literal_prefix(PLAY_SECTION_SCORING_RUNNER_REGEX.as_str()),
+                PlaySection::ErrorBy() => literal_prefix(PLAY_SECTION_ERROR_BY_REGEX.as_str()),
+                PlaySection::Position() => literal_prefix(PLAY_SECTION_POSITION_REGEX.as_str()),
+                PlaySection::Meta() => "[META] key=value".to_string(),
+                PlaySection::Overturned() => format!("{} ", PLAY_SECTION_OVERTURNED),
+                PlaySection::Fielders(FieldersSection::Tag) => format!("{} ", PLAY_SECTION_FIELDERS_TAG),
+                PlaySection::Movements(MovementsSection::Tag) => format!("{} ", PLAY_SECTION_MOVEMENTS_TAG),
+                PlaySection::Fielders(FieldersSection::Name) => roster_name(false).unwrap_or_else(|| section.expected_token()),
+                PlaySection::Movements(MovementsSection::Name) => roster_name(true).unwrap_or_else(|| section.expected_token()),
+                PlaySection::Movements(MovementsSection::StartBase) => "home ".to_string(),
+                PlaySection::Movements(MovementsSection::EndBase) => " 1".to_string(),
+                PlaySection::Movements(MovementsSection::MovementEnd) => PLAY_SECTION_PLAY_END.to_string(),
+                _ => section.expected_token(),
+            },
         }
+    }
 
-        if let Some(second) = &self.live_game_state.runner_positions.second {
-            let second_or_pinch_runner = if pinch_runners.is_empty() {
-                second.to_string()
-            } else {
-                format!(r"({}|{})", second, pinch_runners)
-            };
-            let second_to_any = format!(r"{second_or_pinch_runner} 2 -> (3|4|home)( \[out\])?");
-            valid_movement_patterns.push(second_to_any);
+    /// Return the texts the section could start with, one per play type, position or player on the roster where the
+    /// section names one, and the section's example otherwise.
+    fn section_candidates(&self, section: &GameSection) -> Vec<String> {
+        let top_bottom = self.game_builder.play_builder.inning.map(|inning| inning.top_bottom);
+        let roster = |prefix: &str, batting: bool| match top_bottom {
+            Some(top_bottom) => self.team_players(top_bottom, batting).iter()
+                .map(|player| format!("{}{}", prefix, player.name))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let candidates = match section {
+            GameSection::HomeTeam(TeamSection::Player) | GameSection::AwayTeam(TeamSection::Player) => {
+                Position::iter().map(|position| format!("[{}] ", position.to_string())).collect()
+            },
+            GameSection::Plays(play_section) => match play_section {
+                PlaySection::Play() => {
+                    let prefix = literal_prefix(PLAY_SECTION_PLAY_REGEX.as_str());
+                    PlayType::iter().map(|play_type| format!("{}{}", prefix, play_type.to_string())).collect()
+                },
+                PlaySection::Batter() => roster(&literal_prefix(PLAY_SECTION_BATTER_REGEX.as_str()), true),
+                PlaySection::Runner() => roster(&literal_prefix(PLAY_SECTION_RUNNER_REGEX.as_str()), true),
+                PlaySection::ScoringRunner() => roster(&literal_prefix(PLAY_SECTION_SCORING_RUNNER_REGEX.as_str()), true),
+                PlaySection::Pitcher() => roster(&literal_prefix(PLAY_SECTION_PITCHER_REGEX.as_str()), false),
+                PlaySection::Catcher() => roster(&literal_prefix(PLAY_SECTION_CATCHER_REGEX.as_str()), false),
+                PlaySection::ErrorBy() => roster(&literal_prefix(PLAY_SECTION_ERROR_BY_REGEX.as_str()), false),
+                PlaySection::Fielders(FieldersSection::Name) => roster("", false),
+                PlaySection::Movements(MovementsSection::Name) => roster("", true),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        match candidates.is_empty() {
+            true => vec![self.section_example(section)],
+            false => candidates,
         }
+    }
 
-        if let Some(third) = &self.live_game_state.runner_positions.third {
-            let third_or_pinch_runner = if pinch_runners.is_empty() {
-                third.to_string()
-            } else {
-                format!(r"({}|{})", third, pinch_runners)
-            };
-            let third_to_any = format!(r"{third_or_pinch_runner} 3 -> (4|home)( \[out\])?");
-            valid_movement_patterns.push(third_to_any);
+    /// Describe what the parser expects next, with examples, for error messages.
+    fn expected_hint(&self) -> String {
+        let examples = self.expected_examples().iter()
+            .map(|example| format!("{:?}", example))
+            .collect::<Vec<_>>();
+        format!("expected one of {} (e.g. {})", self.expected_tokens().join(", "), examples.join(", "))
+    }
+
+    /// Whether the input waiting to be parsed could still be the start of the section. Only the literal text a section
+    /// starts with, and the end of a name, can be checked before the section matches.
+    fn could_start(&self, section: &GameSection) -> bool {
+        let buffer = self.input_buffer.trim_start();
+        if buffer.is_empty() {
+            return true;
         }
 
-        let joined = valid_movement_patterns.iter()
-            .map(|s| format!("({})", s))
-            .collect::<Vec<_>>()
-            .join("|");
-        let many = format!(r"{joined}(, {joined})*");
-        s.push_str(&many);
+        let literal = match section {
+            GameSection::HomeTeam(TeamSection::Player) | GameSection::AwayTeam(TeamSection::Player) => "[".to_string(),
+            GameSection::Plays(PlaySection::Fielders(FieldersSection::Name) | PlaySection::Movements(MovementsSection::Name)) => {
+                return matches!(PLAYER_NAME_REGEX.is_match(buffer), Ok(true));
+            },
+            GameSection::Plays(PlaySection::Movements(
+                MovementsSection::StartBase | MovementsSection::EndBase | MovementsSection::MovementEnd
+            )) => return true,
+            _ => section.expected_token(),
+        };
+        if buffer.len() < literal.len() {
+            return literal.starts_with(buffer);
+        }
 
-        s
+        // a name section that has seen the end of its name would already have matched
+        buffer.starts_with(&literal) && !(section.ends_with_name() && has_name_terminator(buffer))
     }
 
-    /// Return a regex that matches a single play.
-    pub fn play_regex(&self) -> String {
-        let inning = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_INNING_REGEX.as_str(), "").replace("^", "");
-        let all_plays = PlayType::iter().map(|play_type| self.inner_pattern_from_play_type(&play_type)).collect::<Vec<_>>();
-        let inner = all_plays.iter().map(|s| format!("({})", s)).collect::<Vec<_>>().join("|");
-        let movements = self.movements_regex();
+    /// Check that the pitcher, catcher, and fielders of the play in progress are on the fielding team, and haven't been
+    /// taken out of the game by a defensive substitution. Anyone who isn't is an error in strict mode, and a warning
+    /// otherwise.
+    fn check_fielding_team(&mut self) -> PyResult<()> {
+        let play_builder = &self.game_builder.play_builder;
+        let Some(inning) = play_builder.inning else {
+            return Ok(());
+        };
+
+        let (side, team_id) = match inning.top_bottom {
+            TopBottom::Top => ("home", self.game_builder.home_team_id),
+            TopBottom::Bottom => ("away", self.game_builder.away_team_id),
+        };
+        let players = self.team_players(inning.top_bottom, false);
+        let alignment = self.live_game_state.fielding_alignment_in(inning.top_bottom);
+        let problems = play_builder.pitcher.iter()
+            .chain(play_builder.catcher.iter())
+            .chain(play_builder.fielders.iter())
+            .chain(play_builder.error_by.iter())
+            .filter_map(|name| if !players.iter().any(|player| &player.name == name) {
+                Some(Diagnostic::new(DiagnosticKind::FielderNotOnFieldingTeam, format!(
+                    "{} is not on the fielding team ({} team {})",
+                    name,
+                    side,
+                    team_id.unwrap_or_default(),
+                )))
+            } else if alignment.has_left_game(name) {
+                Some(Diagnostic::new(DiagnosticKind::PlayerLeftGame, format!("{} has left the game", name)))
+            } else {
+                None
+            })
+            .map(|diagnostic| diagnostic.in_inning(inning))
+            .collect::<Vec<_>>();
+
+        self.report_problems(problems)
+    }
+
+    /// Check that the runner of a pickoff, caught stealing, or stolen base play is on the base its `[BASE]` implies:
+    /// the base itself for a pickoff, or the base before it for a steal. This is an error in strict mode, and a
+    /// warning otherwise.
+    fn check_runner_base(&mut self) -> PyResult<()> {
+        let play_builder = &self.game_builder.play_builder;
+        let (Some(inning), Some(play_type), Some(base), Some(runner)) = (play_builder.inning, play_builder.play_type, play_builder.base, &play_builder.runner) else {
+            return Ok(());
+        };
+
+        let from = match play_type {
+            PlayType::Pickoff | PlayType::PickoffError => Some(base),
+            PlayType::CaughtStealing | PlayType::PickoffCaughtStealing | PlayType::StolenBase => match base {
+                Base::First => None,
+                Base::Second => Some(Base::First),
+                Base::Third => Some(Base::Second),
+                Base::Home => Some(Base::Third),
+            },
+            _ => return Ok(()),
+        };
+
+        let problem = match from.map(|from| (from, self.live_game_state.runner_positions.runner_on(from))) {
+            None => Some(Diagnostic::new(
+                DiagnosticKind::ImpossibleBase,
+                format!("{} {} is not possible", play_type.to_string(), base.to_string()),
+            )),
+            Some((from, None)) => Some(Diagnostic::new(
+                DiagnosticKind::RunnerNotOnBase,
+                format!("{} {} needs {} on {}, but no runner is on {}", play_type.to_string(), base.to_string(), runner, from.to_string(), from.to_string()),
+            )),
+            Some((from, Some(on_base))) if on_base != runner && !self.live_game_state.eligible_pinch_runners(&self.pinch_runners).contains(runner) => Some(Diagnostic::new(
+                DiagnosticKind::RunnerNotOnBase,
+                format!("{} {} needs {} on {}, but {} is on {}", play_type.to_string(), base.to_string(), runner, from.to_string(), on_base, from.to_string()),
+            )),
+            _ => None,
+        };
+
+        let problems = problem.map(|problem| problem.in_inning(inning));
+        self.report_problems(problems.into_iter().collect())
+    }
+
+    /// Check that every player named in the plays already buffered is declared, reporting the undeclared ones together.
+    /// The plays are parsed ahead on a copy of the parser, which doesn't check them.
+    fn check_buffered_plays_declared(&mut self) -> PyResult<()> {
+        let mut lookahead = self.clone();
+        lookahead.options.require_declared_players = false;
+        lookahead.options.skip_validation = true;
+        lookahead.options.strict = false;
+        // a play that doesn't parse will be reported when this parser reaches it
+        let _ = lookahead.parse_input("");
+
+        let names = lookahead.game_builder.plays.iter()
+            .flat_map(|play| play.overturned.as_deref().into_iter().chain([play]))
+            .flat_map(|play| {
+                let content = &play.play_content;
+                content.batter().into_iter()
+                    .chain(content.pitcher())
+                    .chain(content.catcher())
+                    .chain(content.fielders())
+                    .chain(content.runner())
+                    .chain(content.scoring_runner())
+                    .chain(content.error_by())
+                    .chain(play.movements.iter().map(|movement| &movement.runner))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        self.check_declared(names, None)
+    }
+
+    /// Report everything implausible about the rosters in one problem: with `check_rosters`, a team without a pitcher or
+    /// catcher or with a player listed twice, and a team outside `roster_size`.
+    fn check_roster_plausibility(&mut self) -> PyResult<()> {
+        let mut violations = Vec::new();
+        let teams = [
+            (self.game_builder.home_team_id, &self.game_builder.home_team_players),
+            (self.game_builder.away_team_id, &self.game_builder.away_team_players),
+        ];
+        for (team_id, players) in teams {
+            let team = format!("Team {}", team_id.map_or("?".to_string(), |id| id.to_string()));
+            if self.options.check_rosters {
+                for position in [Position::Pitcher, Position::Catcher] {
+                    if !players.iter().any(|player| player.position == position) {
+                        violations.push(format!("{} has no {}", team, position.to_string().to_lowercase()));
+                    }
+                }
+
+                let mut seen = HashSet::new();
+                let mut duplicates = Vec::new();
+                for player in players.iter() {
+                    if !seen.insert(&player.name) && !duplicates.contains(&player.name.as_str()) {
+                        duplicates.push(player.name.as_str());
+                    }
+                }
+                if !duplicates.is_empty() {
+                    violations.push(format!("{} lists more than once: {}", team, duplicates.join(", ")));
+                }
+            }
+
+            if let Some((min, max)) = self.options.roster_size {
+                if players.len() < min || players.len() > max {
+                    violations.push(format!("{} has {} players, expected {} to {}", team, players.len(), min, max));
+                }
+            }
+        }
+        if violations.is_empty() {
+            return Ok(());
+        }
+
+        self.report_problems(vec![Diagnostic::new(DiagnosticKind::ImplausibleRoster, violations.join("; "))])
+    }
+
+    /// Report the players who aren't on either team's roster, in one problem, unless they've been reported already.
+    fn check_declared(&mut self, names: Vec<SmallString>, inning: Option<Inning>) -> PyResult<()> {
+        let mut undeclared = Vec::new();
+        for name in names {
+            let declared = self.game_builder.home_team_players.iter()
+                .chain(self.game_builder.away_team_players.iter())
+                .any(|player| player.name == name);
+            if !declared && self.undeclared_players.insert(name.clone()) {
+                undeclared.push(name.to_string());
+            }
+        }
+        if undeclared.is_empty() {
+            return Ok(());
+        }
+
+        let problem = Diagnostic::new(
+            DiagnosticKind::UndeclaredPlayer,
+            format!("Not declared in either team section: {}", undeclared.join(", ")),
+        );
+        let problem = match inning {
+            Some(inning) => problem.in_inning(inning),
+            None => problem,
+        };
+        self.report_problems(vec![problem])
+    }
+
+    /// Reject the first problem in strict mode, or keep them all as warnings otherwise.
+    fn report_problems(&mut self, problems: Vec<Diagnostic>) -> PyResult<()> {
+        if self.options.strict {
+            if let Some(problem) = problems.into_iter().next() {
+                return Err(problem.into());
+            }
+        } else {
+            self.live_game_state.warnings.extend(problems.into_iter().map(Diagnostic::as_warning));
+        }
+
+        Ok(())
+    }
+
+    /// Return a regex that matches the name of any player on the batting or fielding team in the given half-inning,
+    /// or any player name if that team's roster hasn't been parsed yet.
+    fn roster_pattern(&self, top_bottom: TopBottom, batting: bool) -> String {
+        let players = self.team_players(top_bottom, batting);
+        if players.is_empty() {
+            return PLAYER_NAME.to_string();
+        }
+
+        let names = players.iter()
+            .map(|player| escape_regex(&player.name))
+            .collect::<Vec<_>>()
+            .join("|");
+        format!("({})", names)
+    }
+
+    /// Return a regex that matches a player on the fielding team in the given half-inning. A live pattern leaves out the
+    /// players a defensive substitution has taken out of the game, and for a defensive switch, anyone not in the field.
+    fn fielder_pattern(&self, top_bottom: TopBottom, live: bool, in_field: bool) -> String {
+        let players = self.team_players(top_bottom, false);
+        let alignment = self.live_game_state.fielding_alignment_in(top_bottom);
+        if !live || self.options.skip_validation || players.is_empty() {
+            return self.roster_pattern(top_bottom, false);
+        }
+
+        let names = players.iter()
+            .map(|player| &player.name)
+            .filter(|name| !alignment.has_left_game(name))
+            .filter(|name| !(in_field && alignment.has_roster()) || alignment.position_of(name).is_some())
+            .map(|name| escape_regex(name))
+            .collect::<Vec<_>>()
+            .join("|");
+        format!("({})", names)
+    }
+
+    /// Return a regex that matches a batter in the given half-inning. When constraining the next batter of a live
+    /// pattern, this is only the batter due up and the team's pinch hitters, once the team has been through its batting
+    /// order.
+    fn batter_pattern(&self, top_bottom: TopBottom, live: bool) -> String {
+        let due_batter = self.live_game_state.batting_order(top_bottom).due_batter();
+        let (true, true, Some(due_batter)) = (live, self.options.constrain_next_batter, due_batter) else {
+            return self.roster_pattern(top_bottom, true);
+        };
+
+        let pinch_hitters = self.team_players(top_bottom, true).iter()
+            .filter(|player| player.position == Position::PinchHitter)
+            .map(|player| &player.name);
+        let names = std::iter::once(due_batter)
+            .chain(pinch_hitters)
+            .map(|name| escape_regex(name))
+            .collect::<Vec<_>>()
+            .join("|");
+        format!("({})", names)
+    }
+
+    /// Return a regex that matches the inner part of a play of a given type.
+    fn inner_pattern_from_play_type(&self, play_type: &PlayType, top_bottom: TopBottom, live: bool) -> String {
+        let mut s = format!(r"\[PLAY\] {} ", play_type.to_string());
+        let fielding_team = self.fielder_pattern(top_bottom, live, false);
+
+        if play_type.allows_base() {
+            // optional, so it's a group of its own
+            let base = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_BASE_REGEX.as_str(), "");
+            s.push_str(&format!("({} )?", base));
+        }
+        if play_type.requires_base() {
+            let base = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_BASE_REGEX.as_str(), "");
+            s.push_str(&base);
+            s.push_str(" ");
+        }
+        if play_type.requires_batter() {
+            let batter = format!(r"\[BATTER\] {}", self.batter_pattern(top_bottom, live));
+            s.push_str(&batter);
+            s.push_str(" ");
+        }
+        if play_type.requires_pitcher() {
+            let pitcher = format!(r"\[PITCHER\] {}", fielding_team);
+            s.push_str(&pitcher);
+            s.push_str(" ");
+        }
+        if play_type.requires_catcher() {
+            let catcher = format!(r"\[CATCHER\] {}", fielding_team);
+            s.push_str(&catcher);
+            s.push_str(" ");
+        }
+        if play_type.requires_fielders() {
+            let fielders = format!(
+                "{tag} {name}(, {name})*",
+                tag=PLAY_SECTION_FIELDERS_TAG.replace("[", r"\[").replace("]", r"\]"),
+                name=self.fielder_pattern(top_bottom, live, *play_type == PlayType::DefensiveSwitch),
+            );
+
+            s.push_str(&fielders);
+            s.push_str(" ");
+        }
+        if play_type.requires_position() {
+            let position = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_POSITION_REGEX.as_str(), "");
+            s.push_str(&position);
+            s.push(' ');
+        }
+        if play_type.requires_runner() {
+            let runner = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_RUNNER_REGEX.as_str(), "");
+            s.push_str(&runner);
+            s.push_str(" ");
+        }
+        if play_type.requires_scoring_runner() {
+            let scoring_runner = CAPTURE_GROUP_REGEX.replace_all(PLAY_SECTION_SCORING_RUNNER_REGEX.as_str(), "");
+            s.push_str(&scoring_runner);
+            s.push_str(" ");
+        }
+        if play_type.allows_error_by() {
+            // optional, so it brings its own leading space
+            s = format!(r"{}( \[ERROR_BY\] {})?", s.trim_end(), fielding_team);
+        }
+
+        s.trim().replace("^", "")
+    }
+
+    /// Return a regex that matches the movements part of a play. A live pattern only allows the runners on base and the
+    /// outs left in the half-inning in progress.
+    fn movements_regex(&self, top_bottom: TopBottom, live: bool) -> String {
+        let live = live && !self.options.skip_validation;
+        let mut s = PLAY_SECTION_MOVEMENTS_TAG.replace("[", r"\[").replace("]", r"\]");
+        s.push_str(" ");
+
+        // only the batting team's pinch runners who haven't yet entered can take over a base
+        let pinch_runners = self.pinch_runners.batting(top_bottom).iter()
+            .filter(|name| !self.live_game_state.entered_pinch_runners.contains(*name))
+            .map(|name| escape_regex(name))
+            .collect::<Vec<_>>()
+            .join("|");
+
+        let mut valid_movement_patterns = Vec::new();
+        // only the batting team starts from home, and its roster includes its pinch runners
+        let batting_team = self.roster_pattern(top_bottom, true);
+        let home_to_any = format!(r"{batting_team} home -> (1|2|3|4|home)");
+        valid_movement_patterns.push(home_to_any);
+
+        // the runners on base belong to the half-inning in progress, and the other half starts with the bases empty
+        let empty_bases = RunnerPositions::empty();
+        let runner_positions = if live && top_bottom == self.live_game_state.inning.top_bottom {
+            &self.live_game_state.runner_positions
+        } else {
+            &empty_bases
+        };
+
+        if !live {
+            // runners aren't tracked, or it's not the next play, so any batting player may be on any base
+            valid_movement_patterns.push(format!(r"{batting_team} 1 -> (2|3|4|home)"));
+            valid_movement_patterns.push(format!(r"{batting_team} 2 -> (3|4|home)"));
+            valid_movement_patterns.push(format!(r"{batting_team} 3 -> (4|home)"));
+        }
+
+        // a runner may carry on from a later base in the same play, e.g. "A 1 -> 2, A 2 -> 3", so each base is left by
+        // the runners on it or on the bases before it. a pinch runner can only take over a base someone is on
+        let mut runners = Vec::new();
+        for (base, runner, to) in [
+            (1, &runner_positions.first, "(2|3|4|home)"),
+            (2, &runner_positions.second, "(3|4|home)"),
+            (3, &runner_positions.third, "(4|home)"),
+        ] {
+            if let Some(runner) = runner {
+                runners.push(escape_regex(runner));
+            }
+            if runners.is_empty() {
+                continue;
+            }
+
+            let names = runners.iter()
+                .cloned()
+                .chain((runner.is_some() && !pinch_runners.is_empty()).then(|| pinch_runners.clone()))
+                .collect::<Vec<_>>()
+                .join("|");
+            valid_movement_patterns.push(format!(r"({names}) {base} -> {to}"));
+        }
+
+        let safe = valid_movement_patterns.iter()
+            .map(|s| format!(r"({})( \[on error\])?", s))
+            .collect::<Vec<_>>()
+            .join("|");
+        let out = format!(r"({safe}) {}", MOVEMENT_OUT.replace("?P<base>", "").replace("?P<credits>", ""));
+
+        // a half-inning has at most three outs, so only allow the outs that are left. once three outs
+        // have been made, or in the other half, the next play starts a new half-inning
+        let outs = if live && top_bottom == self.live_game_state.inning.top_bottom { self.live_game_state.outs } else { 0 };
+        let outs_left = if outs >= 3 { 3 } else { 3 - outs as usize };
+        let more_outs = |n: usize| format!(r"(, {out}(, ({safe}))*)?").repeat(n);
+        let many = format!(
+            r"((({safe})(, ({safe}))*{})|({out}(, ({safe}))*{}))",
+            more_outs(outs_left),
+            more_outs(outs_left - 1),
+        );
+        s.push_str(&many);
+
+        s
+    }
+
+    /// Return a regex that matches the next play, which is in the current half-inning or the one after it. Any inning
+    /// is allowed if validation is skipped.
+    pub fn play_regex(&self) -> String {
+        self.play_pattern(true)
+    }
+
+    /// Return a regex that matches a play. A live pattern is constrained by the game state to the next play, while
+    /// otherwise any inning is allowed and any batting player may be on any base, as for the plays after the next.
+    fn play_pattern(&self, live: bool) -> String {
+        // which team is batting depends on the half-inning, so each half gets its own players. the next play is either
+        // in the current half-inning or the one after it, which pins down the number of each half, unless the game
+        // isn't being validated. once the current half-inning has its three outs, only the one after it is left
+        let current = self.live_game_state.inning;
+        let current_is_over = live && !self.options.skip_validation && self.live_game_state.outs >= 3;
+        let halves = [TopBottom::Top, TopBottom::Bottom].into_iter()
+            .filter(|top_bottom| !(current_is_over && *top_bottom == current.top_bottom))
+            .map(|top_bottom| {
+                let number = if self.options.skip_validation || !live {
+                    r"\d{1,2}".to_string()
+                } else if top_bottom == TopBottom::Top && current.top_bottom == TopBottom::Bottom {
+                    (current.number + 1).to_string()
+                } else {
+                    current.number.to_string()
+                };
+                let inning = format!(r"\[INNING\] {} {}", number, top_bottom.to_string());
+                let (no_movements, all_plays) = PlayType::iter().partition::<Vec<_>, _>(|play_type| play_type.allows_no_movements());
+                let inner = |play_types: Vec<PlayType>| play_types.iter()
+                    .map(|play_type| format!("({})", self.inner_pattern_from_play_type(play_type, top_bottom, live)))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                let movements = self.movements_regex(top_bottom, live);
+
+                format!(
+                    "({} (({}) {}|({}) {} ))",
+                    inning,
+                    inner(all_plays),
+                    movements,
+                    inner(no_movements),
+                    escape_regex(PLAY_SECTION_MOVEMENTS_TAG),
+                )
+            })
+            .collect::<Vec<_>>();
 
         format!(
-            "{} ({}) {}{}",
-            inning,
-            inner,
-            movements,
+            "({}){}",
+            halves.join("|"),
             PLAY_SECTION_PLAY_END,
         )
     }
+
+    /// Return a regex that matches the context and team sections, up to `[GAME_START]`.
+    fn pregame_regex(&self) -> String {
+        let game = CAPTURE_GROUP_REGEX.replace_all(CONTEXT_SECTION_GAME_REGEX.as_str(), "").replace("^", "");
+        let date = CAPTURE_GROUP_REGEX.replace_all(CONTEXT_SECTION_DATE_REGEX.as_str(), "").replace("^", "");
+        let venue = CAPTURE_GROUP_REGEX.replace_all(CONTEXT_SECTION_VENUE_REGEX.as_str(), "").replace("^", "");
+        let weather = CAPTURE_GROUP_REGEX.replace_all(CONTEXT_SECTION_WEATHER_REGEX.as_str(), "").replace("^", "");
+        let context_section_regex = format!(
+            "{} {} {} {}",
+            game,
+            date,
+            venue,
+            weather,
+        );
+
+        let team = CAPTURE_GROUP_REGEX.replace_all(TEAM_SECTION_TEAM_REGEX.as_str(), "").replace("^", "");
+        let player = CAPTURE_GROUP_REGEX.replace_all(TEAM_SECTION_PLAYER_REGEX.as_str(), "").replace("^", "");
+        let team_section_regex = format!(
+            "{}{}({})({}{})*",
+            team,
+            LINE_BREAK,
+            player,
+            LINE_BREAK,
+            player,
+        );
+
+        let section_break = LINE_BREAK.repeat(2);
+        format!(
+            "{}{}{}{}{}{}",
+            context_section_regex,
+            section_break,
+            team_section_regex,
+            section_break,
+            team_section_regex,
+            section_break,
+        )
+    }
 }
 
 #[pymethods]
 impl Parser {
+    /// Create a parser with the default options, or the given ones, with any option also settable by keyword, e.g.
+    /// `Parser(strict=True, print_debug=False)`. Keywords take precedence over `options`.
     #[new]
-    fn new(print_debug: bool) -> Self {
-        Self {
-            input_buffer: String::new(),
-            possible_sections: vec![GameSection::Context(ContextSection::Game)],
-            game_builder: GameBuilder::new(),
-            finished: false,
-            print_debug,
-            live_game_state: LiveGameState::new(),
-            pinch_runners: Vec::new(),
-        }
+    #[pyo3(signature = (options=None, **kwargs))]
+    fn py_new(options: Option<ParserOptions>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let options = match kwargs {
+            Some(kwargs) => options.unwrap_or_default().with_kwargs(kwargs)?,
+            None => options.unwrap_or_default(),
+        };
+
+        Ok(Self::with_options(options))
+    }
+
+    /// Create a parser with the given options.
+    #[staticmethod]
+    pub fn with_options(options: ParserOptions) -> Self {
+        let mut parser = Self::new(false);
+        parser.venue_registry = options.venues.clone().map(|venues| Arc::new(VenueRegistry::new(venues)));
+        parser.options = options;
+        parser
     }
 
     /// Stream-parse a game and return the set of valid next characters.
     pub fn parse_input(&mut self, input: &str) -> PyResult<()> {
-        let input = INITIAL_NEWLINES_REGEX.replace(input, "");
-        self.input_buffer.push_str(&input);
+        // control characters other than whitespace only appear in corrupted input
+        if let Some((index, c)) = input.char_indices().find(|(_, c)| c.is_control() && !c.is_whitespace()) {
+            return Err(Diagnostic::new(DiagnosticKind::InvalidControlCharacter, format!(
+                "Invalid control character {:?} at byte {}: {}",
+                c,
+                self.bytes_received + index,
+                hex_window(input.as_bytes(), index),
+            )).into());
+        }
+        self.bytes_received += input.len();
+        self.push_input(input);
 
         loop {
             if self.finished {
@@ -1164,9 +2110,241 @@ impl Parser {
             let success = self.parse_input_buffer()?;
 
             if !success {
-                return Ok(());
+                return match self.options.max_buffer_bytes {
+                    Some(max_buffer_bytes) if self.input_buffer.len() > max_buffer_bytes => Err(Diagnostic::new(DiagnosticKind::BufferLimitExceeded, format!(
+                        "{} bytes of input buffered without completing a section, more than the maximum of {}; {}",
+                        self.input_buffer.len(),
+                        max_buffer_bytes,
+                        self.expected_hint(),
+                    )).into()),
+                    _ => Ok(()),
+                };
+            }
+        }
+    }
+
+    /// Parse a full game without keeping it, returning whether it's valid along with any errors and warnings.
+    #[staticmethod]
+    #[pyo3(signature = (text, options=None))]
+    pub fn validate(py: Python<'_>, text: &str, options: Option<ParserOptions>) -> ValidationReport {
+        let mut parser = Self::with_options(options.unwrap_or_default());
+        let mut errors = Vec::new();
+        if let Err(e) = parser.parse_input(text) {
+            let value = e.value(py);
+            errors.push(match value.extract::<PyRef<ParseError>>() {
+                Ok(parse_error) => parse_error.diagnostic.clone(),
+                Err(_) => Diagnostic::new(DiagnosticKind::InvalidInput, value.to_string()),
+            });
+        } else if !parser.finished {
+            errors.push(Diagnostic::new(
+                DiagnosticKind::IncompleteGame,
+                format!("Game ended early, {}", parser.expected_hint()),
+            ));
+        } else if parser.game_builder.build().is_none() {
+            errors.push(Diagnostic::new(DiagnosticKind::MissingSection, "Game is missing a required section"));
+        }
+
+        ValidationReport {
+            ok: errors.is_empty(),
+            errors,
+            warnings: parser.live_game_state.warnings,
+            plays: parser.game_builder.plays.len(),
+            innings: parser.game_builder.plays.last().map_or(0, |play| play.inning.number),
+            home_team_score: parser.live_game_state.home_team_score,
+            away_team_score: parser.live_game_state.away_team_score,
+        }
+    }
+
+    /// Stream-parse a game like `parse_input`, returning the plays completed by this call.
+    pub fn feed(&mut self, input: &str) -> PyResult<Vec<Play>> {
+        let plays_before = self.game_builder.plays.len();
+        self.parse_input(input)?;
+
+        Ok(self.game_builder.plays.iter().skip(plays_before).cloned().collect())
+    }
+
+    /// Return the events queued since the last call, oldest first, and clear the queue.
+    pub fn poll_events(&mut self) -> Vec<ParserEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Stream-parse raw bytes, which may split a UTF-8 character between calls.
+    pub fn parse_bytes(&mut self, input: &[u8]) -> PyResult<()> {
+        let mut bytes = std::mem::take(&mut self.pending_bytes);
+        bytes.extend_from_slice(input);
+
+        let valid_up_to = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            // an incomplete character at the end may be completed by the next call
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(Diagnostic::new(DiagnosticKind::InvalidUtf8, format!(
+                "Invalid UTF-8 at byte {}: {}",
+                self.bytes_received + e.valid_up_to(),
+                hex_window(&bytes, e.valid_up_to()),
+            )).into()),
+        };
+
+        self.pending_bytes = bytes.split_off(valid_up_to);
+        self.parse_input(std::str::from_utf8(&bytes).unwrap())
+    }
+
+    /// Export the state of the game parsed so far as JSON, without the raw input buffer.
+    pub fn export_state(&self) -> PyResult<String> {
+        self.game_builder.to_json()
+    }
+
+    /// Create a parser that continues from exported state, expecting the next play (or the end of the game), or the
+    /// rest of the away roster if the state was exported before `[GAME_START]`.
+    #[staticmethod]
+    #[pyo3(signature = (state, options=None))]
+    pub fn from_state(state: &str, options: Option<ParserOptions>) -> PyResult<Self> {
+        let game_builder = GameBuilder::from_json(state)?;
+        if game_builder.away_team_id.is_none() {
+            return Err(Diagnostic::new(DiagnosticKind::InvalidState, "State can only be resumed once both team sections have been parsed").into());
+        }
+
+        let live_game_state = game_builder.replay_plays().map_err(|e| Diagnostic::new(DiagnosticKind::InvalidState, e))?;
+        let pinch_runners = game_builder.pinch_runners();
+
+        let mut parser = Self::with_options(options.unwrap_or_default());
+        // plays can only have been parsed after the game started, whatever state older exports without the flag give
+        parser.possible_sections = if !game_builder.game_started && game_builder.plays.is_empty() {
+            match game_builder.away_team_players.is_empty() {
+                true => vec![GameSection::AwayTeam(TeamSection::Player)],
+                false => vec![
+                    GameSection::AwayTeam(TeamSection::Player),
+                    GameSection::Plays(PlaySection::GameStart()),
+                ],
+            }
+        } else if game_builder.plays.is_empty() {
+            vec![
+                GameSection::Plays(PlaySection::Overturned()),
+                GameSection::Plays(PlaySection::Inning()),
+            ]
+        } else {
+            vec![
+                GameSection::Plays(PlaySection::Overturned()),
+                GameSection::Plays(PlaySection::Inning()),
+                GameSection::Plays(PlaySection::GameEnd()),
+            ]
+        };
+        parser.game_builder = game_builder;
+        parser.live_game_state = live_game_state;
+        parser.pinch_runners = pinch_runners;
+
+        Ok(parser)
+    }
+
+    /// Return a snapshot of the live game state after the plays parsed so far.
+    pub fn game_state(&self) -> GameState {
+        GameState::from_live_state(self.game_builder.plays.len().saturating_sub(1), &self.live_game_state)
+    }
+
+    /// Return the runs scored in each half-inning parsed so far, in order, as `(inning, runs)` pairs.
+    pub fn inning_scores(&self) -> Vec<(Inning, u64)> {
+        self.live_game_state.inning_scores.clone()
+    }
+
+    /// Return the batter in the most recent play, if any.
+    #[getter]
+    pub fn current_batter(&self) -> Option<String> {
+        self.live_game_state.current_batter.as_ref().map(|batter| batter.to_string())
+    }
+
+    /// Return the pitcher in the most recent play, if any. Every play with a pitcher after a `PitchingChange` event has the new one.
+    #[getter]
+    pub fn current_pitcher(&self) -> Option<String> {
+        self.live_game_state.current_pitcher.as_ref().map(|pitcher| pitcher.to_string())
+    }
+
+    /// Return the problems found outside strict mode that strict mode would have rejected, e.g. a fourth out in a half-inning.
+    #[getter]
+    pub fn warnings(&self) -> Vec<String> {
+        self.live_game_state.warnings.iter().map(|warning| warning.message.clone()).collect()
+    }
+
+    /// Return the same problems as `warnings`, as diagnostics with codes such as `W305`.
+    #[getter]
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.live_game_state.warnings.clone()
+    }
+
+    /// Return the time spent parsing so far, as `{"sections": {name: (calls, seconds)}, "regexes": {...}}`.
+    /// Sections are grouped by kind, e.g. `"Plays"`, and regexes by the section they match, e.g. `"Plays(Batter())"`.
+    /// Only available when built with the `perf-stats` feature.
+    #[cfg(feature = "perf-stats")]
+    pub fn perf_stats(&self) -> std::collections::HashMap<String, std::collections::HashMap<String, (u64, f64)>> {
+        self.perf_stats.as_map()
+    }
+
+    /// Return the tokens the parser will accept next, e.g. `["[INNING]", "[GAME_END]"]`.
+    pub fn expected_tokens(&self) -> Vec<String> {
+        let mut tokens = Vec::new();
+        for section in &self.possible_sections {
+            let token = section.expected_token();
+            if !tokens.contains(&token) {
+                tokens.push(token);
+            }
+        }
+
+        tokens
+    }
+
+    /// Return an example of the text each expected section would accept next, e.g. `["[INNING] ", "[GAME_END]"]`,
+    /// taken from the sections' own regexes. Useful for finding where generated text went wrong.
+    pub fn expected_examples(&self) -> Vec<String> {
+        let mut examples = Vec::new();
+        for section in &self.possible_sections {
+            let example = self.section_example(section);
+            if !examples.contains(&example) {
+                examples.push(example);
+            }
+        }
+
+        examples
+    }
+
+    /// Return up to `n` of the shortest texts that would complete the input waiting to be parsed into the next section,
+    /// e.g. `[" Fly", " Bunt"]` after `[PLAY] Sac`. Each is the rest of a section example, play type, position or
+    /// rostered player name that the buffered text is a prefix of, shortest first. Whitespace between sections is left
+    /// to the caller, since the parser accepts any. Useful for auto-complete, and for repairing truncated games.
+    #[pyo3(signature = (n=5))]
+    pub fn suggest_completions(&self, n: usize) -> Vec<String> {
+        if self.finished {
+            return Vec::new();
+        }
+
+        let buffer = self.input_buffer.trim_start();
+        let mut completions = Vec::new();
+        for section in &self.possible_sections {
+            for candidate in self.section_candidates(section) {
+                let Some(rest) = candidate.strip_prefix(buffer) else {
+                    continue;
+                };
+                if !rest.is_empty() && !completions.iter().any(|completion| completion == rest) {
+                    completions.push(rest.to_string());
+                }
             }
         }
+        completions.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        completions.truncate(n);
+
+        completions
+    }
+
+    /// Return whether any text could follow the input so far: the game isn't finished, the input waiting to be parsed
+    /// could still start a section the parser expects, and no name outside the rosters was let through with a warning,
+    /// which `valid_regex()` could never match. A sampling loop can backtrack once this is false.
+    pub fn can_continue(&self) -> bool {
+        let left_rosters = self.live_game_state.warnings.iter()
+            .any(|warning| matches!(warning.kind, DiagnosticKind::FielderNotOnFieldingTeam | DiagnosticKind::UndeclaredPlayer));
+
+        !self.finished && !left_rosters && self.possible_sections.iter().any(|section| self.could_start(section))
+    }
+
+    /// Return whether the game can't be finished from here, i.e. it isn't finished and nothing can follow the input.
+    pub fn is_dead_end(&self) -> bool {
+        !self.finished && !self.can_continue()
     }
 
     /// Return the completed game if the parser is finished.
@@ -1178,143 +2356,1185 @@ impl Parser {
         }
     }
 
-    /// Return a regex that matches a full valid game, taking into account the current game state.
-    pub fn valid_regex(&self) -> String {
-        let game = CAPTURE_GROUP_REGEX.replace_all(CONTEXT_SECTION_GAME_REGEX.as_str(), "").replace("^", "");
-        let date = CAPTURE_GROUP_REGEX.replace_all(CONTEXT_SECTION_DATE_REGEX.as_str(), "").replace("^", "");
-        let venue = CAPTURE_GROUP_REGEX.replace_all(CONTEXT_SECTION_VENUE_REGEX.as_str(), "").replace("^", "");
-        let weather = CAPTURE_GROUP_REGEX.replace_all(CONTEXT_SECTION_WEATHER_REGEX.as_str(), "").replace("^", "");
-        let context_section_regex = format!(
-            "{} {} {} {}",
-            game,
-            date,
-            venue,
-            weather,
-        );
+    /// Close out a game whose input was cut off in its plays, returning the game along with a description of each
+    /// repair. The play in progress is ended where it stands if that makes a complete play, and dropped otherwise, as
+    /// is any other unparsed text or an overturned call still waiting for its correction. `[GAME_END]` is then added,
+    /// with a note if the game could not legally end there. A finished game is returned as is.
+    pub fn finalize_truncated(&mut self) -> PyResult<(Game, Vec<String>)> {
+        let mut repairs = Vec::new();
+        if !self.finished {
+            let in_plays = self.possible_sections.iter()
+                .any(|section| matches!(section, GameSection::Plays(play_section) if *play_section != PlaySection::GameStart()));
+            if !in_plays {
+                return Err(Diagnostic::new(
+                    DiagnosticKind::IncompleteGame,
+                    format!("Only a game cut off after [GAME_START] can be repaired, {}", self.expected_hint()),
+                ).into());
+            }
+
+            let between_plays = self.possible_sections.contains(&GameSection::Plays(PlaySection::GameEnd()));
+            if !between_plays {
+                // no play is in progress right after [GAME_START], or while an overturned call waits for its correction
+                let in_play = self.play_start.is_some();
+                let inning = self.game_builder.play_builder.inning;
+                let mut ended = self.fork();
+                let is_ended = in_play
+                    && ended.parse_input(PLAY_SECTION_PLAY_END).is_ok()
+                    && ended.possible_sections.contains(&GameSection::Plays(PlaySection::GameEnd()));
+                if is_ended {
+                    *self = ended;
+                    repairs.push(format!("Ended the play in progress with {:?}", PLAY_SECTION_PLAY_END));
+                } else {
+                    self.game_builder.reset_play_builder();
+                    self.play_start = None;
+                    self.possible_sections = vec![
+                        GameSection::Plays(PlaySection::Overturned()),
+                        GameSection::Plays(PlaySection::Inning()),
+                        GameSection::Plays(PlaySection::GameEnd()),
+                    ];
+                    if in_play {
+                        repairs.push(match inning {
+                            Some(inning) => format!("Dropped the unfinished play in inning {}", inning.to_string()),
+                            None => "Dropped the unfinished play".to_string(),
+                        });
+                    }
+                }
+            }
+            let buffered = std::mem::take(&mut self.input_buffer);
+            if !buffered.trim().is_empty() {
+                repairs.push(format!("Dropped the unparsed text {:?}", buffered.trim()));
+            }
+            if let Some(overturned) = self.game_builder.overturned_call.take() {
+                repairs.push(format!("Dropped the overturned call in inning {} with no play correcting it", overturned.inning.to_string()));
+            }
+
+            let state = &self.live_game_state;
+            repairs.push(match self.options.skip_validation || state.could_end() {
+                true => format!("Added {}", PLAY_SECTION_GAME_END),
+                false => format!(
+                    "Added {}, though the game could not legally end in inning {} with the score {}-{}",
+                    PLAY_SECTION_GAME_END,
+                    state.inning.to_string(),
+                    state.away_team_score,
+                    state.home_team_score,
+                ),
+            });
+            self.parse_input(PLAY_SECTION_GAME_END)?;
+        }
+
+        let game = self.complete().ok_or_else(|| Diagnostic::new(DiagnosticKind::MissingSection, "Game is missing a required section"))?;
+
+        Ok((game, repairs))
+    }
+
+    /// Return an independent copy of the parser, e.g. for each hypothesis in a beam search.
+    /// The parsed rosters and plays are shared with the original, so the cost of forking doesn't grow with the length of
+    /// the game. The rest of the parser is copied, including the input waiting to be parsed and the live game state.
+    pub fn fork(&self) -> Self {
+        self.clone()
+    }
+
+    /// Clear the input and everything parsed from it, keeping the options, so the parser can be reused for the next
+    /// game without being created again. Performance stats keep accumulating across games.
+    pub fn reset(&mut self) {
+        self.input_buffer.clear();
+        self.input_buffer.keep_from(0);
+        self.possible_sections.clear();
+        self.possible_sections.push(GameSection::Context(ContextSection::Game));
+        self.game_builder = GameBuilder::new();
+        self.finished = false;
+        self.undeclared_players.clear();
+        self.live_game_state = LiveGameState::new();
+        self.pinch_runners = PinchRunners::default();
+        self.bytes_received = 0;
+        self.consumed_bytes = 0;
+        self.source_offsets.clear();
+        self.play_start = None;
+        self.pending_bytes.clear();
+        self.events.clear();
+    }
+
+    /// Return the valid next characters for each prefix under this parser's `valid_regex()`, checking only the
+    /// characters in `alphabet` if it's given.
+    #[pyo3(signature = (prefixes, alphabet=None))]
+    pub fn next_valid_chars_batch(&self, py: Python<'_>, prefixes: Vec<String>, alphabet: Option<&str>) -> PyResult<Vec<Vec<char>>> {
+        let regex = crate::compile_pattern(&self.valid_regex())?;
+        let alphabet = crate::alphabet_chars(alphabet);
+        Ok(py.allow_threads(|| crate::next_valid_chars_batch(&prefixes, &regex, alphabet.as_deref())))
+    }
+
+    /// Return a regex that matches a full valid game. Only the rosters parsed so far constrain its plays, so it still
+    /// matches the plays already parsed; `remaining_regex()` also follows the game state.
+    pub fn valid_regex(&self) -> String {
+        let game_start = PLAY_SECTION_GAME_START.replace("[", r"\[").replace("]", r"\]");
+        let game_end = PLAY_SECTION_GAME_END.replace("[", r"\[").replace("]", r"\]");
+        let play_section_regex = format!(
+            "{}{}({}{})+{}",
+            game_start,
+            LINE_BREAK,
+            self.play_pattern(false),
+            LINE_BREAK,
+            game_end,
+        );
+
+        format!("{}{}", self.pregame_regex(), play_section_regex).replace("^", "")
+    }
+
+    /// Return a regex that matches the rest of the game from `remaining_input()`, i.e. after the last completed play, or
+    /// the whole game before the first. The next play is constrained by the game state, like `play_regex()`, and
+    /// `[GAME_END]` is only matched next once the game could legally end, i.e. after a complete ninth or later inning
+    /// with a winner, or once the home team takes the lead in the bottom half of one.
+    pub fn remaining_regex(&self) -> String {
+        let game_end = PLAY_SECTION_GAME_END.replace("[", r"\[").replace("]", r"\]");
+        let plays = format!(
+            "{}({}{})*{}",
+            self.play_regex(),
+            LINE_BREAK,
+            self.play_pattern(false),
+            LINE_BREAK,
+        );
+
+        if self.game_builder.plays.is_empty() {
+            let game_start = PLAY_SECTION_GAME_START.replace("[", r"\[").replace("]", r"\]");
+            return format!("{}{}{}{}{}", self.pregame_regex(), game_start, LINE_BREAK, plays, game_end).replace("^", "");
+        }
+
+        // only allow the game to end now where it legally could, so constrained generation can't stop a tied game
+        // early. runners and the score aren't tracked without validation, so then it may end anywhere
+        let plays = if self.options.skip_validation || self.live_game_state.could_end() {
+            format!("({})?", plays)
+        } else {
+            plays
+        };
+        format!("{}{}{}", LINE_BREAK, plays, game_end).replace("^", "")
+    }
+
+    /// Return the input `remaining_regex()` starts from, which is everything since the end of the last completed play, as
+    /// the parser normalised it, e.g. with `\r\n` line endings as `\n`, and with `lenient_whitespace`, each run of
+    /// whitespace as a single space.
+    pub fn remaining_input(&self) -> String {
+        self.input_buffer.kept().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strict_parser() -> Parser {
+        let mut options = ParserOptions::default();
+        options.set_strict(true);
+        Parser::with_options(options)
+    }
+
+    mod parsing_tests {
+
+        use super::*;
+        use game::Movement;
+        use state::RunnerPositions;
+
+        #[test]
+        fn parse_game_pk() {
+            let mut parser = Parser::new(false);
+            let input = "[GAME] 766493";
+            let _ = parser.parse_input(input);
+
+            if let Some(game_pk) = parser.game_builder.game_pk {
+                assert_eq!(game_pk, 766493);
+            } else {
+                panic!("game_pk is None");
+            }
+        }
+
+        #[test]
+        fn parse_date() {
+            let mut parser = Parser::new(false);
+            let input = "[GAME] 766493 [DATE] 2024-03-24";
+
+            let _ = parser.parse_input(input);
+
+            if let Some(date) = parser.game_builder.date {
+                assert_eq!(date, "2024-03-24");
+            } else {
+                panic!("date is None");
+            }
+        }
+
+        #[test]
+        fn parse_partial_input_is_ok() {
+            let mut parser = Parser::new(false);
+            let input = "[GAM";
+            let result = parser.parse_input(input);
+
+            assert!(result.is_ok());
+            assert_eq!(parser.possible_sections, vec![GameSection::Context(ContextSection::Game)]);
+
+            let input = "E] 766493";
+            let _ = parser.parse_input(input);
+
+            if let Some(game_pk) = parser.game_builder.game_pk {
+                assert_eq!(game_pk, 766493);
+            } else {
+                panic!("game_pk is None");
+            }
+        }
+
+        #[test]
+        fn parse_entire_context_section() {
+            let mut parser = Parser::new(false);
+            let input = "[GAME] 766493 [DATE] 2024-03-24 [VENUE] Estadio Alfredo Harp Helu [WEATHER] Sunny 85 9";
+
+            let _ = parser.parse_input(input);
+
+            if let Some(game_pk) = parser.game_builder.game_pk {
+                assert_eq!(game_pk, 766493);
+            } else {
+                panic!("game_pk is None");
+            }
+
+            if let Some(date) = parser.game_builder.date {
+                assert_eq!(date, "2024-03-24");
+            } else {
+                panic!("date is None");
+            }
+
+            if let Some(venue) = parser.game_builder.venue {
+                assert_eq!(venue, "Estadio Alfredo Harp Helu");
+            } else {
+                panic!("venue is None");
+            }
+
+            if let Some(weather_condition) = parser.game_builder.weather_condition {
+                assert_eq!(weather_condition, WeatherCondition::Sunny);
+            } else {
+                panic!("weather_condition is None");
+            }
+
+            if let Some(temperature) = parser.game_builder.weather_temperature {
+                assert_eq!(temperature, 85.0);
+            } else {
+                panic!("temperature is None");
+            }
+
+            if let Some(wind_speed) = parser.game_builder.weather_wind_speed {
+                assert_eq!(wind_speed, 9.0);
+            } else {
+                panic!("wind_speed is None");
+            }
+        }
+
+        #[test]
+        fn parse_weather_with_units() {
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Snow -2.5C 1").unwrap();
+            parser.parse_input("2.5k").unwrap();
+            parser.parse_input("ph\n\n[TEAM] 1").unwrap();
+
+            assert_eq!(parser.game_builder.weather_temperature, Some(-2.5));
+            assert_eq!(parser.game_builder.weather_temperature_unit, Some(TemperatureUnit::Celsius));
+            assert_eq!(parser.game_builder.weather_wind_speed, Some(12.5));
+            assert_eq!(parser.game_builder.weather_wind_speed_unit, Some(SpeedUnit::KilometersPerHour));
+            assert_eq!(parser.possible_sections, vec![GameSection::HomeTeam(TeamSection::Team)]);
+        }
+
+        #[test]
+        fn strict_mode_rejects_unknown_weather() {
+            let input = "[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Hazy 70 5\n\n[TEAM] 1";
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(input).unwrap();
+            assert_eq!(parser.game_builder.weather_condition, Some(WeatherCondition::Other("Hazy".into())));
+
+            let mut parser = strict_parser();
+            assert!(parser.parse_input(input).is_err());
+
+            let mut parser = strict_parser();
+            parser.parse_input(&input.replace("Hazy", "Partly Cloudy")).unwrap();
+            assert_eq!(parser.game_builder.weather_condition, Some(WeatherCondition::PartlyCloudy));
+        }
+
+        #[test]
+        fn strict_mode_rejects_unknown_venue() {
+            let input = "[GAME] 1 [DATE] 2024-04-01 [VENUE] Dodgers Stadium [WEATHER] Clear 70 5\n\n[TEAM] 1";
+
+            let mut options = ParserOptions::default();
+            options.set_strict(true).set_venues(vec!["Dodger Stadium".to_string()]);
+
+            let mut parser = Parser::with_options(options.clone());
+            assert!(parser.parse_input(input).is_err());
+
+            let mut parser = Parser::with_options(options.clone());
+            parser.parse_input(&input.replace("Dodgers", "Dodger")).unwrap();
+            assert_eq!(parser.game_builder.venue, Some("Dodger Stadium".into()));
+
+            // without strict mode the registry isn't checked
+            let mut parser = Parser::with_options(options.set_strict(false).clone());
+            parser.parse_input(input).unwrap();
+        }
+
+        #[test]
+        fn parse_wide_ids() {
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 123456789 [DATE] 2030-04-01 [VENUE] venue [WEATHER] Clear 70 5\n\n[TEAM] 4124\n").unwrap();
+
+            assert_eq!(parser.game_builder.game_pk, Some(123456789));
+            assert_eq!(parser.game_builder.home_team_id, Some(4124));
+        }
+
+        #[test]
+        fn parse_venue_with_punctuation() {
+            for venue in ["George M. Steinbrenner Field", "Estadio 23 de Marzo", "T-Mobile Park", "Ed Smith Stadium & Complex"] {
+                let mut parser = Parser::new(false);
+                parser.parse_input(&format!("[GAME] 1 [DATE] 2024-04-01 [VENUE] {} [WEATHER] Clear 70 5\n\n[TEAM] 1", venue)).unwrap();
+
+                assert_eq!(parser.game_builder.venue, Some(venue.into()));
+            }
+        }
+
+        #[test]
+        fn game_state_tracks_parsed_plays() {
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n").unwrap();
+
+            let state = parser.game_state();
+            assert_eq!(state.first, Some("Person D".into()));
+            assert_eq!(state.outs, 0);
+
+            parser.parse_input("[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home, Person D 1 -> home;\n").unwrap();
+
+            let state = parser.game_state();
+            assert_eq!(state.first, None);
+            assert_eq!(state.away_team_score, 2);
+        }
+
+        #[test]
+        fn inning_scores_tally_each_half_inning() {
+            let mut parser = Parser::new(false);
+            parser.parse_input(include_str!("../test_data/748231.txt")).unwrap();
+            let scores = parser.inning_scores();
+            assert_eq!(scores[0].0, Inning { number: 1, top_bottom: TopBottom::Top });
+            assert_eq!(scores[1].0, Inning { number: 1, top_bottom: TopBottom::Bottom });
+
+            let game = parser.complete().unwrap();
+            let summaries = half_innings::half_innings(&game).unwrap();
+            assert_eq!(scores, summaries.iter().map(|summary| (summary.inning, summary.runs)).collect::<Vec<_>>());
+            assert_eq!(game.replay().unwrap().inning_scores, scores);
+        }
+
+        #[test]
+        fn feed_returns_completed_plays() {
+            let mut parser = Parser::new(false);
+            let plays = parser.feed("[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n").unwrap();
+            assert_eq!(plays.len(), 1);
+            assert_eq!(plays[0].play_content.play_type(), PlayType::Single);
+
+            let plays = parser.feed("[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A ").unwrap();
+            assert!(plays.is_empty());
+
+            let plays = parser.feed("[MOVEMENTS] Person E home -> home, Person D 1 -> home;\n").unwrap();
+            assert_eq!(plays.len(), 1);
+            assert_eq!(plays[0].play_content.play_type(), PlayType::HomeRun);
+        }
+
+        #[test]
+        fn poll_events_drains_queue() {
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n").unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Strikeout [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home [out];\n").unwrap();
+
+            let events = parser.poll_events();
+            assert_eq!(events.len(), 3);
+            assert!(matches!(events[0], ParserEvent::InningChanged { inning: Inning { number: 1, top_bottom: TopBottom::Top } }));
+            assert!(matches!(&events[1], ParserEvent::PlayParsed { play } if play.play_content.play_type() == PlayType::Single));
+            assert!(matches!(&events[2], ParserEvent::PlayParsed { play } if play.play_content.play_type() == PlayType::Strikeout));
+            assert!(parser.poll_events().is_empty());
+
+            parser.parse_input("[INNING] 1 bottom [PLAY] Strikeout [BATTER] Person B [PITCHER] Person C [MOVEMENTS] Person B home -> home [out];\n[GAME_END]").unwrap();
+            let events = parser.poll_events();
+            assert_eq!(events.len(), 3);
+            assert!(matches!(events[0], ParserEvent::InningChanged { inning: Inning { top_bottom: TopBottom::Bottom, .. } }));
+            assert!(matches!(events[2], ParserEvent::GameFinished()));
+        }
+
+        #[test]
+        fn invalid_plays_are_not_reported() {
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n").unwrap();
+            parser.poll_events();
+
+            // nobody is on second base
+            assert!(parser.parse_input("[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1, Person E 2 -> 3;\n").is_err());
+            assert!(parser.poll_events().is_empty());
+        }
+
+        #[test]
+        fn current_batter_and_pitcher_follow_plays() {
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[PITCHER] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n").unwrap();
+            assert_eq!(parser.current_batter(), None);
+            assert_eq!(parser.current_pitcher(), None);
+
+            parser.parse_input("[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n").unwrap();
+            assert_eq!(parser.current_batter().as_deref(), Some("Person D"));
+            assert_eq!(parser.current_pitcher().as_deref(), Some("Person A"));
+
+            parser.parse_input("[INNING] 1 top [PLAY] Strikeout [BATTER] Person E [PITCHER] Person B [MOVEMENTS] Person E home -> home [out];\n").unwrap();
+            assert_eq!(parser.current_batter().as_deref(), Some("Person E"));
+            assert_eq!(parser.current_pitcher().as_deref(), Some("Person B"));
+
+            let events = parser.poll_events();
+            let changes = events.iter()
+                .filter_map(|event| match event {
+                    ParserEvent::PitchingChange { pitcher, replaced } => Some((pitcher.as_str(), replaced.as_str())),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(changes, vec![("Person B", "Person A")]);
+
+            // the other team's pitcher isn't a change
+            parser.parse_input("[INNING] 1 bottom [PLAY] Strikeout [BATTER] Person F [PITCHER] Person C [MOVEMENTS] Person F home -> home [out];\n").unwrap();
+            assert!(!parser.poll_events().iter().any(|event| matches!(event, ParserEvent::PitchingChange { .. })));
+            assert_eq!(parser.current_pitcher().as_deref(), Some("Person C"));
+        }
+
+        #[test]
+        fn validate_reports_errors() {
+            pyo3::prepare_freethreaded_python();
+
+            Python::with_gil(|py| {
+                let input = include_str!("../test_data/748231.txt");
+                let report = Parser::validate(py, input, None);
+                assert!(report.ok);
+                assert!(report.errors.is_empty());
+                assert!(report.innings >= 9);
+                assert_ne!(report.home_team_score, report.away_team_score);
+
+                let end = input.find("[INNING] 5 top").unwrap();
+                let report = Parser::validate(py, &input[..end], None);
+                assert!(!report.ok);
+                assert_eq!(report.errors[0].code(), "E104");
+                assert!(report.errors[0].message.starts_with("Game ended early"));
+                assert_eq!(report.innings, 4);
+
+                let report = Parser::validate(py, &input.replacen("[WEATHER]", "[WEATHR]", 1), None);
+                assert!(!report.ok);
+                assert_eq!(report.errors.len(), 1);
+                assert_eq!(report.plays, 0);
+            });
+        }
+
+        #[test]
+        fn fork_is_independent() {
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n").unwrap();
+
+            let mut fork = parser.fork();
+
+            fork.parse_input("[INNING] 1 top [PLAY] Strikeout [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home [out];\n").unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home, Person D 1 -> home;\n").unwrap();
+
+            assert_eq!(fork.game_builder.plays.len(), 2);
+            assert_eq!(parser.game_builder.plays.len(), 2);
+            assert_eq!(fork.game_state().outs, 1);
+            assert_eq!(fork.game_state().away_team_score, 0);
+            assert_eq!(parser.game_state().outs, 0);
+            assert_eq!(parser.game_state().away_team_score, 2);
+        }
+
+        #[test]
+        fn early_game_end_is_rejected_in_strict_mode() {
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n[GAME_END]";
+
+            let mut parser = strict_parser();
+            let err = parser.parse_input(input).unwrap_err();
+            assert!(err.to_string().contains("could not legally end in inning 1 top with the score 0-0"));
+            assert!(!parser.finished);
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(input).unwrap();
+            assert!(parser.finished);
+        }
+
+        #[test]
+        fn fourth_out_is_rejected_in_strict_mode() {
+            let mut input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n".to_string();
+            for batter in ["Person D", "Person E", "Person F", "Person G"] {
+                input.push_str(&format!("[INNING] 1 top [PLAY] Strikeout [BATTER] {batter} [PITCHER] Person A [MOVEMENTS] {batter} home -> home [out];\n"));
+            }
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(&input).unwrap();
+            assert_eq!(parser.warnings(), vec!["Inning 1 top: 4 outs in a half-inning"]);
+            assert_eq!(parser.diagnostics()[0].code(), "W305");
+
+            let mut parser = strict_parser();
+            assert!(parser.parse_input(&input).is_err());
+        }
+
+        #[test]
+        fn too_many_outs_is_reported_once() {
+            let mut input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n".to_string();
+            for batter in ["Person D", "Person E", "Person F", "Person G", "Person H", "Person I"] {
+                input.push_str(&format!("[INNING] 1 top [PLAY] Strikeout [BATTER] {batter} [PITCHER] Person A [MOVEMENTS] {batter} home -> home [out];\n"));
+            }
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(&input).unwrap();
+            assert_eq!(parser.warnings(), vec!["Inning 1 top: 4 outs in a half-inning"]);
+            assert_eq!(parser.live_game_state.outs, 6);
+        }
+
+        #[test]
+        fn out_at_records_the_putout_base() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n";
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Single [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 1, Person D 1 -> 3 [out at 2];\n").unwrap();
+            let movement = &parser.game_builder.plays[1].movements[1];
+            assert!(movement.out);
+            assert_eq!(movement.out_at, Some(Base::Second));
+            assert_eq!(movement.to_string(), "Person D 1 -> 3 [out at 2]");
+            assert_eq!(parser.game_state().outs, 1);
+            assert_eq!(parser.game_state().first.as_ref().map(|name| name.as_str()), Some("Person E"));
+        }
+
+        #[test]
+        fn out_credits_fielders() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[CATCHER] Person F\n[SECOND_BASE] Person G\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n";
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Forceout [BATTER] Person E [PITCHER] Person A [FIELDERS] Person B [MOVEMENTS] Person E home -> 1, Person D 1 -> 2 [out 6-4];\n").unwrap();
+            let movement = &parser.game_builder.plays[1].movements[1];
+            assert_eq!(movement.credits, vec![6, 4]);
+            assert_eq!(movement.putout(), Some(4));
+            assert_eq!(movement.assists(), vec![6]);
+            assert_eq!(movement.to_string(), "Person D 1 -> 2 [out 6-4]");
+
+            parser.parse_input("[INNING] 1 top [PLAY] Caught Stealing [BASE] 2 [RUNNER] Person E [FIELDERS] Person B [MOVEMENTS] Person E 1 -> 2 [out at 2 2-6];\n").unwrap();
+            let movement = &parser.game_builder.plays[2].movements[0];
+            assert_eq!(movement.out_at, Some(Base::Second));
+            assert_eq!(movement.credits, vec![2, 6]);
+            assert_eq!(movement.to_string(), "Person E 1 -> 2 [out at 2 2-6]");
+        }
+
+        #[test]
+        fn defensive_alignment_follows_substitutions() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[PITCHER] Person H\n[SHORTSTOP] Person B\n[UTILITY] Person I\n[SHORTSTOP] Person J\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n";
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            let alignment = parser.game_state().fielding_alignment;
+            assert_eq!(alignment.player_at(1).map(|name| name.as_str()), Some("Person A"));
+            assert_eq!(alignment.player_at(6).map(|name| name.as_str()), Some("Person B"));
+            assert_eq!(alignment.player_at(4), None);
+
+            parser.parse_input("[INNING] 1 top [PLAY] Groundout [BATTER] Person D [PITCHER] Person H [FIELDERS] Person J [MOVEMENTS] Person D home -> 1 [out 6-3];\n").unwrap_err();
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Groundout [BATTER] Person D [PITCHER] Person H [FIELDERS] Person J [MOVEMENTS] Person D home -> 1 [out 6-1];\n").unwrap();
+            let alignment = parser.game_state().fielding_alignment;
+            assert_eq!(alignment.player_at(1).map(|name| name.as_str()), Some("Person H"));
+            assert_eq!(alignment.player_at(6).map(|name| name.as_str()), Some("Person J"));
+            assert_eq!(alignment.position_of("Person B"), None);
+            assert!(parser.diagnostics().is_empty());
+
+            parser.parse_input("[INNING] 1 top [PLAY] Groundout [BATTER] Person E [PITCHER] Person H [FIELDERS] Person J [MOVEMENTS] Person E home -> 1 [out 6-3];\n").unwrap();
+            assert_eq!(parser.diagnostics()[0].code(), "W308");
+        }
+
+        #[test]
+        fn fielders_keep_their_order_and_positions() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SECOND_BASE] Person G\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n";
+            let input = format!("{}[INNING] 1 top [PLAY] Groundout [BATTER] Person D [PITCHER] Person A [FIELDERS] Person B, Person G, Person B [MOVEMENTS] Person D home -> 1 [out 6-4];\n", setup);
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(&input).unwrap();
+            let play = parser.game_builder.plays[0].clone();
+            assert_eq!(play.play_content.fielders(), ["Person B", "Person G", "Person B"]);
+            assert_eq!(play.primary_fielder().as_deref(), Some("Person B"));
+            assert_eq!(play.fielder_positions(&parser.game_state().fielding_alignment), vec![Some(6), Some(4), Some(6)]);
+
+            let mut options = ParserOptions::default();
+            options.set_dedupe_fielders(true);
+            let mut parser = Parser::with_options(options);
+            parser.parse_input(&input).unwrap();
+            assert_eq!(parser.game_builder.plays[0].play_content.fielders(), ["Person B", "Person G"]);
+        }
+
+        #[test]
+        fn defensive_subs_and_switches_change_the_alignment() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SECOND_BASE] Person G\n[SHORTSTOP] Person B\n[UTILITY] Person J\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n";
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Defensive Sub [FIELDERS] Person J, Person B [POSITION] 6 [MOVEMENTS] ;\n").unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Defensive Switch [FIELDERS] Person J, Person G [POSITION] 4 [MOVEMENTS] ;\n").unwrap();
+            let alignment = parser.game_state().fielding_alignment;
+            assert_eq!(alignment.player_at(4).map(|name| name.as_str()), Some("Person J"));
+            assert_eq!(alignment.player_at(6).map(|name| name.as_str()), Some("Person G"));
+            assert_eq!(alignment.position_of("Person B"), None);
+
+            let play = &parser.game_builder.plays[0];
+            assert_eq!(play.play_content, game::PlayContent::DefensiveSub { fielders: vec!["Person J".into(), "Person B".into()], position: 6 });
+            assert!(play.movements.is_empty());
+
+            // the shortstop substituted out has left the game
+            let regex = Regex::new(&format!("^{}$", parser.play_regex())).unwrap();
+            assert!(regex.is_match("[INNING] 1 top [PLAY] Defensive Switch [FIELDERS] Person G [POSITION] 4 [MOVEMENTS] ;").unwrap());
+            assert!(!regex.is_match("[INNING] 1 top [PLAY] Defensive Switch [FIELDERS] Person B [POSITION] 4 [MOVEMENTS] ;").unwrap());
+            assert!(!regex.is_match("[INNING] 1 top [PLAY] Groundout [BATTER] Person D [PITCHER] Person A [FIELDERS] Person B [MOVEMENTS] Person D home -> home [out];").unwrap());
+            assert!(parser.fork().parse_input("[INNING] 1 top [PLAY] Defensive Sub [FIELDERS] Person B, Person G [POSITION] 6 [MOVEMENTS] ;\n").is_err());
+            assert!(parser.parse_input("[INNING] 1 top [PLAY] Defensive Switch [FIELDERS] Person C [POSITION] 6 [MOVEMENTS] ;\n").is_err());
+        }
+
+        #[test]
+        fn defensive_switches_only_move_players_in_the_field() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n[UTILITY] Person J\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n";
+            let switch = "[INNING] 1 top [PLAY] Defensive Switch [FIELDERS] Person J [POSITION] 4 [MOVEMENTS] ;\n";
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            let regex = Regex::new(&format!("^{}$", parser.play_regex())).unwrap();
+            assert!(!regex.is_match(switch.trim_end()).unwrap());
+            assert!(parser.parse_input(switch).is_err());
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            parser.parse_input(switch).unwrap();
+            assert_eq!(parser.diagnostics()[0].code(), "W310");
+        }
+
+        #[test]
+        fn runner_outs_may_name_the_base() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n";
+            let plays = concat!(
+                "[INNING] 1 top [PLAY] Double [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 2;\n",
+                "[INNING] 1 top [PLAY] Runner Out [BASE] 3 [FIELDERS] Person B [RUNNER] Person D [MOVEMENTS] Person D 2 -> 3 [out];\n",
+                "[INNING] 1 top [PLAY] Single [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 1;\n",
+                "[INNING] 1 top [PLAY] Field Out [FIELDERS] Person B [RUNNER] Person E [MOVEMENTS] Person E 1 -> 2 [out];\n",
+                "[INNING] 1 top [PLAY] Walk [BATTER] Person F [PITCHER] Person A [MOVEMENTS] Person F home -> 1;\n",
+                "[INNING] 1 top [PLAY] Runner Out [RUNNER] Person F [FIELDERS] Person B [MOVEMENTS] Person F 1 -> 2 [out];\n",
+            );
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            parser.parse_input(plays).unwrap();
+            let game = parser.game_builder.build().unwrap();
+            assert_eq!(game.plays[1].play_content, game::PlayContent::RunnerOut {
+                fielders: vec!["Person B".into()],
+                runner: "Person D".into(),
+                base: Some(Base::Third),
+            });
+            assert_eq!(game.plays[3].play_content.base(), None);
+            assert_eq!(game.plays[5].play_content.runner().unwrap(), "Person F");
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Double [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 2;\n").unwrap();
+            assert!(parser.parse_input("[INNING] 1 top [PLAY] Runner Out [BASE] 1 [FIELDERS] Person B [RUNNER] Person D [MOVEMENTS] Person D 2 -> 3 [out];\n").is_err());
+        }
+
+        #[test]
+        fn plays_record_their_source_span() {
+            let header = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\r\n\r\n[TEAM] 1\r\n[PITCHER] Person A\r\n[SHORTSTOP] Person B\r\n\r\n[TEAM] 2\r\n[PITCHER] Person C\r\n\r\n[GAME_START]\r\n";
+            let plays = [
+                "[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;",
+                "[OVERTURNED] [INNING] 1 top [PLAY] Single [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 1, Person D 1 -> 2;",
+                "[INNING] 1 top [PLAY] Groundout [BATTER] Person E [PITCHER] Person A [FIELDERS] Person B [MOVEMENTS] Person E home -> 1 [out];",
+            ];
+            let input = format!("{}{}\r\n{}\r\n{}\r\n", header, plays[0], plays[1], plays[2]);
+
+            for lenient_whitespace in [false, true] {
+                let mut parser = strict_parser();
+                parser.options.lenient_whitespace = lenient_whitespace;
+                // split the input mid-play and between "\r" and "\n"
+                let split = header.len() + plays[0].len() + 1;
+                parser.parse_input(&input[..split]).unwrap();
+                parser.parse_input(&input[split..]).unwrap();
+                let game = parser.game_builder.build().unwrap();
+
+                let text = |play: &Play| {
+                    let (start, end) = play.source_span.unwrap();
+                    &input[start..end]
+                };
+                assert_eq!(game.plays.len(), 2);
+                assert_eq!(text(&game.plays[0]), plays[0]);
+                assert_eq!(text(game.plays[1].overturned.as_ref().unwrap()), plays[1]);
+                assert_eq!(text(&game.plays[1]), plays[2]);
+            }
+        }
+
+        #[test]
+        fn check_rosters_reports_every_violation() {
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n[LEFT_FIELD] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n[CATCHER] Person D\n\n[GAME_START]\n";
+
+            let mut options = ParserOptions::default();
+            options.set_strict(true).set_check_rosters(true).set_roster_size(2, 2);
+            let mut parser = Parser::with_options(options.clone());
+            let error = parser.parse_input(input).unwrap_err();
+            pyo3::prepare_freethreaded_python();
+            Python::with_gil(|py| {
+                let diagnostic = error.value(py).extract::<PyRef<ParseError>>().unwrap().diagnostic.clone();
+                assert_eq!(diagnostic.code(), "E401");
+                assert_eq!(diagnostic.message, "Team 1 has no catcher; Team 1 lists more than once: Person B; Team 1 has 3 players, expected 2 to 2");
+            });
+
+            options.set_strict(false);
+            let mut parser = Parser::with_options(options);
+            parser.parse_input(input).unwrap();
+            assert_eq!(parser.diagnostics()[0].code(), "W401");
+
+            // off by default
+            let mut parser = strict_parser();
+            parser.parse_input(input).unwrap();
+        }
+
+        #[test]
+        fn options_can_be_given_by_keyword() {
+            use pyo3::types::PyDictMethods;
+
+            pyo3::prepare_freethreaded_python();
+            Python::with_gil(|py| {
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("strict", true).unwrap();
+                kwargs.set_item("print_debug", true).unwrap();
+                kwargs.set_item("max_buffer_bytes", 100).unwrap();
+                let parser = Parser::py_new(None, Some(&kwargs)).unwrap();
+                assert!(parser.options.strict);
+                assert_eq!(parser.options.max_buffer_bytes, Some(100));
+                assert_eq!(parser.options.debug_sink, Some(DebugSink::Stdout));
+
+                // keywords take precedence over the options they're given with
+                let mut options = ParserOptions::default();
+                options.set_strict(true).set_lenient_whitespace(true);
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("strict", false).unwrap();
+                let parser = Parser::py_new(Some(options), Some(&kwargs)).unwrap();
+                assert!(!parser.options.strict && parser.options.lenient_whitespace);
+
+                kwargs.set_item("stricter", true).unwrap();
+                let error = Parser::py_new(None, Some(&kwargs)).err().unwrap();
+                assert_eq!(error.value(py).to_string(), "Unknown parser option: stricter");
+                assert!(ParserOptions::default().with_kwargs(&kwargs).is_err());
+
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("roster_size", (9, 26)).unwrap();
+                assert_eq!(ParserOptions::default().with_kwargs(&kwargs).unwrap().roster_size, Some((9, 26)));
+            });
+        }
+
+        #[test]
+        fn check_weather_ranges_warns() {
+            let mut options = ParserOptions::default();
+            options.set_strict(true).set_check_weather_ranges(true);
+            let mut parser = Parser::with_options(options.clone());
+            parser.parse_input("[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Clear 60C 100kph\n\n[TEAM] 1").unwrap();
+            assert_eq!(parser.warnings(), vec!["Temperature 60C is outside 0F to 120F", "Wind speed 100kph is above 60mph"]);
+            assert_eq!(parser.diagnostics()[0].code(), "W203");
+
+            let mut parser = Parser::with_options(options);
+            parser.parse_input("[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Snow -5C 40kph\n\n[TEAM] 1").unwrap();
+            assert!(parser.warnings().is_empty());
+
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Clear 150 0\n\n[TEAM] 1").unwrap();
+            assert!(parser.warnings().is_empty());
+        }
+
+        #[test]
+        fn require_declared_players_reports_every_undeclared_name() {
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n[LEFT_FIELD] Person D\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n[INNING] 1 top [PLAY] Single [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 1, Person D 1 -> 2;\n[INNING] 1 top [PLAY] Groundout [BATTER] Person F [PITCHER] Person A [FIELDERS] Person B [MOVEMENTS] Person F home -> 1 [out];\n";
+            let mut options = ParserOptions::default();
+            options.set_require_declared_players(true);
+
+            let mut parser = Parser::with_options(options.clone());
+            parser.parse_input(input).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Strikeout [BATTER] Person G [PITCHER] Person A [MOVEMENTS] Person G home -> home [out];\n").unwrap();
+            let diagnostics = parser.diagnostics();
+            assert_eq!(diagnostics.len(), 2);
+            assert_eq!(diagnostics[0].code(), "W309");
+            assert!(diagnostics[0].message.ends_with("Person E, Person F"));
+            assert!(diagnostics[1].message.contains("Person G"));
+
+            options.set_strict(true);
+            let mut parser = Parser::with_options(options);
+            assert!(parser.parse_input(input).is_err());
+
+            let mut parser = strict_parser();
+            parser.parse_input(input).unwrap();
+            assert!(parser.diagnostics().is_empty());
+        }
+
+        #[test]
+        fn overturned_call_is_kept_with_its_correction() {
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[OVERTURNED] [INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n[INNING] 1 top [PLAY] Groundout [BATTER] Person D [PITCHER] Person A [FIELDERS] Person B [MOVEMENTS] Person D home -> 1 [out];\n";
+
+            let mut parser = strict_parser();
+            parser.parse_input(input).unwrap();
+            assert_eq!(parser.game_state().outs, 1);
+            assert_eq!(parser.game_state().first, None);
+
+            let game = parser.game_builder.build().unwrap();
+            assert_eq!(game.plays.len(), 1);
+            assert_eq!(game.plays[0].play_content.play_type(), PlayType::Groundout);
+            let overturned = game.plays[0].overturned.as_deref().unwrap();
+            assert_eq!(overturned.play_content.play_type(), PlayType::Single);
+            assert_eq!(overturned.movements[0].to, Base::First);
+
+            // the original call has to be followed by the play that replaced it
+            let mut parser = strict_parser();
+            let unfinished = format!("{}[GAME_END]", input.replace("[INNING] 1 top [PLAY] Groundout [BATTER] Person D [PITCHER] Person A [FIELDERS] Person B [MOVEMENTS] Person D home -> 1 [out];\n", ""));
+            let _ = parser.parse_input(&unfinished);
+            assert!(parser.complete().is_none());
+        }
+
+        #[test]
+        fn strikeout_batter_reaching_base_needs_on_error() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n";
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Strikeout [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1 [on error];\n").unwrap();
+            let movement = &parser.game_builder.plays[0].movements[0];
+            assert!(movement.on_error);
+            assert_eq!(movement.to_string(), "Person D home -> 1 [on error]");
+            assert_eq!(parser.game_state().first.as_ref().map(|name| name.as_str()), Some("Person D"));
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Strikeout [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n").unwrap();
+            assert_eq!(parser.warnings(), vec!["Inning 1 top: Batter Person D reaches base on a strikeout without [on error]"]);
+            assert_eq!(parser.diagnostics()[0].code(), "W307");
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            assert!(parser.parse_input("[INNING] 1 top [PLAY] Strikeout [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n").is_err());
+        }
+
+        #[test]
+        fn steals_need_a_runner_on_the_base_before() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[CATCHER] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n";
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Caught Stealing [BASE] 2 [RUNNER] Person D [FIELDERS] Person B [MOVEMENTS] Person D 1 -> 2 [out];\n").unwrap();
+
+            let mut parser = strict_parser();
+            parser.parse_input(setup).unwrap();
+            assert!(parser.parse_input("[INNING] 1 top [PLAY] Caught Stealing [BASE] 3 [RUNNER] Person D [FIELDERS] Person B [MOVEMENTS] Person D 1 -> 2 [out];\n").is_err());
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Pickoff [BASE] 2 [RUNNER] Person D [FIELDERS] Person A [MOVEMENTS] Person D 1 -> 1 [out];\n").unwrap();
+            assert_eq!(parser.warnings(), vec!["Inning 1 top: Pickoff 2 needs Person D on 2, but no runner is on 2"]);
+            assert_eq!(parser.diagnostics()[0].code(), "W301");
+        }
+
+        #[test]
+        fn fielders_must_be_on_fielding_team() {
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Groundout [BATTER] Person D [PITCHER] Person A [FIELDERS] Person B, Person C [MOVEMENTS] Person D home -> 1 [out];\n";
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(input).unwrap();
+            assert_eq!(parser.warnings(), vec!["Inning 1 top: Person C is not on the fielding team (home team 1)"]);
+            assert_eq!(parser.diagnostics()[0].code(), "W304");
+
+            let mut parser = strict_parser();
+            assert!(parser.parse_input(input).is_err());
+        }
+
+        #[test]
+        fn skip_validation_only_parses() {
+            // Person C isn't on the fielding team and no one is on second
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n[CATCHER] Person D\n[SHORTSTOP] Person E\n\n[GAME_START]\n[INNING] 1 top [PLAY] Groundout [BATTER] Person D [PITCHER] Person A [FIELDERS] Person C [MOVEMENTS] Person D home -> 1 [out], Person E 2 -> 3;\n[GAME_END]";
+
+            let mut parser = strict_parser();
+            assert!(parser.parse_input(input).is_err());
+
+            let mut options = ParserOptions::default();
+            options.set_strict(true).set_skip_validation(true);
+            let mut parser = Parser::with_options(options);
+            parser.parse_input(input).unwrap();
+            assert_eq!(parser.complete().unwrap().plays.len(), 1);
+
+            let regex = Regex::new(&format!("^{}$", parser.play_regex())).unwrap();
+            assert!(regex.is_match("[INNING] 2 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1, Person E 3 -> home;").unwrap());
+        }
+
+        #[test]
+        fn keep_unknown_tags_as_extensions() {
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n[ATTENDANCE] 41000\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [EXIT_VELOCITY] 101.2 [MOVEMENTS] Person D home -> 1;\n[GAME_END]";
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(input).unwrap();
+            assert!(!parser.finished);
+
+            let mut parser = Parser::new(false);
+            parser.options.allow_unknown_tags = true;
+            parser.parse_input(input).unwrap();
+            let game = parser.complete().unwrap();
+
+            assert_eq!(game.extensions, vec![Extension { tag: "ATTENDANCE".to_string(), value: "41000".to_string() }]);
+            assert_eq!(game.plays[0].extensions, vec![Extension { tag: "EXIT_VELOCITY".to_string(), value: "101.2".to_string() }]);
+        }
+
+        #[test]
+        fn keep_meta_annotations() {
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1 [META] source=model-v3 confidence=0.92;\n[INNING] 1 top [PLAY] Strikeout [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home [out];\n[GAME_END]";
+
+            // the annotations arrive a character at a time, as they would when streaming
+            let mut parser = Parser::new(false);
+            for c in input.chars() {
+                parser.parse_input(&c.to_string()).unwrap();
+            }
+            let game = parser.complete().unwrap();
+
+            let meta = &game.plays[0].meta;
+            assert_eq!(meta.get("confidence").map(String::as_str), Some("0.92"));
+            assert_eq!(meta.get("source").map(String::as_str), Some("model-v3"));
+            assert!(game.plays[1].meta.is_empty());
+
+            let text = canonical::to_text(&game, false, true);
+            assert!(text.contains("home -> 1 [META] confidence=0.92 source=model-v3;"));
+            let json = serde_json::to_value(&game).unwrap();
+            assert_eq!(json["plays"][0]["meta"]["confidence"], "0.92");
+            assert!(json["plays"][1].get("meta").is_none());
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(&input.replace("source=model-v3", "source=")).unwrap();
+            assert!(parser.complete().is_none());
+        }
+
+        #[test]
+        fn lenient_whitespace_matches_canonical_game() {
+            let input = include_str!("../test_data/748231.txt");
+            let mut parser = Parser::new(false);
+            parser.parse_input(input).unwrap();
+            let canonical = parser.complete().unwrap();
+
+            let messy = input.replace(' ', "  \t").replace('\n', " \n\n ");
+            let mut parser = Parser::new(false);
+            parser.options.lenient_whitespace = true;
+            // feed it in pieces so runs of whitespace are split across chunks
+            for chunk in messy.chars().collect::<Vec<_>>().chunks(7) {
+                parser.parse_input(&chunk.iter().collect::<String>()).unwrap();
+            }
+            let game = parser.complete().unwrap();
 
-        let team = CAPTURE_GROUP_REGEX.replace_all(TEAM_SECTION_TEAM_REGEX.as_str(), "").replace("^", "");
-        let player = CAPTURE_GROUP_REGEX.replace_all(TEAM_SECTION_PLAYER_REGEX.as_str(), "").replace("^", "");
-        let team_section_regex = format!(
-            "{}\n({})(\n{})*",
-            team,
-            player,
-            player,
-        );
+            assert_eq!(
+                export::export_value(&game, export::ExportOptions::default()),
+                export::export_value(&canonical, export::ExportOptions::default()),
+            );
+        }
 
-        let game_start = PLAY_SECTION_GAME_START.replace("[", r"\[").replace("]", r"\]");
-        let game_end = PLAY_SECTION_GAME_END.replace("[", r"\[").replace("]", r"\]");
-        let play_section_regex = format!(
-            "{}\n({}\n)+{}",
-            game_start,
-            self.play_regex(),
-            game_end,
-        );
+        #[test]
+        fn max_buffer_bytes_caps_unconsumed_input() {
+            let mut options = ParserOptions::default();
+            options.set_max_buffer_bytes(64);
+
+            let mut parser = Parser::with_options(options.clone());
+            parser.parse_input("[GAME] 1 [DATE] 2024-04-01 [VENUE] ").unwrap();
+            assert!(parser.parse_input(&"x".repeat(64)).is_err());
+
+            // input that completes sections doesn't count towards the cap
+            let mut parser = Parser::with_options(options);
+            parser.parse_input(include_str!("../test_data/748231.txt")).unwrap();
+            assert!(parser.complete().is_some());
+        }
 
-        format!(
-            "{}\n\n{}\n\n{}\n\n{}",
-            context_section_regex,
-            team_section_regex,
-            team_section_regex,
-            play_section_regex,
-        ).replace("^", "")
-    }
-}
+        #[test]
+        fn parse_char_by_char() {
+            let input = include_str!("../test_data/748231.txt");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+            let mut parser = Parser::new(false);
+            parser.parse_input(input).unwrap();
+            let whole = parser.complete().unwrap();
 
-    mod parsing_tests {
-        use super::*;
+            let mut parser = Parser::new(false);
+            let mut buffer = [0; 4];
+            for c in input.chars() {
+                parser.parse_input(c.encode_utf8(&mut buffer)).unwrap();
+            }
+            let streamed = parser.complete().unwrap();
+
+            assert_eq!(
+                export::export_value(&streamed, export::ExportOptions::default()),
+                export::export_value(&whole, export::ExportOptions::default()),
+            );
+        }
 
         #[test]
-        fn parse_game_pk() {
+        fn parse_crlf_line_endings() {
+            let input = include_str!("../test_data/748231.txt");
             let mut parser = Parser::new(false);
-            let input = "[GAME] 766493";
-            let _ = parser.parse_input(input);
+            parser.parse_input(input).unwrap();
+            let canonical = parser.complete().unwrap();
 
-            if let Some(game_pk) = parser.game_builder.game_pk {
-                assert_eq!(game_pk, 766493);
-            } else {
-                panic!("game_pk is None");
+            let crlf = input.replace('\n', "\r\n");
+            let mut parser = Parser::new(false);
+            // split some line endings between the "\r" and the "\n"
+            for chunk in crlf.chars().collect::<Vec<_>>().chunks(5) {
+                parser.parse_input(&chunk.iter().collect::<String>()).unwrap();
             }
+            let game = parser.complete().unwrap();
+
+            assert_eq!(
+                export::export_value(&game, export::ExportOptions::default()),
+                export::export_value(&canonical, export::ExportOptions::default()),
+            );
+            assert!(parser.valid_regex().contains("\r?\n"));
         }
 
         #[test]
-        fn parse_date() {
+        fn report_invalid_bytes() {
             let mut parser = Parser::new(false);
-            let input = "[GAME] 766493 [DATE] 2024-03-24";
+            parser.parse_bytes(b"[GAME] 1 [DATE] 2024-04-01 [VENUE] Estadio ").unwrap();
+            // "é" split across two chunks
+            parser.parse_bytes(&[0xc3]).unwrap();
+            parser.parse_bytes(&[0xa9]).unwrap();
+            parser.parse_bytes(b" [WEATHER] Clear 70 5\n").unwrap();
+            assert_eq!(parser.game_builder.venue, Some("Estadio é".into()));
 
-            let _ = parser.parse_input(input);
+            assert_eq!(hex_window(&[0x61, 0xff, 0x62], 1), "61 [ff] 62");
+            assert_eq!(hex_window(b"0123456789abcdef", 10), "32 33 34 35 36 37 38 39 [61] 62 63 64 65 66");
 
-            if let Some(date) = parser.game_builder.date {
-                assert_eq!(date, "2024-03-24");
-            } else {
-                panic!("date is None");
-            }
+            let mut parser = Parser::new(false);
+            assert!(parser.parse_bytes(b"[GAME] 1 [DATE] 2024\xff-04-01").is_err());
+
+            let mut parser = Parser::new(false);
+            assert!(parser.parse_input("[GAME] 1 [DATE] \u{0}2024-04-01").is_err());
         }
 
         #[test]
-        fn parse_partial_input_is_ok() {
+        fn dead_ends_are_detected() {
+            let setup = "[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Clear 70 5\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n";
             let mut parser = Parser::new(false);
-            let input = "[GAM";
-            let result = parser.parse_input(input);
+            parser.parse_input(setup).unwrap();
+            assert!(parser.can_continue());
+
+            let mut partial = parser.fork();
+            partial.parse_input("[INNING] 1 top [PLAY] Groundout [BATTER] Person E [PITCHER] Per").unwrap();
+            assert!(partial.can_continue());
+            let mut typo = parser.fork();
+            typo.parse_input("[INNIGN]").unwrap();
+            assert!(typo.is_dead_end());
+            let mut batter = parser.fork();
+            batter.parse_input("[INNING] 1 top [PLAY] Groundout [BATTER] Person E [PTCHER]").unwrap();
+            assert!(batter.is_dead_end());
+
+            // outside strict mode a fielder who isn't on the roster only gets a warning, but no valid game has them
+            let mut outsider = parser.fork();
+            outsider.parse_input("[INNING] 1 top [PLAY] Groundout [BATTER] Person E [PITCHER] Person A [FIELDERS] Person Q [MOVEMENTS] Person E home -> 1 [out];\n").unwrap();
+            assert!(outsider.is_dead_end());
+
+            parser.parse_input("[GAME_END]").unwrap();
+            assert!(!parser.can_continue());
+            assert!(!parser.is_dead_end());
+        }
 
-            assert!(result.is_ok());
-            assert_eq!(parser.possible_sections, vec![GameSection::Context(ContextSection::Game)]);
+        #[test]
+        fn reset_parsers_can_parse_another_game() {
+            let input = include_str!("../test_data/748231.txt");
+            let mut options = ParserOptions::default();
+            options.set_lenient_whitespace(true);
+            let mut parser = Parser::with_options(options);
+            parser.parse_input(input).unwrap();
+            let first = parser.complete().unwrap();
+
+            parser.reset();
+            assert!(!parser.finished && parser.options.lenient_whitespace);
+            assert_eq!(parser.expected_tokens(), vec!["[GAME]"]);
+            parser.parse_input(input).unwrap();
+            let second = parser.complete().unwrap();
+            assert_eq!(serde_json::to_value(&second).unwrap(), serde_json::to_value(&first).unwrap());
+            assert_eq!(second.plays[0].source_span, first.plays[0].source_span);
+        }
 
-            let input = "E] 766493";
-            let _ = parser.parse_input(input);
+        #[test]
+        fn truncated_games_are_closed_out() {
+            let header = "[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Clear 70 5\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n[LEFT_FIELD] Person D\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n";
 
-            if let Some(game_pk) = parser.game_builder.game_pk {
-                assert_eq!(game_pk, 766493);
-            } else {
-                panic!("game_pk is None");
-            }
+            let mut parser = Parser::new(false);
+            parser.parse_input(&format!("{}[INNING] 1 top [PLAY] Walk [BATTER] Person C [PITCHER] Person A [MOVEMENTS] Person C home -> 1, Person D 1 -> 2", header)).unwrap();
+            let (game, repairs) = parser.finalize_truncated().unwrap();
+            assert_eq!(game.plays.len(), 2);
+            assert_eq!(repairs, vec![
+                r#"Ended the play in progress with ";""#,
+                "Added [GAME_END], though the game could not legally end in inning 1 top with the score 0-0",
+            ]);
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(&format!("{}[INNING] 1 top [PLAY] Walk [BATTER] Person C [PITCHER] Person A [MOVEMENTS] Person C home -> 1, Per", header)).unwrap();
+            let (game, repairs) = parser.finalize_truncated().unwrap();
+            assert_eq!(game.plays.len(), 1);
+            assert_eq!(repairs[0], "Dropped the unfinished play in inning 1 top");
+            assert_eq!(repairs[1], r#"Dropped the unparsed text "Per""#);
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(&header[..40]).unwrap();
+            assert!(parser.finalize_truncated().is_err());
         }
 
         #[test]
-        fn parse_entire_context_section() {
+        fn truncation_between_plays_drops_no_play() {
+            let header = "[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Clear 70 5\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n[LEFT_FIELD] Person D\n\n[GAME_START]\n";
+            let game_end = "Added [GAME_END], though the game could not legally end in inning 1 top with the score 0-0";
+
             let mut parser = Parser::new(false);
-            let input = "[GAME] 766493 [DATE] 2024-03-24 [VENUE] Estadio Alfredo Harp Helu [WEATHER] Sunny 85 9";
+            parser.parse_input(header).unwrap();
+            let (game, repairs) = parser.finalize_truncated().unwrap();
+            assert!(game.plays.is_empty());
+            assert_eq!(repairs, vec![game_end]);
 
-            let _ = parser.parse_input(input);
+            let mut parser = Parser::new(false);
+            parser.parse_input(&format!("{}[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n", header)).unwrap();
+            parser.parse_input("[OVERTURNED] [INNING] 1 top [PLAY] Single [BATTER] Person C [PITCHER] Person A [MOVEMENTS] Person C home -> 1;\n").unwrap();
+            let (game, repairs) = parser.finalize_truncated().unwrap();
+            assert_eq!(game.plays.len(), 1);
+            assert_eq!(repairs, vec!["Dropped the overturned call in inning 1 top with no play correcting it", game_end]);
+        }
 
-            if let Some(game_pk) = parser.game_builder.game_pk {
-                assert_eq!(game_pk, 766493);
-            } else {
-                panic!("game_pk is None");
-            }
+        #[test]
+        fn completions_finish_the_buffered_section() {
+            let mut parser = Parser::new(false);
+            assert_eq!(parser.suggest_completions(5), vec!["[GAME] "]);
 
-            if let Some(date) = parser.game_builder.date {
-                assert_eq!(date, "2024-03-24");
-            } else {
-                panic!("date is None");
-            }
+            parser.parse_input("[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Clear 70 5\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n[LEFT_FIELD] Person D\n\n[GAME_START]\n[INNING] 1 top [PLAY] Sac").unwrap();
+            assert_eq!(parser.suggest_completions(2), vec![" Fly", " Bunt"]);
 
-            if let Some(venue) = parser.game_builder.venue {
-                assert_eq!(venue, "Estadio Alfredo Harp Helu");
-            } else {
-                panic!("venue is None");
-            }
+            parser.parse_input(" Fly [BATTER] Person").unwrap();
+            assert_eq!(parser.suggest_completions(5), vec![" C", " D"]);
+            parser.parse_input(" D [PITCHER] ").unwrap();
+            assert_eq!(parser.suggest_completions(5), vec!["Person A"]);
+            parser.parse_input("Person B").unwrap();
+            assert!(parser.suggest_completions(5).is_empty());
+        }
 
-            if let Some(weather_condition) = parser.game_builder.weather_condition {
-                assert_eq!(weather_condition, "Sunny");
-            } else {
-                panic!("weather_condition is None");
-            }
+        #[test]
+        fn expected_tokens_follow_sections() {
+            let mut parser = Parser::new(false);
+            assert_eq!(parser.expected_tokens(), vec!["[GAME]"]);
 
-            if let Some(temperature) = parser.game_builder.weather_temperature {
-                assert_eq!(temperature, 85);
-            } else {
-                panic!("temperature is None");
-            }
+            parser.parse_input("[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Clear 70 5\n\n[TEAM] 1\n").unwrap();
+            assert_eq!(parser.expected_tokens(), vec!["a player position tag, e.g. [PITCHER]"]);
+            assert_eq!(parser.expected_examples(), vec!["[PITCHER] "]);
 
-            if let Some(wind_speed) = parser.game_builder.weather_wind_speed {
-                assert_eq!(wind_speed, 9);
-            } else {
-                panic!("wind_speed is None");
-            }
+            parser.parse_input("[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n").unwrap();
+            assert_eq!(parser.expected_examples(), vec!["[OVERTURNED] ", "[INNING] ", "[GAME_END]"]);
+            assert_eq!(parser.expected_hint(), r#"expected one of [OVERTURNED], [INNING], [GAME_END] (e.g. "[OVERTURNED] ", "[INNING] ", "[GAME_END]")"#);
+
+            let mut options = ParserOptions::default();
+            options.set_max_buffer_bytes(8);
+            let mut parser = Parser::with_options(options);
+            let error = parser.parse_input("[GAME] 1 [DATE] 2024-04-01 [VENUE] venue [WEATHER] Clear 70 5\n\n[TEAMS] 1\n").unwrap_err();
+            pyo3::prepare_freethreaded_python();
+            Python::with_gil(|py| {
+                let diagnostic = error.value(py).extract::<PyRef<ParseError>>().unwrap().diagnostic.clone();
+                assert!(diagnostic.message.ends_with(r#"expected one of [TEAM] (e.g. "[TEAM] ")"#));
+            });
         }
 
         #[test]
@@ -1373,16 +3593,19 @@ mod tests {
             if let Some(play) = parser.game_builder.plays.iter().next() {
                 assert!(play.inning == Inning { number: 1, top_bottom: TopBottom::Top });
                 assert!(play.play_content == PlayContent::Lineout {
-                    batter: "Anthony Volpe".to_string(),
-                    pitcher: "Trevor Bauer".to_string(),
-                    fielders: vec!["Aristides Aquino".to_string()],
+                    batter: "Anthony Volpe".into(),
+                    pitcher: "Trevor Bauer".into(),
+                    fielders: vec!["Aristides Aquino".into()],
                 });
                 assert!(play.movements == vec![
                     Movement {
-                        runner: "Anthony Volpe".to_string(),
+                        runner: "Anthony Volpe".into(),
                         from: Base::Home,
                         to: Base::Home,
                         out: true,
+                        on_error: false,
+                        out_at: None,
+                        credits: Vec::new(),
                     },
                 ]);
             } else {
@@ -1401,22 +3624,28 @@ mod tests {
             if let Some(play) = parser.game_builder.plays.iter().next() {
                 assert!(play.inning == Inning { number: 1, top_bottom: TopBottom::Top });
                 assert!(play.play_content == PlayContent::Groundout {
-                    batter: "Juan Carlos Gamboa".to_string(),
-                    pitcher: "Tanner Tully".to_string(),
-                    fielders: vec!["Tanner Tully".to_string(), "Trevor Bauer".to_string()],
+                    batter: "Juan Carlos Gamboa".into(),
+                    pitcher: "Tanner Tully".into(),
+                    fielders: vec!["Tanner Tully".into(), "Trevor Bauer".into()],
                 });
                 assert!(play.movements == vec![
                     Movement {
-                        runner: "Juan Carlos Gamboa".to_string(),
+                        runner: "Juan Carlos Gamboa".into(),
                         from: Base::Home,
                         to: Base::Home,
                         out: true,
+                        on_error: false,
+                        out_at: None,
+                        credits: Vec::new(),
                     },
                     Movement {
-                        runner: "Xavier Fernández".to_string(),
+                        runner: "Xavier Fernández".into(),
                         from: Base::Home,
                         to: Base::Second,
                         out: false,
+                        on_error: false,
+                        out_at: None,
+                        credits: Vec::new(),
                     },
                 ]);
             } else {
@@ -1473,19 +3702,19 @@ mod tests {
             }
 
             if let Some(weather_condition) = parser.game_builder.weather_condition {
-                assert_eq!(weather_condition, "Sunny");
+                assert_eq!(weather_condition, WeatherCondition::Sunny);
             } else {
                 panic!("weather_condition is None");
             }
 
             if let Some(weather_temperature) = parser.game_builder.weather_temperature {
-                assert_eq!(weather_temperature, 85);
+                assert_eq!(weather_temperature, 85.0);
             } else {
                 panic!("weather_temperature is None");
             }
 
             if let Some(weather_wind_speed) = parser.game_builder.weather_wind_speed {
-                assert_eq!(weather_wind_speed, 91);
+                assert_eq!(weather_wind_speed, 91.0);
             } else {
                 panic!("weather_wind_speed is None");
             }
@@ -1516,19 +3745,22 @@ mod tests {
             // println!("play: {:#?}", parser.game_builder.plays[0]);
             assert!(parser.game_builder.plays[0].inning == Inning { number: 1, top_bottom: TopBottom::Top });
             assert!(parser.game_builder.plays[0].play_content == PlayContent::Lineout {
-                batter: "Anthony Volpe".to_string(),
-                pitcher: "Trevor Bauer".to_string(),
+                batter: "Anthony Volpe".into(),
+                pitcher: "Trevor Bauer".into(),
                 fielders: vec![
-                    "Aristides Aquino".to_string(),
-                    "Kris Bryant".to_string(),
+                    "Aristides Aquino".into(),
+                    "Kris Bryant".into(),
                 ],
             });
             assert!(parser.game_builder.plays[0].movements == vec![
                 Movement {
-                    runner: "Anthony Volpe".to_string(),
+                    runner: "Anthony Volpe".into(),
                     from: Base::Home,
                     to: Base::Home,
                     out: true,
+                    on_error: false,
+                    out_at: None,
+                    credits: Vec::new(),
                 },
             ]);
         }
@@ -1615,7 +3847,7 @@ mod tests {
         #[test]
         fn test_valid_pinch_runner() {
             let mut parser = Parser::new(false);
-            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[PINCH_RUNNER] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person E [MOVEMENTS] Person D home -> 1;\n[INNING] 1 top [PLAY] Single [BATTER] Person Z [PITCHER] Person E [MOVEMENTS] Person Z home -> 1, Person B 1 -> 2;\n[GAME_END]";
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n[PINCH_RUNNER] Person B\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person E [MOVEMENTS] Person D home -> 1;\n[INNING] 1 top [PLAY] Single [BATTER] Person Z [PITCHER] Person E [MOVEMENTS] Person Z home -> 1, Person B 1 -> 2;\n[GAME_END]";
 
             let result = parser.parse_input(input);
 
@@ -1623,6 +3855,58 @@ mod tests {
             assert!(result.is_ok());
         }
 
+        #[test]
+        fn pinch_runners_only_run_for_their_team_once() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[PINCH_RUNNER] Person H\n\n[TEAM] 2\n[PITCHER] Person C\n[PINCH_RUNNER] Person B\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n";
+
+            // the fielding team's pinch runner can't take over a base
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            let regex = Regex::new(&format!("^{}$", parser.movements_regex(TopBottom::Top, true))).unwrap();
+            assert!(regex.is_match("[MOVEMENTS] Person B 1 -> 2").unwrap());
+            assert!(!regex.is_match("[MOVEMENTS] Person H 1 -> 2").unwrap());
+            assert!(parser.parse_input("[INNING] 1 top [PLAY] Stolen Base [BASE] 2 [RUNNER] Person H [MOVEMENTS] Person H 1 -> 2;\n").is_err());
+
+            // once in, a pinch runner is a regular runner and can't take over another base
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Stolen Base [BASE] 2 [RUNNER] Person B [MOVEMENTS] Person B 1 -> 2;\n").unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Single [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 1;\n").unwrap();
+            let regex = Regex::new(&format!("^{}$", parser.movements_regex(TopBottom::Top, true))).unwrap();
+            assert!(regex.is_match("[MOVEMENTS] Person B 2 -> 3").unwrap());
+            assert!(!regex.is_match("[MOVEMENTS] Person B 1 -> 3").unwrap());
+        }
+
+        #[test]
+        fn pinch_runners_take_over_a_base() {
+            let setup = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n[PINCH_RUNNER] Person B\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n";
+
+            // the pinch runner replaces the runner on first, who leaves the bases
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            parser.parse_input("[INNING] 1 top [PLAY] Single [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 1, Person B 1 -> 2;\n").unwrap();
+            let runner_positions = &parser.live_game_state.runner_positions;
+            assert_eq!((runner_positions.first.as_deref(), runner_positions.second.as_deref()), (Some("Person E"), Some("Person B")));
+
+            // so the runner they replaced can't move from their base as well
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            assert!(parser.parse_input("[INNING] 1 top [PLAY] Single [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 1, Person B 1 -> 2, Person D 1 -> 3;\n").is_err());
+
+            // and there has to be a runner on the base to replace
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            assert!(parser.parse_input("[INNING] 1 top [PLAY] Wild Pitch [PITCHER] Person A [RUNNER] Person B [MOVEMENTS] Person B 2 -> 3;\n").is_err());
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(setup).unwrap();
+            let regex = Regex::new(&format!("^{}$", parser.movements_regex(TopBottom::Top, true))).unwrap();
+            assert!(regex.is_match("[MOVEMENTS] Person B 1 -> 2").unwrap());
+            assert!(regex.is_match("[MOVEMENTS] Person D 1 -> 2, Person D 2 -> 3").unwrap());
+            assert!(!regex.is_match("[MOVEMENTS] Person B 2 -> 3").unwrap());
+            assert!(!regex.is_match("[MOVEMENTS] Person B 3 -> home").unwrap());
+        }
+
         #[test]
         fn test_invalid_pinch_runner() {
             let mut parser = Parser::new(false);
@@ -1634,34 +3918,171 @@ mod tests {
             assert!(result.is_err());
         }
 
+        #[test]
+        fn resume_from_exported_state() {
+            let input = include_str!("../test_data/748231.txt");
+            let split_index = input.find("[INNING] 5 top").unwrap();
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(&input[..split_index]).unwrap();
+            let state = parser.export_state().unwrap();
+
+            let mut resumed_parser = Parser::from_state(&state, None).unwrap();
+            resumed_parser.parse_input(&input[split_index..]).unwrap();
+            let resumed_game = resumed_parser.complete().unwrap();
+
+            let mut full_parser = Parser::new(false);
+            full_parser.parse_input(input).unwrap();
+            let full_game = full_parser.complete().unwrap();
+
+            assert_eq!(resumed_game.plays.len(), full_game.plays.len());
+            assert_eq!(resumed_parser.live_game_state.home_team_score, full_parser.live_game_state.home_team_score);
+            assert_eq!(resumed_parser.live_game_state.away_team_score, full_parser.live_game_state.away_team_score);
+        }
+
+        #[test]
+        fn resume_partway_through_the_away_roster() {
+            let input = include_str!("../test_data/748231.txt");
+            let away_team = input.match_indices("[TEAM]").nth(1).unwrap().0;
+            // after the away team's first player
+            let split_index = away_team + input[away_team..].match_indices('\n').nth(1).unwrap().0 + 1;
+
+            let mut parser = Parser::new(false);
+            parser.parse_input(&input[..split_index]).unwrap();
+            assert_eq!(parser.game_builder.away_team_players.len(), 1);
+            let state = parser.export_state().unwrap();
+
+            let mut resumed_parser = Parser::from_state(&state, None).unwrap();
+            assert_eq!(resumed_parser.possible_sections, parser.possible_sections);
+            resumed_parser.parse_input(&input[split_index..]).unwrap();
+            let resumed_game = resumed_parser.complete().unwrap();
+
+            let mut full_parser = Parser::new(false);
+            full_parser.parse_input(input).unwrap();
+            let full_game = full_parser.complete().unwrap();
+            assert_eq!(resumed_game.away_team.players.len(), full_game.away_team.players.len());
+            assert_eq!(resumed_game.plays.len(), full_game.plays.len());
+
+            // once the game has started, only plays may follow
+            let split_index = input.find("[INNING]").unwrap();
+            let mut parser = Parser::new(false);
+            parser.parse_input(&input[..split_index]).unwrap();
+            let mut resumed_parser = Parser::from_state(&parser.export_state().unwrap(), None).unwrap();
+            assert_eq!(resumed_parser.expected_tokens(), vec!["[OVERTURNED]", "[INNING]"]);
+            assert!(resumed_parser.parse_input("[PITCHER] Person Z\n").is_ok());
+            assert_eq!(resumed_parser.game_builder.away_team_players.len(), full_game.away_team.players.len());
+        }
+
         #[test]
         fn simplify_movements() {
             let mut runner_positions = RunnerPositions::empty();
-            runner_positions.home = Some("Garrett Hampson".to_string());
-            runner_positions.first = Some("Cam Devanney".to_string());
-            runner_positions.third = Some("Freddy Fermin".to_string());
+            runner_positions.first = Some("Cam Devanney".into());
+            runner_positions.third = Some("Freddy Fermin".into());
 
             let movements = vec![
-                Movement { runner: "Freddy Fermin".to_string(), from: Base::Third, to: Base::Home, out: false },
-                Movement { runner: "Cam Devanney".to_string(), from: Base::First, to: Base::Second, out: false },
-                Movement { runner: "Garrett Hampson".to_string(), from: Base::Home, to: Base::Home, out: true },
+                Movement { runner: "Freddy Fermin".into(), from: Base::Third, to: Base::Home, out: false, on_error: false, out_at: None, credits: Vec::new() },
+                Movement { runner: "Cam Devanney".into(), from: Base::First, to: Base::Second, out: false, on_error: false, out_at: None, credits: Vec::new() },
+                Movement { runner: "Garrett Hampson".into(), from: Base::Home, to: Base::Home, out: true, on_error: false, out_at: None, credits: Vec::new() },
             ];
 
             let simplified_movements = runner_positions.simplify_movements(&movements);
-            assert_eq!(HashSet::<_>::from_iter(simplified_movements), HashSet::from([
-                Movement { runner: "Freddy Fermin".to_string(), from: Base::Third, to: Base::Home, out: false },
-                Movement { runner: "Cam Devanney".to_string(), from: Base::First, to: Base::Second, out: false },
-                Movement { runner: "Garrett Hampson".to_string(), from: Base::Home, to: Base::Home, out: true },
-            ]));
+            assert_eq!(simplified_movements, vec![
+                Movement { runner: "Garrett Hampson".into(), from: Base::Home, to: Base::Home, out: true, on_error: false, out_at: None, credits: Vec::new() },
+                Movement { runner: "Cam Devanney".into(), from: Base::First, to: Base::Second, out: false, on_error: false, out_at: None, credits: Vec::new() },
+                Movement { runner: "Freddy Fermin".into(), from: Base::Third, to: Base::Home, out: false, on_error: false, out_at: None, credits: Vec::new() },
+            ]);
+        }
+
+        #[test]
+        fn live_state_tracks_outs_score_and_players() {
+            let mut parser = Parser::new(false);
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home, Person D 1 -> home;\n[INNING] 1 top [PLAY] Strikeout [BATTER] Person F [PITCHER] Person A [MOVEMENTS] Person F home -> home [out];\n";
+            parser.parse_input(input).unwrap();
+
+            let state = &parser.live_game_state;
+            assert_eq!((state.outs, state.away_team_score, state.home_team_score), (1, 2, 0));
+            assert_eq!(state.current_batter, Some("Person F".into()));
+            assert_eq!(state.current_pitcher, Some("Person A".into()));
+        }
+
+        #[test]
+        fn advancing_runners_leave_their_base() {
+            let mut parser = Parser::new(false);
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n[INNING] 1 top [PLAY] Double [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 2, Person D 1 -> 3;\n";
+            parser.parse_input(input).unwrap();
+
+            let runner_positions = &parser.live_game_state.runner_positions;
+            assert_eq!(runner_positions.first, None);
+            assert_eq!(runner_positions.second, Some("Person E".into()));
+            assert_eq!(runner_positions.third, Some("Person D".into()));
+        }
+
+        #[test]
+        fn a_new_half_inning_clears_the_bases_and_outs() {
+            let mut state = LiveGameState::new();
+            state.set_inning(Inning { number: 1, top_bottom: TopBottom::Top });
+            state.runner_positions.first = Some("Person B".into());
+            state.outs = 2;
+
+            // the same half-inning keeps its runners and outs
+            state.set_inning(Inning { number: 1, top_bottom: TopBottom::Top });
+            assert_eq!((state.runner_positions.first.clone(), state.outs), (Some("Person B".into()), 2));
+
+            // a later inning with the same half is still a new half-inning, not only a switch between top and bottom
+            state.set_inning(Inning { number: 2, top_bottom: TopBottom::Top });
+            assert_eq!((state.runner_positions.first.clone(), state.outs), (None, 0));
+        }
+
+        #[test]
+        fn runners_reaching_home_score() {
+            let mut runner_positions = RunnerPositions::empty();
+            runner_positions.first = Some("Person B".into());
+            runner_positions.second = Some("Person C".into());
+            runner_positions.third = Some("Person D".into());
+
+            let movement = |runner: &str, from, to| Movement { runner: runner.into(), from, to, out: false, on_error: false, out_at: None, credits: Vec::new() };
+            let grand_slam = vec![
+                movement("Person E", Base::Home, Base::Home),
+                movement("Person B", Base::First, Base::Home),
+                movement("Person C", Base::Second, Base::Home),
+                movement("Person D", Base::Third, Base::Home),
+            ];
+            assert_eq!(runner_positions.process_movements(&grand_slam, &[]).unwrap(), 4);
+            assert_eq!((runner_positions.first, runner_positions.second, runner_positions.third), (None, None, None));
+
+            // a runner who scores after a hop counts once
+            let mut runner_positions = RunnerPositions::empty();
+            runner_positions.second = Some("Person C".into());
+            let double = vec![
+                movement("Person E", Base::Home, Base::Second),
+                movement("Person C", Base::Second, Base::Third),
+                movement("Person C", Base::Third, Base::Home),
+            ];
+            assert_eq!(runner_positions.process_movements(&double, &[]).unwrap(), 1);
+            assert_eq!(runner_positions.second, Some("Person E".into()));
+            assert_eq!(runner_positions.third, None);
+        }
+
+        #[test]
+        fn validate_movement_chains() {
+            let movement = |from, to, out| Movement { runner: "Cam Devanney".into(), from, to, out, on_error: false, out_at: None, credits: Vec::new() };
+
+            assert!(RunnerPositions::validate_movements(&[movement(Base::First, Base::Second, false), movement(Base::Second, Base::Third, false)]).is_ok());
+            // put out at second, then scores
+            assert!(RunnerPositions::validate_movements(&[movement(Base::First, Base::Second, true), movement(Base::Second, Base::Home, false)]).is_err());
+            // skips from second to third without a movement between them
+            assert!(RunnerPositions::validate_movements(&[movement(Base::First, Base::Second, false), movement(Base::Third, Base::Home, false)]).is_err());
+            assert!(RunnerPositions::validate_movements(&[movement(Base::Third, Base::Home, false), movement(Base::Home, Base::First, false)]).is_err());
         }
     }
 
     mod regex_tests {
         use super::*;
+        use fancy_regex::RegexBuilder;
 
         fn test_valid_regex_for_play_type(play_type: PlayType, input: &str) {
             let parser = Parser::new(false);
-            let pattern = parser.inner_pattern_from_play_type(&play_type);
+            let pattern = parser.inner_pattern_from_play_type(&play_type, TopBottom::Top, true);
             let regex = Regex::new(&pattern).unwrap();
             println!("pattern: \"{}\"\n", pattern);
 
@@ -1845,6 +4266,14 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_valid_regex_for_runner_out_with_base() {
+            test_valid_regex_for_play_type(
+                PlayType::RunnerOut,
+                "[PLAY] Runner Out [BASE] 2 [FIELDERS] C, D [RUNNER] E",
+            );
+        }
+
         #[test]
         fn test_valid_regex_for_field_out() {
             test_valid_regex_for_play_type(
@@ -2005,6 +4434,22 @@ mod tests {
             );
         }
 
+        #[test]
+        fn test_valid_regex_for_defensive_switch() {
+            test_valid_regex_for_play_type(
+                PlayType::DefensiveSwitch,
+                "[PLAY] Defensive Switch [FIELDERS] C, D [POSITION] 6",
+            );
+        }
+
+        #[test]
+        fn test_valid_regex_for_defensive_sub() {
+            test_valid_regex_for_play_type(
+                PlayType::DefensiveSub,
+                "[PLAY] Defensive Sub [FIELDERS] C, D [POSITION] 6",
+            );
+        }
+
         #[test]
         fn test_valid_regex_for_game_advisory() {
             test_valid_regex_for_play_type(
@@ -2016,7 +4461,7 @@ mod tests {
         #[test]
         fn test_valid_regex_for_movement_from_home() {
             let parser = Parser::new(false);
-            let regex = parser.movements_regex();
+            let regex = parser.movements_regex(TopBottom::Top, true);
             let regex = Regex::new(&regex).unwrap();
 
             let input = "[MOVEMENTS] A home -> 1";
@@ -2027,9 +4472,9 @@ mod tests {
         #[test]
         fn test_valid_regex_for_movement_from_first() {
             let mut parser = Parser::new(false);
-            parser.live_game_state.runner_positions.first = Some("B".to_string());
+            parser.live_game_state.runner_positions.first = Some("B".into());
 
-            let regex = parser.movements_regex();
+            let regex = parser.movements_regex(TopBottom::Top, true);
             let regex = Regex::new(&regex).unwrap();
 
             let input = "[MOVEMENTS] B 1 -> 2";
@@ -2040,9 +4485,9 @@ mod tests {
         #[test]
         fn test_valid_regex_for_movement_from_first_with_out() {
             let mut parser = Parser::new(false);
-            parser.live_game_state.runner_positions.first = Some("B".to_string());
+            parser.live_game_state.runner_positions.first = Some("B".into());
 
-            let regex = parser.movements_regex();
+            let regex = parser.movements_regex(TopBottom::Top, true);
             let regex = Regex::new(&regex).unwrap();
 
             let input = "[MOVEMENTS] B 1 -> 2 [out]";
@@ -2053,14 +4498,163 @@ mod tests {
         #[test]
         fn test_valid_regex_for_multiple_movements() {
             let mut parser = Parser::new(false);
-            parser.live_game_state.runner_positions.first = Some("B".to_string());
+            parser.live_game_state.runner_positions.first = Some("B".into());
 
-            let regex = parser.movements_regex();
+            let regex = parser.movements_regex(TopBottom::Top, true);
             let regex = Regex::new(&regex).unwrap();
 
             let input = "[MOVEMENTS] A home -> 1, B 1 -> 2 [out]";
             let is_match = regex.is_match(input).unwrap();
             assert!(is_match);
         }
+
+        #[test]
+        fn test_valid_regex_for_play_uses_rosters() {
+            let mut parser = Parser::new(false);
+            parser.parse_input("[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[CATCHER] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n[SHORTSTOP] J.D. Person\n\n[GAME_START]\n").unwrap();
+
+            let regex = Regex::new(&format!("^{}$", parser.play_regex())).unwrap();
+            assert!(regex.is_match("[INNING] 1 top [PLAY] Single [BATTER] J.D. Person [PITCHER] Person A [MOVEMENTS] J.D. Person home -> 1;").unwrap());
+            assert!(regex.is_match("[INNING] 1 bottom [PLAY] Groundout [BATTER] Person B [PITCHER] Person C [FIELDERS] J.D. Person, Person C [MOVEMENTS] Person B home -> home [out];").unwrap());
+            // the runner on first is only on base in the half-inning in progress
+            parser.live_game_state.runner_positions.first = Some("J.D. Person".into());
+            let regex = Regex::new(&format!("^{}$", parser.play_regex())).unwrap();
+            assert!(regex.is_match("[INNING] 1 top [PLAY] Single [BATTER] Person C [PITCHER] Person A [MOVEMENTS] Person C home -> 1, J.D. Person 1 -> 2;").unwrap());
+            assert!(!regex.is_match("[INNING] 1 bottom [PLAY] Single [BATTER] Person B [PITCHER] Person C [MOVEMENTS] Person B home -> 1, J.D. Person 1 -> 2;").unwrap());
+
+            // the home team doesn't bat in the top of an inning
+            assert!(!regex.is_match("[INNING] 1 top [PLAY] Single [BATTER] Person B [PITCHER] Person A [MOVEMENTS] Person B home -> 1;").unwrap());
+            assert!(!regex.is_match("[INNING] 1 top [PLAY] Single [BATTER] J.D. Person [PITCHER] Person A [MOVEMENTS] Person A home -> 1;").unwrap());
+            assert!(!regex.is_match("[INNING] 1 top [PLAY] Single [BATTER] J.D. Person [PITCHER] Person C [MOVEMENTS] J.D. Person home -> 1;").unwrap());
+            assert!(!regex.is_match("[INNING] 1 top [PLAY] Single [BATTER] JXD. Person [PITCHER] Person A [MOVEMENTS] J.D. Person home -> 1;").unwrap());
+        }
+
+        #[test]
+        fn test_valid_regex_for_due_batter() {
+            let game = include_str!("../test_data/747060.txt");
+            let fourth_inning = game.find("[INNING] 4 top").unwrap();
+
+            let mut parser = Parser::new(false);
+            parser.options.constrain_next_batter = true;
+            parser.parse_input(&game[..fourth_inning]).unwrap();
+
+            let regex = Regex::new(&format!("^{}$", parser.batter_pattern(TopBottom::Top, true))).unwrap();
+            assert!(regex.is_match("Aaron Hicks").unwrap());
+            assert!(!regex.is_match("Mike Trout").unwrap());
+
+            parser.options.constrain_next_batter = false;
+            let regex = Regex::new(&format!("^{}$", parser.batter_pattern(TopBottom::Top, true))).unwrap();
+            assert!(regex.is_match("Mike Trout").unwrap());
+        }
+
+        #[test]
+        fn test_valid_regex_for_play_follows_the_inning() {
+            let mut parser = Parser::new(false);
+            parser.live_game_state.inning = Inning { number: 3, top_bottom: TopBottom::Bottom };
+            let regex = Regex::new(&format!("^{}$", parser.play_regex())).unwrap();
+            assert!(regex.is_match("[INNING] 3 bottom [PLAY] Strikeout [BATTER] A [PITCHER] B [MOVEMENTS] A home -> home [out];").unwrap());
+            assert!(regex.is_match("[INNING] 4 top [PLAY] Strikeout [BATTER] A [PITCHER] B [MOVEMENTS] A home -> home [out];").unwrap());
+            assert!(!regex.is_match("[INNING] 3 top [PLAY] Strikeout [BATTER] A [PITCHER] B [MOVEMENTS] A home -> home [out];").unwrap());
+            assert!(!regex.is_match("[INNING] 9 top [PLAY] Strikeout [BATTER] A [PITCHER] B [MOVEMENTS] A home -> home [out];").unwrap());
+        }
+
+        #[test]
+        fn test_valid_regex_for_play_after_the_third_out() {
+            let mut parser = Parser::new(false);
+            parser.live_game_state.outs = 3;
+            let regex = Regex::new(&format!("^{}$", parser.play_regex())).unwrap();
+            // the top of the 1st is over, so a fourth out can't be made in it
+            assert!(!regex.is_match("[INNING] 1 top [PLAY] Strikeout [BATTER] A [PITCHER] B [MOVEMENTS] A home -> home [out];").unwrap());
+            assert!(!regex.is_match("[INNING] 1 top [PLAY] Single [BATTER] A [PITCHER] B [MOVEMENTS] A home -> 1;").unwrap());
+            assert!(regex.is_match("[INNING] 1 bottom [PLAY] Strikeout [BATTER] A [PITCHER] B [MOVEMENTS] A home -> home [out];").unwrap());
+        }
+
+        #[test]
+        fn test_remaining_regex_ends_only_where_the_game_could() {
+            let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] Clear 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;";
+            let mut parser = Parser::new(false);
+            parser.parse_input(input).unwrap();
+            let ends_now = |parser: &Parser| Regex::new(&format!("^{}$", parser.remaining_regex())).unwrap().is_match("\n[GAME_END]").unwrap();
+
+            parser.live_game_state.inning = Inning { number: 7, top_bottom: TopBottom::Bottom };
+            parser.live_game_state.outs = 3;
+            assert!(!ends_now(&parser));
+
+            // still tied after nine
+            parser.live_game_state.inning.number = 9;
+            assert!(!ends_now(&parser));
+
+            parser.live_game_state.outs = 1;
+            parser.live_game_state.home_team_score = 1;
+            assert!(ends_now(&parser));
+
+            // the away team can still come back before the third out
+            parser.live_game_state.inning.top_bottom = TopBottom::Top;
+            parser.live_game_state.outs = 2;
+            assert!(!ends_now(&parser));
+            parser.live_game_state.outs = 3;
+            assert!(ends_now(&parser));
+
+            parser.options.skip_validation = true;
+            parser.live_game_state.inning.number = 1;
+            assert!(ends_now(&parser));
+        }
+
+        #[test]
+        fn test_remaining_regex_follows_a_game() {
+            let game = include_str!("../test_data/748231.txt");
+            let mut parser = Parser::new(false);
+            parser.parse_input(game).unwrap();
+            let spans = parser.game_builder.plays.iter()
+                .map(|play| play.source_span.unwrap())
+                .collect::<Vec<_>>();
+
+            // a real game's rosters make the pattern too big for the regex engine to keep track of every group, so none of
+            // them capture
+            let without_captures = |pattern: &str| Regex::new(r"(?<!\\)\((?!\?)").unwrap().replace_all(pattern, "(?:").to_string();
+
+            // fed a character at a time, the line breaks after a play are kept too
+            let mut parser = Parser::new(false);
+            for c in game[..spans[1].0].chars() {
+                parser.parse_input(&c.to_string()).unwrap();
+            }
+            assert_eq!(parser.remaining_input(), &game[spans[0].1..spans[1].0]);
+
+            // each play is one the game state allows next, and what's left of the game starts after it
+            let mut parser = Parser::new(false);
+            let mut fed = 0;
+            for (start, end) in spans {
+                parser.parse_input(&game[fed..start]).unwrap();
+                let regex = RegexBuilder::new(&format!("^{}$", without_captures(&parser.play_regex())))
+                    .delegate_size_limit(1 << 28)
+                    .build()
+                    .unwrap();
+                assert!(regex.is_match(&game[start..end]).unwrap(), "no match for {:?}", &game[start..end]);
+                assert_eq!(parser.remaining_input(), &game[fed..start]);
+
+                parser.parse_input(&game[start..end]).unwrap();
+                assert_eq!(parser.remaining_input(), "");
+                fed = end;
+            }
+        }
+
+        #[test]
+        fn test_valid_regex_for_movements_limits_outs() {
+            let mut parser = Parser::new(false);
+            parser.live_game_state.runner_positions.first = Some("B".into());
+            parser.live_game_state.outs = 1;
+
+            let regex = format!("^{}$", parser.movements_regex(TopBottom::Top, true));
+            let regex = Regex::new(&regex).unwrap();
+
+            assert!(regex.is_match("[MOVEMENTS] A home -> 1 [out], B 1 -> 2 [out]").unwrap());
+            assert!(regex.is_match("[MOVEMENTS] B 1 -> 2 [out], A home -> 1").unwrap());
+            assert!(!regex.is_match("[MOVEMENTS] A home -> 1 [out], B 1 -> 2 [out], C home -> 1 [out]").unwrap());
+
+            parser.live_game_state.outs = 2;
+            let regex = Regex::new(&format!("^{}$", parser.movements_regex(TopBottom::Top, true))).unwrap();
+            assert!(regex.is_match("[MOVEMENTS] A home -> 1, B 1 -> 2 [out]").unwrap());
+            assert!(!regex.is_match("[MOVEMENTS] A home -> 1 [out], B 1 -> 2 [out]").unwrap());
+        }
     }
 }
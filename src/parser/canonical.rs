@@ -0,0 +1,215 @@
+use pyo3::prelude::{pyfunction, PyResult};
+
+use super::Parser;
+use super::options::ParserOptions;
+use super::diagnostics::{Diagnostic, DiagnosticKind};
+use super::game::{Extension, Game, Play, PlayType, SpeedUnit, Team, TemperatureUnit};
+use super::state::RunnerPositions;
+
+/// Write a game in the canonical form of the format: one space between tokens, the sections and plays on lines of
+/// their own, the default weather units left out, and each runner's hops in a play joined into one movement if
+/// `join_hops` is set. Fielders are listed alphabetically if `sort_fielders` is set, and in the order they were given
+/// otherwise. With neither set, parsing the text gives back the same game.
+pub fn to_text(game: &Game, sort_fielders: bool, join_hops: bool) -> String {
+    let weather = &game.context.weather;
+    let mut text = format!(
+        "[GAME] {} [DATE] {} [VENUE] {} [WEATHER] {} {}{} {}{}\n\n",
+        game.context.game_pk,
+        game.context.date,
+        game.context.venue,
+        weather.condition.to_string(),
+        weather.temperature,
+        if weather.temperature_unit == TemperatureUnit::Fahrenheit { String::new() } else { weather.temperature_unit.to_string() },
+        weather.wind_speed,
+        if weather.wind_speed_unit == SpeedUnit::MilesPerHour { String::new() } else { weather.wind_speed_unit.to_string() },
+    );
+    text.push_str(&team_text(&game.home_team));
+    text.push('\n');
+    text.push_str(&team_text(&game.away_team));
+    text.push_str("\n[GAME_START]\n");
+
+    for extension in &game.extensions {
+        text.push_str(&format!("{}\n", extension_text(extension)));
+    }
+    for play in &game.plays {
+        if let Some(overturned) = &play.overturned {
+            text.push_str(&format!("[OVERTURNED] {}\n", play_text(overturned, sort_fielders, join_hops)));
+        }
+        text.push_str(&format!("{}\n", play_text(play, sort_fielders, join_hops)));
+    }
+    text.push_str("[GAME_END]\n");
+
+    text
+}
+
+fn team_text(team: &Team) -> String {
+    let mut text = format!("[TEAM] {}\n", team.team_id);
+    for player in &team.players {
+        text.push_str(&format!("[{}] {}\n", player.position.to_string(), player.name));
+    }
+
+    text
+}
+
+fn extension_text(extension: &Extension) -> String {
+    format!("[{}] {}", extension.tag, extension.value)
+}
+
+fn play_text(play: &Play, sort_fielders: bool, join_hops: bool) -> String {
+    let content = &play.play_content;
+    let mut tokens = vec![
+        format!("[INNING] {}", play.inning.to_string()),
+        format!("[PLAY] {}", content.play_type().to_string()),
+    ];
+    // a game advisory has nothing more to it, not even the end of the play
+    if content.play_type() == PlayType::GameAdvisory {
+        return tokens.join(" ");
+    }
+
+    if let Some(base) = content.base() {
+        tokens.push(format!("[BASE] {}", base.to_string()));
+    }
+    if let Some(batter) = content.batter() {
+        tokens.push(format!("[BATTER] {}", batter));
+    }
+    if let Some(pitcher) = content.pitcher() {
+        tokens.push(format!("[PITCHER] {}", pitcher));
+    }
+    if let Some(catcher) = content.catcher() {
+        tokens.push(format!("[CATCHER] {}", catcher));
+    }
+    // after a required base with no batter, pitcher or catcher, the parser takes the runner before the fielders
+    let play_type = content.play_type();
+    let runner_first = content.base().is_some()
+        && play_type.requires_runner()
+        && !(play_type.requires_batter() || play_type.requires_pitcher() || play_type.requires_catcher() || play_type.allows_base());
+    let runner = content.runner().map(|runner| format!("[RUNNER] {}", runner));
+    if runner_first {
+        tokens.extend(runner.clone());
+    }
+    if !content.fielders().is_empty() {
+        let mut fielders = content.fielders().iter().map(|fielder| fielder.as_str()).collect::<Vec<_>>();
+        if sort_fielders {
+            fielders.sort();
+        }
+        tokens.push(format!("[FIELDERS] {}", fielders.join(", ")));
+    }
+    if let Some(position) = content.position() {
+        tokens.push(format!("[POSITION] {}", position));
+    }
+    if !runner_first {
+        tokens.extend(runner);
+    }
+    if let Some(scoring_runner) = content.scoring_runner() {
+        tokens.push(format!("[SCORING_RUNNER] {}", scoring_runner));
+    }
+    if let Some(error_by) = content.error_by() {
+        tokens.push(format!("[ERROR_BY] {}", error_by));
+    }
+    tokens.extend(play.extensions.iter().map(extension_text));
+
+    let movements = match join_hops {
+        true => RunnerPositions::empty().simplify_movements(&play.movements),
+        false => play.movements.clone(),
+    };
+    let movements = movements.iter().map(|movement| movement.to_string()).collect::<Vec<_>>();
+    tokens.push(format!("[MOVEMENTS] {}", movements.join(", ")));
+    if !play.meta.is_empty() {
+        let pairs = play.meta.iter().map(|(key, value)| format!("{}={}", key, value)).collect::<Vec<_>>();
+        tokens.push(format!("[META] {}", pairs.join(" ")));
+    }
+
+    format!("{};", tokens.join(" "))
+}
+
+/// Parse a game and write it back in canonical form, so that games written by different producers can be compared
+/// byte for byte. Any whitespace between tokens and unknown tags are accepted, as with `lenient_whitespace` and
+/// `allow_unknown_tags`, and the plays are not replayed, as with `skip_validation`, so a game whose plays don't add
+/// up still has a canonical form.
+pub fn canonicalize(text: &str, sort_fielders: bool) -> PyResult<String> {
+    let mut options = ParserOptions::default();
+    options.set_lenient_whitespace(true).set_allow_unknown_tags(true).set_skip_validation(true);
+    let mut parser = Parser::with_options(options);
+    parser.parse_input(text)?;
+
+    let game = parser.complete().ok_or_else(|| Diagnostic::new(
+        DiagnosticKind::IncompleteGame,
+        format!("Game ended early, {}", parser.expected_hint()),
+    ))?;
+
+    Ok(to_text(&game, sort_fielders, true))
+}
+
+/// Parse a game and write it back in canonical form: one space between tokens, each play on its own line, and
+/// each runner's hops joined into one movement. Fielders are sorted alphabetically if `sort_fielders` is set.
+#[pyfunction(name = "canonicalize")]
+#[pyo3(signature = (text, sort_fielders=false))]
+pub fn py_canonicalize(text: &str, sort_fielders: bool) -> PyResult<String> {
+    canonicalize(text, sort_fielders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_games() -> Vec<String> {
+        let paths = crate::corpus::game_paths(std::path::Path::new("test_data")).unwrap();
+        paths.iter().map(|path| std::fs::read_to_string(path).unwrap()).collect()
+    }
+
+    #[test]
+    fn canonical_text_is_a_fixed_point() {
+        let input = include_str!("../../test_data/748231.txt");
+        let canonical = canonicalize(input, false).unwrap();
+        assert!(canonical.starts_with("[GAME] 748231 [DATE] 2024-02-25 [VENUE] Angel Stadium [WEATHER] Partly Cloudy 77 4\n\n[TEAM] 108\n"));
+        assert!(canonical.ends_with(";\n[GAME_END]\n"));
+
+        // some recorded games leave out a pinch runner coming in, so they are only parsed
+        let parse = |text: &str| {
+            let mut parser = Parser::new(false);
+            parser.options.skip_validation = true;
+            parser.parse_input(text).unwrap();
+            parser.complete().unwrap()
+        };
+        for input in test_games() {
+            let canonical = canonicalize(&input, false).unwrap();
+            assert_eq!(canonicalize(&canonical, false).unwrap(), canonical);
+
+            let (original, game) = (parse(&input), parse(&canonical));
+            assert_eq!(game.plays.len(), original.plays.len());
+            if let (Ok(state), Ok(original_state)) = (game.replay(), original.replay()) {
+                assert_eq!((state.away_team_score, state.home_team_score), (original_state.away_team_score, original_state.home_team_score));
+            }
+        }
+    }
+
+    #[test]
+    fn parsing_the_text_gives_back_the_game() {
+        let parse = |text: &str| {
+            let mut parser = Parser::new(false);
+            parser.options.allow_unknown_tags = true;
+            parser.options.skip_validation = true;
+            parser.parse_input(text).unwrap();
+            parser.complete().unwrap()
+        };
+        let hops = "[GAME] 1 [DATE] 2024-04-01 [VENUE] Example Park [WEATHER] Clear 21.5C 8kph\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [EXIT_VELOCITY] 101.2 [MOVEMENTS] Person D home -> 1, Person D 1 -> 2 [META] confidence=0.9;\n[OVERTURNED] [INNING] 1 top [PLAY] Single [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> 1;\n[INNING] 1 top [PLAY] Groundout [BATTER] Person E [PITCHER] Person A [FIELDERS] Person B [MOVEMENTS] Person E home -> 1 [out 6-3];\n[GAME_END]".to_string();
+
+        for text in test_games().iter().chain([&hops]) {
+            let game = parse(text);
+            let rendered = parse(&to_text(&game, false, false));
+            assert_eq!(serde_json::to_value(&rendered).unwrap(), serde_json::to_value(&game).unwrap());
+        }
+    }
+
+    #[test]
+    fn producers_agree_after_canonicalizing() {
+        let header = "[GAME] 1 [DATE] 2024-04-01 [VENUE] Example Park [WEATHER] Clear 70F 5mph\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n[SECOND_BASE] Person E\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n";
+        let one = format!("{}[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1, Person D 1 -> 2;\n[INNING] 1 top [PLAY] Double Play [BATTER] Person F [PITCHER] Person A [FIELDERS] Person E, Person B [MOVEMENTS] Person F home -> 1 [out], Person D 2 -> 3 [out];\n[GAME_END]", header);
+        let two = format!("{}[INNING]  1 top [PLAY] Single\t[BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 2;\r\n[INNING] 1 top [PLAY] Double Play [BATTER] Person F [PITCHER] Person A [FIELDERS] Person B, Person E [MOVEMENTS] Person D 2 -> 3 [out], Person F home -> 1 [out];\r\n[GAME_END]\r\n", header.replace("70F 5mph", "70 5"));
+
+        assert_ne!(canonicalize(&one, false).unwrap(), canonicalize(&two, false).unwrap());
+        assert_eq!(canonicalize(&one, true).unwrap(), canonicalize(&two, true).unwrap());
+        assert!(canonicalize(&one, true).unwrap().contains("[MOVEMENTS] Person D home -> 2;"));
+        assert!(canonicalize(header, false).is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use std::fmt;
+
+use pyo3::{prelude::{pyclass, pymethods, PyErr}, exceptions::PyValueError};
+
+use super::game::Inning;
+
+/// The kind of problem a diagnostic reports. Each kind has a stable number, shared by its error and warning codes,
+/// e.g. `E301` and `W301` for `RunnerNotOnBase`. New kinds get new numbers; numbers are never reused.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DiagnosticKind {
+    // the input itself
+    InvalidInput,
+    InvalidControlCharacter,
+    InvalidUtf8,
+    BufferLimitExceeded,
+    IncompleteGame,
+    MissingSection,
+    InvalidState,
+    // the context section
+    UnknownVenue,
+    UnknownWeatherCondition,
+    ImplausibleWeather,
+    // the team sections
+    ImplausibleRoster,
+    // plays
+    RunnerNotOnBase,
+    RunnerMovesBackwards,
+    BrokenMovementChain,
+    FielderNotOnFieldingTeam,
+    TooManyOuts,
+    ImpossibleBase,
+    MissingOnError,
+    NoFielderAtPosition,
+    UndeclaredPlayer,
+    FielderNotInField,
+    PlayerLeftGame,
+}
+
+impl DiagnosticKind {
+    pub fn number(&self) -> u16 {
+        match self {
+            DiagnosticKind::InvalidInput => 100,
+            DiagnosticKind::InvalidControlCharacter => 101,
+            DiagnosticKind::InvalidUtf8 => 102,
+            DiagnosticKind::BufferLimitExceeded => 103,
+            DiagnosticKind::IncompleteGame => 104,
+            DiagnosticKind::MissingSection => 105,
+            DiagnosticKind::InvalidState => 106,
+            DiagnosticKind::UnknownVenue => 201,
+            DiagnosticKind::UnknownWeatherCondition => 202,
+            DiagnosticKind::ImplausibleWeather => 203,
+            DiagnosticKind::ImplausibleRoster => 401,
+            DiagnosticKind::RunnerNotOnBase => 301,
+            DiagnosticKind::RunnerMovesBackwards => 302,
+            DiagnosticKind::BrokenMovementChain => 303,
+            DiagnosticKind::FielderNotOnFieldingTeam => 304,
+            DiagnosticKind::TooManyOuts => 305,
+            DiagnosticKind::ImpossibleBase => 306,
+            DiagnosticKind::MissingOnError => 307,
+            DiagnosticKind::NoFielderAtPosition => 308,
+            DiagnosticKind::UndeclaredPlayer => 309,
+            DiagnosticKind::FielderNotInField => 310,
+            DiagnosticKind::PlayerLeftGame => 311,
+        }
+    }
+
+    /// Return the kind's name, e.g. `RunnerNotOnBase`.
+    pub fn name(&self) -> String {
+        format!("{:?}", self)
+    }
+}
+
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem found while parsing, with a stable code such as `E301` for tooling to filter on.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    #[pyo3(get)]
+    pub kind: DiagnosticKind,
+    #[pyo3(get)]
+    pub severity: Severity,
+    #[pyo3(get)]
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Create an error diagnostic.
+    pub fn new(kind: DiagnosticKind, message: impl Into<String>) -> Self {
+        Self { kind, severity: Severity::Error, message: message.into() }
+    }
+
+    pub fn as_warning(mut self) -> Self {
+        self.severity = Severity::Warning;
+        self
+    }
+
+    /// Prefix the message with the inning the problem was found in.
+    pub fn in_inning(mut self, inning: Inning) -> Self {
+        self.message = format!("Inning {}: {}", inning.to_string(), self.message);
+        self
+    }
+
+    /// Return the code, e.g. `E301` for an error or `W301` for a warning.
+    pub fn code(&self) -> String {
+        let prefix = match self.severity {
+            Severity::Error => 'E',
+            Severity::Warning => 'W',
+        };
+        format!("{}{}", prefix, self.kind.number())
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<Diagnostic> for String {
+    fn from(diagnostic: Diagnostic) -> Self {
+        diagnostic.message
+    }
+}
+
+impl From<Diagnostic> for PyErr {
+    fn from(diagnostic: Diagnostic) -> Self {
+        PyErr::new::<ParseError, _>((diagnostic.kind, diagnostic.message))
+    }
+}
+
+#[pymethods]
+impl Diagnostic {
+    #[getter(code)]
+    fn py_code(&self) -> String {
+        self.code()
+    }
+
+    #[getter]
+    fn name(&self) -> String {
+        self.kind.name()
+    }
+
+    fn __str__(&self) -> String {
+        format!("{} {}: {}", self.code(), self.kind.name(), self.message)
+    }
+}
+
+/// The exception raised for a problem found while parsing. It's a `ValueError`, carrying the problem's `diagnostic`.
+#[pyclass(extends = PyValueError)]
+pub struct ParseError {
+    #[pyo3(get)]
+    pub diagnostic: Diagnostic,
+}
+
+#[pymethods]
+impl ParseError {
+    #[new]
+    fn new(kind: DiagnosticKind, message: String) -> Self {
+        Self { diagnostic: Diagnostic::new(kind, message) }
+    }
+
+    #[getter]
+    fn code(&self) -> String {
+        self.diagnostic.code()
+    }
+
+    fn __str__(&self) -> String {
+        self.diagnostic.message.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::{prelude::{PyAnyMethods, PyRef, Python}, PyTypeInfo};
+
+    #[test]
+    fn codes_follow_severity() {
+        let diagnostic = Diagnostic::new(DiagnosticKind::RunnerNotOnBase, "No runner is on first base");
+        assert_eq!(diagnostic.code(), "E301");
+        assert_eq!(diagnostic.as_warning().code(), "W301");
+    }
+
+    #[test]
+    fn parse_error_carries_diagnostic() {
+        pyo3::prepare_freethreaded_python();
+
+        Python::with_gil(|py| {
+            let err = PyErr::from(Diagnostic::new(DiagnosticKind::UnknownVenue, "Unknown venue: Nowhere"));
+            assert!(err.is_instance_of::<PyValueError>(py));
+
+            let value = err.value(py);
+            assert!(value.is_instance(&ParseError::type_object(py)).unwrap());
+            assert_eq!(value.to_string(), "Unknown venue: Nowhere");
+            let parse_error = value.extract::<PyRef<ParseError>>().unwrap();
+            assert_eq!(parse_error.code(), "E201");
+        });
+    }
+}
@@ -0,0 +1,21 @@
+use super::game::{Inning, Play};
+use pyo3::prelude::pyclass;
+
+/// Something that happened while parsing, queued until drained with `Parser.poll_events()`.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub enum ParserEvent {
+    PlayParsed {
+        play: Play,
+    },
+    /// Queued before the first play of each inning.
+    InningChanged {
+        inning: Inning,
+    },
+    /// Queued before a play by a different pitcher than the fielding team's last play.
+    PitchingChange {
+        pitcher: String,
+        replaced: String,
+    },
+    GameFinished(),
+}
@@ -0,0 +1,199 @@
+use pyo3::{
+    prelude::{PyDictMethods, PyListMethods, PyObject, PyResult, Python},
+    exceptions::PyValueError,
+    types::{PyDict, PyList},
+    IntoPyObjectExt,
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyCase {
+    Snake,
+    Camel,
+}
+
+impl std::str::FromStr for KeyCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "snake" => Ok(KeyCase::Snake),
+            "camel" => Ok(KeyCase::Camel),
+            _ => Err(format!("Invalid key case: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnumStyle {
+    /// `{"Single": {"batter": ...}}`
+    Tagged,
+    /// `{"type": "Single", "batter": ...}`
+    String,
+}
+
+impl std::str::FromStr for EnumStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tagged" => Ok(EnumStyle::Tagged),
+            "string" => Ok(EnumStyle::String),
+            _ => Err(format!("Invalid enum style: {}", s)),
+        }
+    }
+}
+
+/// How games and plays are laid out when exported as JSON or Python objects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub key_case: KeyCase,
+    pub enum_style: EnumStyle,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            key_case: KeyCase::Snake,
+            enum_style: EnumStyle::Tagged,
+        }
+    }
+}
+
+impl ExportOptions {
+    pub fn from_strs(key_case: &str, enum_style: &str) -> Result<Self, String> {
+        Ok(Self {
+            key_case: key_case.parse()?,
+            enum_style: enum_style.parse()?,
+        })
+    }
+}
+
+/// Serialize a value to JSON, laid out according to the export options.
+pub fn export_value<T: Serialize>(value: &T, options: ExportOptions) -> Result<Value, String> {
+    let value = serde_json::to_value(value).map_err(|e| format!("Failed to export: {}", e))?;
+    Ok(apply_options(value, options))
+}
+
+fn apply_options(value: Value, options: ExportOptions) -> Value {
+    match value {
+        Value::Array(values) => Value::Array(values.into_iter().map(|value| apply_options(value, options)).collect()),
+        Value::Object(values) => {
+            let values = match options.enum_style {
+                EnumStyle::Tagged => values,
+                EnumStyle::String => untag_variant(values),
+            };
+
+            let values = values.into_iter()
+                .map(|(key, value)| {
+                    let key = match options.key_case {
+                        KeyCase::Snake => key,
+                        KeyCase::Camel => to_camel_case(&key),
+                    };
+                    (key, apply_options(value, options))
+                })
+                .collect::<Map<_, _>>();
+
+            Value::Object(values)
+        },
+        value => value,
+    }
+}
+
+/// Turn a serde enum variant with fields, `{"Variant": {...}}`, into `{"type": "Variant", ...}`.
+fn untag_variant(values: Map<String, Value>) -> Map<String, Value> {
+    // struct fields are snake case, so a single capitalised key can only be a variant name
+    let is_variant = values.len() == 1 && values.keys().all(|key| key.starts_with(char::is_uppercase));
+    if !is_variant {
+        return values;
+    }
+
+    let (variant, fields) = values.into_iter().next().unwrap();
+    let mut untagged = Map::new();
+    untagged.insert("type".to_string(), Value::String(variant));
+    if let Value::Object(fields) = fields {
+        untagged.extend(fields);
+    }
+
+    untagged
+}
+
+fn to_camel_case(key: &str) -> String {
+    let mut camel = String::new();
+    let mut capitalise_next = false;
+    for c in key.chars() {
+        if c == '_' {
+            capitalise_next = true;
+        } else if capitalise_next {
+            camel.extend(c.to_uppercase());
+            capitalise_next = false;
+        } else {
+            camel.push(c);
+        }
+    }
+
+    camel
+}
+
+/// Convert a serializable value to the equivalent plain Python objects.
+pub fn to_py_object<T: Serialize>(py: Python<'_>, value: &T, options: ExportOptions) -> PyResult<PyObject> {
+    let value = export_value(value, options).map_err(PyValueError::new_err)?;
+    json_to_py_object(py, &value)
+}
+
+pub(crate) fn json_to_py_object(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    match value {
+        Value::Null => Ok(py.None()),
+        Value::Bool(value) => value.into_py_any(py),
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(value), _) => value.into_py_any(py),
+            (None, Some(value)) => value.into_py_any(py),
+            (None, None) => number.as_f64().into_py_any(py),
+        },
+        Value::String(value) => value.into_py_any(py),
+        Value::Array(values) => {
+            let list = PyList::empty(py);
+            for value in values {
+                list.append(json_to_py_object(py, value)?)?;
+            }
+            list.into_py_any(py)
+        },
+        Value::Object(values) => {
+            let dict = PyDict::new(py);
+            for (key, value) in values {
+                dict.set_item(key, json_to_py_object(py, value)?)?;
+            }
+            dict.into_py_any(py)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn export_with_options() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+        let play = &game.plays[0];
+
+        let tagged = export_value(play, ExportOptions::default()).unwrap();
+        assert_eq!(tagged["play_content"]["Strikeout"]["batter"], "Garrett Hampson");
+        assert_eq!(tagged["inning"]["top_bottom"], "Top");
+
+        let options = ExportOptions::from_strs("camel", "string").unwrap();
+        let untagged = export_value(play, options).unwrap();
+        assert_eq!(untagged["playContent"]["type"], "Strikeout");
+        assert_eq!(untagged["playContent"]["batter"], "Garrett Hampson");
+        assert_eq!(untagged["inning"]["topBottom"], "Top");
+
+        let game = export_value(&game, options).unwrap();
+        assert!(game["context"]["weather"]["windSpeedUnit"].is_string());
+
+        assert!(ExportOptions::from_strs("kebab", "tagged").is_err());
+    }
+}
@@ -1,23 +1,295 @@
 use std::cmp::Ordering;
-use pyo3::pyclass;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use pyo3::{prelude::{pyclass, pymethods, Bound, PyAnyMethods, PyObject, PyRef, PyResult, Python}, exceptions::{PyIndexError, PyValueError}, types::{PyIterator, PyList}};
+use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
-#[derive(Debug)]
+use super::{
+    section_matches,
+    CONTEXT_SECTION_DATE_REGEX,
+    CONTEXT_SECTION_GAME_REGEX,
+    CONTEXT_SECTION_VENUE_REGEX,
+    CONTEXT_SECTION_WEATHER_REGEX,
+    PLAYER_NAME_REGEX,
+    TEAM_SECTION_PLAYER_REGEX,
+    TEAM_SECTION_TEAM_REGEX,
+};
+use super::export::{export_value, json_to_py_object, to_py_object, ExportOptions};
+use super::gumbo::to_gumbo;
+use super::scoring::{decisions, Decisions};
+use super::canonical::to_text;
+use super::half_innings::{half_innings, HalfInningSummary};
+use super::play_by_play::{play_by_play, render_play_by_play, PlayByPlayRow};
+use super::retrosheet::to_retrosheet;
+use super::indexed::IndexedGame;
+use super::play_list::PlayList;
+use super::small_string::SmallString;
+use super::state::{DefensiveAlignment, LiveGameState, PinchRunners, RunnerPositions};
+
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TemperatureUnit {
+    Fahrenheit,
+    Celsius,
+}
+
+impl ToString for TemperatureUnit {
+    fn to_string(&self) -> String {
+        match self {
+            TemperatureUnit::Fahrenheit => "F",
+            TemperatureUnit::Celsius => "C",
+        }.to_string()
+    }
+}
+
+impl std::str::FromStr for TemperatureUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "F" => Ok(TemperatureUnit::Fahrenheit),
+            "C" => Ok(TemperatureUnit::Celsius),
+            _ => Err(format!("Invalid temperature unit: {}", s)),
+        }
+    }
+}
+
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpeedUnit {
+    MilesPerHour,
+    KilometersPerHour,
+}
+
+impl ToString for SpeedUnit {
+    fn to_string(&self) -> String {
+        match self {
+            SpeedUnit::MilesPerHour => "mph",
+            SpeedUnit::KilometersPerHour => "kph",
+        }.to_string()
+    }
+}
+
+impl std::str::FromStr for SpeedUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mph" => Ok(SpeedUnit::MilesPerHour),
+            "kph" => Ok(SpeedUnit::KilometersPerHour),
+            _ => Err(format!("Invalid speed unit: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnitSystem {
+    Imperial,
+    Metric,
+}
+
+impl std::str::FromStr for UnitSystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "imperial" => Ok(UnitSystem::Imperial),
+            "metric" => Ok(UnitSystem::Metric),
+            _ => Err(format!("Invalid unit system: {}", s)),
+        }
+    }
+}
+
+/// A weather condition as reported by MLB, or any other condition as written.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum WeatherCondition {
+    Clear,
+    Sunny,
+    PartlyCloudy,
+    Cloudy,
+    Overcast,
+    Dome,
+    RoofClosed,
+    Drizzle,
+    Rain,
+    Snow,
+    Other(SmallString),
+}
+
+impl ToString for WeatherCondition {
+    fn to_string(&self) -> String {
+        match self {
+            WeatherCondition::Clear => "Clear",
+            WeatherCondition::Sunny => "Sunny",
+            WeatherCondition::PartlyCloudy => "Partly Cloudy",
+            WeatherCondition::Cloudy => "Cloudy",
+            WeatherCondition::Overcast => "Overcast",
+            WeatherCondition::Dome => "Dome",
+            WeatherCondition::RoofClosed => "Roof Closed",
+            WeatherCondition::Drizzle => "Drizzle",
+            WeatherCondition::Rain => "Rain",
+            WeatherCondition::Snow => "Snow",
+            WeatherCondition::Other(condition) => condition,
+        }.to_string()
+    }
+}
+
+impl From<&str> for WeatherCondition {
+    fn from(s: &str) -> Self {
+        match s {
+            "Clear" => WeatherCondition::Clear,
+            "Sunny" => WeatherCondition::Sunny,
+            "Partly Cloudy" => WeatherCondition::PartlyCloudy,
+            "Cloudy" => WeatherCondition::Cloudy,
+            "Overcast" => WeatherCondition::Overcast,
+            "Dome" => WeatherCondition::Dome,
+            "Roof Closed" => WeatherCondition::RoofClosed,
+            "Drizzle" => WeatherCondition::Drizzle,
+            "Rain" => WeatherCondition::Rain,
+            "Snow" => WeatherCondition::Snow,
+            _ => WeatherCondition::Other(s.into()),
+        }
+    }
+}
+
+impl From<String> for WeatherCondition {
+    fn from(s: String) -> Self {
+        WeatherCondition::from(s.as_str())
+    }
+}
+
+impl From<WeatherCondition> for String {
+    fn from(condition: WeatherCondition) -> Self {
+        condition.to_string()
+    }
+}
+
+impl WeatherCondition {
+    /// The conditions MLB reports.
+    pub const KNOWN: [WeatherCondition; 10] = [
+        WeatherCondition::Clear,
+        WeatherCondition::Sunny,
+        WeatherCondition::PartlyCloudy,
+        WeatherCondition::Cloudy,
+        WeatherCondition::Overcast,
+        WeatherCondition::Dome,
+        WeatherCondition::RoofClosed,
+        WeatherCondition::Drizzle,
+        WeatherCondition::Rain,
+        WeatherCondition::Snow,
+    ];
+
+    /// Check that the condition is one MLB reports, for strict parsing.
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            WeatherCondition::Other(condition) => Err(format!("Unknown weather condition: {}", condition)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Weather {
-    condition: String,
-    temperature: u64,
-    wind_speed: u64,
+    pub condition: WeatherCondition,
+    #[pyo3(get)]
+    pub temperature: f64,
+    #[pyo3(get)]
+    pub temperature_unit: TemperatureUnit,
+    #[pyo3(get)]
+    pub wind_speed: f64,
+    #[pyo3(get)]
+    pub wind_speed_unit: SpeedUnit,
+}
+
+impl Weather {
+    /// Return a description of each value outside the range plausible for a game, e.g. a temperature above 120°F.
+    /// Unusual real weather can fall outside these ranges, so they're only worth a warning.
+    pub fn implausible_values(&self) -> Vec<String> {
+        let imperial = self.in_units(UnitSystem::Imperial);
+        let mut problems = Vec::new();
+        if !(PLAUSIBLE_TEMPERATURE.0..=PLAUSIBLE_TEMPERATURE.1).contains(&imperial.temperature) {
+            problems.push(format!(
+                "Temperature {}{} is outside {}F to {}F",
+                self.temperature, self.temperature_unit.to_string(), PLAUSIBLE_TEMPERATURE.0, PLAUSIBLE_TEMPERATURE.1,
+            ));
+        }
+        if imperial.wind_speed > PLAUSIBLE_WIND_SPEED {
+            problems.push(format!(
+                "Wind speed {}{} is above {}mph",
+                self.wind_speed, self.wind_speed_unit.to_string(), PLAUSIBLE_WIND_SPEED,
+            ));
+        }
+
+        problems
+    }
+
+    /// Return the weather with its temperature and wind speed converted to the given unit system.
+    pub fn in_units(&self, units: UnitSystem) -> Self {
+        let (temperature, temperature_unit) = match (self.temperature_unit, units) {
+            (TemperatureUnit::Fahrenheit, UnitSystem::Metric) => ((self.temperature - 32.0) * 5.0 / 9.0, TemperatureUnit::Celsius),
+            (TemperatureUnit::Celsius, UnitSystem::Imperial) => (self.temperature * 9.0 / 5.0 + 32.0, TemperatureUnit::Fahrenheit),
+            _ => (self.temperature, self.temperature_unit),
+        };
+
+        let (wind_speed, wind_speed_unit) = match (self.wind_speed_unit, units) {
+            (SpeedUnit::MilesPerHour, UnitSystem::Metric) => (self.wind_speed * KILOMETERS_PER_MILE, SpeedUnit::KilometersPerHour),
+            (SpeedUnit::KilometersPerHour, UnitSystem::Imperial) => (self.wind_speed / KILOMETERS_PER_MILE, SpeedUnit::MilesPerHour),
+            _ => (self.wind_speed, self.wind_speed_unit),
+        };
+
+        Self {
+            condition: self.condition.clone(),
+            temperature,
+            temperature_unit,
+            wind_speed,
+            wind_speed_unit,
+        }
+    }
+}
+
+#[pymethods]
+impl Weather {
+    #[getter]
+    fn condition(&self) -> String {
+        self.condition.to_string()
+    }
+
+    /// Whether the condition is one MLB reports, rather than free text.
+    #[getter]
+    fn is_known_condition(&self) -> bool {
+        self.condition.validate().is_ok()
+    }
+
+    /// Return the weather converted to `"imperial"` or `"metric"` units.
+    fn to_units(&self, units: &str) -> PyResult<Self> {
+        let units = units.parse::<UnitSystem>().map_err(PyValueError::new_err)?;
+        Ok(self.in_units(units))
+    }
 }
 
-#[derive(Debug)]
+const KILOMETERS_PER_MILE: f64 = 1.609344;
+// the range of temperatures, in Fahrenheit, and the highest wind speed, in miles per hour, expected at a game
+const PLAUSIBLE_TEMPERATURE: (f64, f64) = (0.0, 120.0);
+const PLAUSIBLE_WIND_SPEED: f64 = 60.0;
+
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Context {
-    game_pk: u64,
-    date: String,
-    venue: String,
-    weather: Weather,
+    #[pyo3(get)]
+    pub game_pk: u64,
+    #[pyo3(get)]
+    pub date: String,
+    #[pyo3(get)]
+    pub venue: SmallString,
+    #[pyo3(get)]
+    pub weather: Weather,
 }
 
-#[derive(Clone, Copy, EnumIter, PartialEq, Eq, Debug)]
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, EnumIter, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum Position {
     Pitcher,
     Catcher,
@@ -64,6 +336,26 @@ impl ToString for Position {
     }
 }
 
+impl Position {
+    /// Return the scorekeeping number of the position, from 1 for the pitcher to 9 for right field, and 10 for the
+    /// designated hitter.
+    pub fn number(&self) -> Option<u8> {
+        match self {
+            Position::Pitcher | Position::StartingPitcher | Position::ReliefPitcher => Some(1),
+            Position::Catcher => Some(2),
+            Position::FirstBase => Some(3),
+            Position::SecondBase => Some(4),
+            Position::ThirdBase => Some(5),
+            Position::Shortstop => Some(6),
+            Position::LeftField => Some(7),
+            Position::CenterField => Some(8),
+            Position::RightField => Some(9),
+            Position::DesignatedHitter => Some(10),
+            _ => None,
+        }
+    }
+}
+
 impl std::str::FromStr for Position {
     type Err = String;
 
@@ -92,19 +384,56 @@ impl std::str::FromStr for Position {
     }
 }
 
-#[derive(Clone, Debug)]
+#[pymethods]
+impl Position {
+    /// Parse a position as written in a game, e.g. `"SHORTSTOP"`.
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn py_from_str(s: &str) -> PyResult<Self> {
+        s.parse::<Self>().map_err(PyValueError::new_err)
+    }
+
+    /// Return a position as written in a game.
+    #[pyo3(name = "to_str")]
+    fn py_to_str(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Player {
     pub position: Position,
-    pub name: String,
+    #[pyo3(get)]
+    pub name: SmallString,
 }
 
-#[derive(Clone, Debug)]
+#[pymethods]
+impl Player {
+    /// Return the position the player is listed under, e.g. `"PITCHER"`.
+    #[getter]
+    fn position(&self) -> String {
+        self.position.to_string()
+    }
+
+    /// The position the player is listed under as a `Position`, to compare without strings.
+    #[getter]
+    fn position_enum(&self) -> Position {
+        self.position
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Team {
-    team_id: u64,
-    players: Vec<Player>,
+    #[pyo3(get)]
+    pub team_id: u64,
+    #[pyo3(get)]
+    pub players: Vec<Player>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TopBottom {
     Top,
     Bottom,
@@ -131,9 +460,28 @@ impl std::str::FromStr for TopBottom {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[pymethods]
+impl TopBottom {
+    /// Parse the half of an inning as written in a game, e.g. `"top"`.
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn py_from_str(s: &str) -> PyResult<Self> {
+        s.parse::<Self>().map_err(PyValueError::new_err)
+    }
+
+    /// Return the half of an inning as written in a game.
+    #[pyo3(name = "to_str")]
+    fn py_to_str(&self) -> String {
+        self.to_string()
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Inning {
+    #[pyo3(get)]
     pub number: u64,
+    #[pyo3(get)]
     pub top_bottom: TopBottom,
 }
 
@@ -143,12 +491,41 @@ impl ToString for Inning {
     }
 }
 
+impl std::str::FromStr for Inning {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (number, top_bottom) = s.split_once(' ').ok_or(format!("Invalid inning: {}", s))?;
+        Ok(Inning {
+            number: number.parse::<u64>().map_err(|_| format!("Invalid inning: {}", s))?,
+            top_bottom: top_bottom.parse::<TopBottom>()?,
+        })
+    }
+}
+
+#[pymethods]
+impl Inning {
+    /// Parse an inning as written in a game, e.g. `"7 bottom"`.
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn py_from_str(s: &str) -> PyResult<Self> {
+        s.parse::<Self>().map_err(PyValueError::new_err)
+    }
+
+    /// Return an inning as written in a game.
+    #[pyo3(name = "to_str")]
+    fn py_to_str(&self) -> String {
+        self.to_string()
+    }
+}
+
 pub enum BaseComparison {
     From,
     To,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Base {
     Home,
     First,
@@ -193,209 +570,510 @@ impl std::str::FromStr for Base {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[pymethods]
+impl Base {
+    /// Parse a base as written in a game, e.g. `"home"`.
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn py_from_str(s: &str) -> PyResult<Self> {
+        s.parse::<Self>().map_err(PyValueError::new_err)
+    }
+
+    /// Return a base as written in a game.
+    #[pyo3(name = "to_str")]
+    fn py_to_str(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// The type-specific content of a play. In Python each variant is a subclass of `PlayContent`, exported at the
+/// top level (e.g. `Single`) and supporting structural pattern matching on its fields.
+#[pyclass(eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PlayContent {
     Groundout {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     BuntGroundout {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     Strikeout {
-        batter: String,
-        pitcher: String,
+        batter: SmallString,
+        pitcher: SmallString,
     },
     Lineout {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     BuntLineout {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     Flyout {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     PopOut {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     BuntPopOut {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     Forceout {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     FieldersChoiceOut {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
-        scoring_runner: String,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
+        scoring_runner: SmallString,
     },
     DoublePlay {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     TriplePlay {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     RunnerDoublePlay {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     RunnerTriplePlay {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     GroundedIntoDoublePlay {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     StrikeoutDoublePlay {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     Pickoff {
         base: Base,
-        fielders: Vec<String>,
-        runner: String,
+        fielders: Vec<SmallString>,
+        runner: SmallString,
     },
     PickoffError {
         base: Base,
-        fielders: Vec<String>,
-        runner: String,
+        fielders: Vec<SmallString>,
+        runner: SmallString,
     },
     CaughtStealing {
         base: Base,
-        fielders: Vec<String>,
-        runner: String,
+        fielders: Vec<SmallString>,
+        runner: SmallString,
     },
     PickoffCaughtStealing {
         base: Base,
-        fielders: Vec<String>,
-        runner: String,
+        fielders: Vec<SmallString>,
+        runner: SmallString,
     },
     WildPitch {
-        pitcher: String,
-        runner: String,
+        pitcher: SmallString,
+        runner: SmallString,
     },
     RunnerOut {
-        fielders: Vec<String>,
-        runner: String,
+        fielders: Vec<SmallString>,
+        runner: SmallString,
+        // where the out was recorded, if written
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base: Option<Base>,
     },
     FieldOut {
-        fielders: Vec<String>,
-        runner: String,
+        fielders: Vec<SmallString>,
+        runner: SmallString,
+        // where the out was recorded, if written
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        base: Option<Base>,
     },
     BatterOut {
-        batter: String,
-        catcher: String,
+        batter: SmallString,
+        catcher: SmallString,
     },
     Balk {
-        pitcher: String,
+        pitcher: SmallString,
     },
     PassedBall {
-        pitcher: String,
-        catcher: String,
+        pitcher: SmallString,
+        catcher: SmallString,
     },
     Error {
-        pitcher: String,
-        catcher: String,
+        pitcher: SmallString,
+        catcher: SmallString,
+        // the fielder charged with the error, if known
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error_by: Option<SmallString>,
     },
     Single {
-        batter: String,
-        pitcher: String,
+        batter: SmallString,
+        pitcher: SmallString,
     },
     Double {
-        batter: String,
-        pitcher: String,
+        batter: SmallString,
+        pitcher: SmallString,
     },
     Triple {
-        batter: String,
-        pitcher: String,
+        batter: SmallString,
+        pitcher: SmallString,
     },
     HomeRun {
-        batter: String,
-        pitcher: String,
+        batter: SmallString,
+        pitcher: SmallString,
     },
     Walk {
-        batter: String,
-        pitcher: String,
+        batter: SmallString,
+        pitcher: SmallString,
     },
     IntentWalk {
-        batter: String,
-        pitcher: String,
+        batter: SmallString,
+        pitcher: SmallString,
     },
     HitByPitch {
-        batter: String,
-        pitcher: String,
+        batter: SmallString,
+        pitcher: SmallString,
     },
     FieldersChoice {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     CatcherInterference {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
     },
     StolenBase {
         base: Base,
-        scoring_runner: String,
+        // stored as `scoring_runner` in older exports
+        #[serde(alias = "scoring_runner")]
+        runner: SmallString,
     },
     SacFly {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
-        scoring_runner: String,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
+        scoring_runner: SmallString,
     },
     SacFlyDoublePlay {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
-        scoring_runner: String,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
+        scoring_runner: SmallString,
     },
     SacBunt {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
-        runner: String,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
+        runner: SmallString,
     },
     SacBuntDoublePlay {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
-        runner: String,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
+        runner: SmallString,
     },
     FieldError {
-        batter: String,
-        pitcher: String,
-        fielders: Vec<String>,
+        batter: SmallString,
+        pitcher: SmallString,
+        fielders: Vec<SmallString>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        error_by: Option<SmallString>,
     },
-    GameAdvisory,
+    /// A fielder already in the field moving to the position with the given number, e.g. 6 for shortstop. The second
+    /// fielder listed, if any, moves to the position the first left.
+    DefensiveSwitch {
+        fielders: Vec<SmallString>,
+        position: u8,
+    },
+    /// A fielder coming into the game at the position with the given number, in place of the second fielder listed, if
+    /// any, who leaves the game.
+    DefensiveSub {
+        fielders: Vec<SmallString>,
+        position: u8,
+    },
+    GameAdvisory(),
+}
+
+impl PlayContent {
+    pub fn play_type(&self) -> PlayType {
+        match self {
+            PlayContent::Groundout { .. } => PlayType::Groundout,
+            PlayContent::BuntGroundout { .. } => PlayType::BuntGroundout,
+            PlayContent::Strikeout { .. } => PlayType::Strikeout,
+            PlayContent::Lineout { .. } => PlayType::Lineout,
+            PlayContent::BuntLineout { .. } => PlayType::BuntLineout,
+            PlayContent::Flyout { .. } => PlayType::Flyout,
+            PlayContent::PopOut { .. } => PlayType::PopOut,
+            PlayContent::BuntPopOut { .. } => PlayType::BuntPopOut,
+            PlayContent::Forceout { .. } => PlayType::Forceout,
+            PlayContent::FieldersChoiceOut { .. } => PlayType::FieldersChoiceOut,
+            PlayContent::DoublePlay { .. } => PlayType::DoublePlay,
+            PlayContent::TriplePlay { .. } => PlayType::TriplePlay,
+            PlayContent::RunnerDoublePlay { .. } => PlayType::RunnerDoublePlay,
+            PlayContent::RunnerTriplePlay { .. } => PlayType::RunnerTriplePlay,
+            PlayContent::GroundedIntoDoublePlay { .. } => PlayType::GroundedIntoDoublePlay,
+            PlayContent::StrikeoutDoublePlay { .. } => PlayType::StrikeoutDoublePlay,
+            PlayContent::Pickoff { .. } => PlayType::Pickoff,
+            PlayContent::PickoffError { .. } => PlayType::PickoffError,
+            PlayContent::CaughtStealing { .. } => PlayType::CaughtStealing,
+            PlayContent::PickoffCaughtStealing { .. } => PlayType::PickoffCaughtStealing,
+            PlayContent::WildPitch { .. } => PlayType::WildPitch,
+            PlayContent::RunnerOut { .. } => PlayType::RunnerOut,
+            PlayContent::FieldOut { .. } => PlayType::FieldOut,
+            PlayContent::BatterOut { .. } => PlayType::BatterOut,
+            PlayContent::Balk { .. } => PlayType::Balk,
+            PlayContent::PassedBall { .. } => PlayType::PassedBall,
+            PlayContent::Error { .. } => PlayType::Error,
+            PlayContent::Single { .. } => PlayType::Single,
+            PlayContent::Double { .. } => PlayType::Double,
+            PlayContent::Triple { .. } => PlayType::Triple,
+            PlayContent::HomeRun { .. } => PlayType::HomeRun,
+            PlayContent::Walk { .. } => PlayType::Walk,
+            PlayContent::IntentWalk { .. } => PlayType::IntentWalk,
+            PlayContent::HitByPitch { .. } => PlayType::HitByPitch,
+            PlayContent::FieldersChoice { .. } => PlayType::FieldersChoice,
+            PlayContent::CatcherInterference { .. } => PlayType::CatcherInterference,
+            PlayContent::StolenBase { .. } => PlayType::StolenBase,
+            PlayContent::SacFly { .. } => PlayType::SacFly,
+            PlayContent::SacFlyDoublePlay { .. } => PlayType::SacFlyDoublePlay,
+            PlayContent::SacBunt { .. } => PlayType::SacBunt,
+            PlayContent::SacBuntDoublePlay { .. } => PlayType::SacBuntDoublePlay,
+            PlayContent::FieldError { .. } => PlayType::FieldError,
+            PlayContent::DefensiveSwitch { .. } => PlayType::DefensiveSwitch,
+            PlayContent::DefensiveSub { .. } => PlayType::DefensiveSub,
+            PlayContent::GameAdvisory() => PlayType::GameAdvisory,
+        }
+    }
+
+    pub fn batter(&self) -> Option<&SmallString> {
+        match self {
+            PlayContent::Groundout { batter, .. } |
+            PlayContent::BuntGroundout { batter, .. } |
+            PlayContent::Strikeout { batter, .. } |
+            PlayContent::Lineout { batter, .. } |
+            PlayContent::BuntLineout { batter, .. } |
+            PlayContent::Flyout { batter, .. } |
+            PlayContent::PopOut { batter, .. } |
+            PlayContent::BuntPopOut { batter, .. } |
+            PlayContent::Forceout { batter, .. } |
+            PlayContent::FieldersChoiceOut { batter, .. } |
+            PlayContent::DoublePlay { batter, .. } |
+            PlayContent::TriplePlay { batter, .. } |
+            PlayContent::RunnerDoublePlay { batter, .. } |
+            PlayContent::RunnerTriplePlay { batter, .. } |
+            PlayContent::GroundedIntoDoublePlay { batter, .. } |
+            PlayContent::StrikeoutDoublePlay { batter, .. } |
+            PlayContent::BatterOut { batter, .. } |
+            PlayContent::Single { batter, .. } |
+            PlayContent::Double { batter, .. } |
+            PlayContent::Triple { batter, .. } |
+            PlayContent::HomeRun { batter, .. } |
+            PlayContent::Walk { batter, .. } |
+            PlayContent::IntentWalk { batter, .. } |
+            PlayContent::HitByPitch { batter, .. } |
+            PlayContent::FieldersChoice { batter, .. } |
+            PlayContent::CatcherInterference { batter, .. } |
+            PlayContent::SacFly { batter, .. } |
+            PlayContent::SacFlyDoublePlay { batter, .. } |
+            PlayContent::SacBunt { batter, .. } |
+            PlayContent::SacBuntDoublePlay { batter, .. } |
+            PlayContent::FieldError { batter, .. } => Some(batter),
+            _ => None,
+        }
+    }
+
+    pub fn pitcher(&self) -> Option<&SmallString> {
+        match self {
+            PlayContent::Groundout { pitcher, .. } |
+            PlayContent::BuntGroundout { pitcher, .. } |
+            PlayContent::Strikeout { pitcher, .. } |
+            PlayContent::Lineout { pitcher, .. } |
+            PlayContent::BuntLineout { pitcher, .. } |
+            PlayContent::Flyout { pitcher, .. } |
+            PlayContent::PopOut { pitcher, .. } |
+            PlayContent::BuntPopOut { pitcher, .. } |
+            PlayContent::Forceout { pitcher, .. } |
+            PlayContent::FieldersChoiceOut { pitcher, .. } |
+            PlayContent::DoublePlay { pitcher, .. } |
+            PlayContent::TriplePlay { pitcher, .. } |
+            PlayContent::RunnerDoublePlay { pitcher, .. } |
+            PlayContent::RunnerTriplePlay { pitcher, .. } |
+            PlayContent::GroundedIntoDoublePlay { pitcher, .. } |
+            PlayContent::StrikeoutDoublePlay { pitcher, .. } |
+            PlayContent::WildPitch { pitcher, .. } |
+            PlayContent::Balk { pitcher } |
+            PlayContent::PassedBall { pitcher, .. } |
+            PlayContent::Error { pitcher, .. } |
+            PlayContent::Single { pitcher, .. } |
+            PlayContent::Double { pitcher, .. } |
+            PlayContent::Triple { pitcher, .. } |
+            PlayContent::HomeRun { pitcher, .. } |
+            PlayContent::Walk { pitcher, .. } |
+            PlayContent::IntentWalk { pitcher, .. } |
+            PlayContent::HitByPitch { pitcher, .. } |
+            PlayContent::FieldersChoice { pitcher, .. } |
+            PlayContent::CatcherInterference { pitcher, .. } |
+            PlayContent::SacFly { pitcher, .. } |
+            PlayContent::SacFlyDoublePlay { pitcher, .. } |
+            PlayContent::SacBunt { pitcher, .. } |
+            PlayContent::SacBuntDoublePlay { pitcher, .. } |
+            PlayContent::FieldError { pitcher, .. } => Some(pitcher),
+            _ => None,
+        }
+    }
+
+    pub fn catcher(&self) -> Option<&SmallString> {
+        match self {
+            PlayContent::BatterOut { catcher, .. } |
+            PlayContent::PassedBall { catcher, .. } |
+            PlayContent::Error { catcher, .. } => Some(catcher),
+            _ => None,
+        }
+    }
+
+    pub fn fielders(&self) -> &[SmallString] {
+        match self {
+            PlayContent::Groundout { fielders, .. } |
+            PlayContent::BuntGroundout { fielders, .. } |
+            PlayContent::Lineout { fielders, .. } |
+            PlayContent::BuntLineout { fielders, .. } |
+            PlayContent::Flyout { fielders, .. } |
+            PlayContent::PopOut { fielders, .. } |
+            PlayContent::BuntPopOut { fielders, .. } |
+            PlayContent::Forceout { fielders, .. } |
+            PlayContent::FieldersChoiceOut { fielders, .. } |
+            PlayContent::DoublePlay { fielders, .. } |
+            PlayContent::TriplePlay { fielders, .. } |
+            PlayContent::RunnerDoublePlay { fielders, .. } |
+            PlayContent::RunnerTriplePlay { fielders, .. } |
+            PlayContent::GroundedIntoDoublePlay { fielders, .. } |
+            PlayContent::StrikeoutDoublePlay { fielders, .. } |
+            PlayContent::Pickoff { fielders, .. } |
+            PlayContent::PickoffError { fielders, .. } |
+            PlayContent::CaughtStealing { fielders, .. } |
+            PlayContent::PickoffCaughtStealing { fielders, .. } |
+            PlayContent::RunnerOut { fielders, .. } |
+            PlayContent::FieldOut { fielders, .. } |
+            PlayContent::FieldersChoice { fielders, .. } |
+            PlayContent::CatcherInterference { fielders, .. } |
+            PlayContent::SacFly { fielders, .. } |
+            PlayContent::SacFlyDoublePlay { fielders, .. } |
+            PlayContent::SacBunt { fielders, .. } |
+            PlayContent::SacBuntDoublePlay { fielders, .. } |
+            PlayContent::FieldError { fielders, .. } |
+            PlayContent::DefensiveSwitch { fielders, .. } |
+            PlayContent::DefensiveSub { fielders, .. } => fielders,
+            _ => &[],
+        }
+    }
+
+    pub fn runner(&self) -> Option<&SmallString> {
+        match self {
+            PlayContent::Pickoff { runner, .. } |
+            PlayContent::PickoffError { runner, .. } |
+            PlayContent::CaughtStealing { runner, .. } |
+            PlayContent::PickoffCaughtStealing { runner, .. } |
+            PlayContent::WildPitch { runner, .. } |
+            PlayContent::RunnerOut { runner, .. } |
+            PlayContent::FieldOut { runner, .. } |
+            PlayContent::StolenBase { runner, .. } |
+            PlayContent::SacBunt { runner, .. } |
+            PlayContent::SacBuntDoublePlay { runner, .. } => Some(runner),
+            _ => None,
+        }
+    }
+
+    pub fn scoring_runner(&self) -> Option<&SmallString> {
+        match self {
+            PlayContent::FieldersChoiceOut { scoring_runner, .. } |
+            PlayContent::SacFly { scoring_runner, .. } |
+            PlayContent::SacFlyDoublePlay { scoring_runner, .. } => Some(scoring_runner),
+            _ => None,
+        }
+    }
+
+    /// Return the fielder charged with the error on an error play, if known.
+    pub fn error_by(&self) -> Option<&SmallString> {
+        match self {
+            PlayContent::Error { error_by, .. } |
+            PlayContent::FieldError { error_by, .. } => error_by.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the player is named anywhere in the play's content, in any role.
+    pub fn involves(&self, name: &str) -> bool {
+        [self.batter(), self.pitcher(), self.catcher(), self.runner(), self.scoring_runner(), self.error_by()].into_iter()
+            .flatten()
+            .chain(self.fielders())
+            .any(|player| player == name)
+    }
+
+    pub fn base(&self) -> Option<Base> {
+        match self {
+            PlayContent::Pickoff { base, .. } |
+            PlayContent::PickoffError { base, .. } |
+            PlayContent::CaughtStealing { base, .. } |
+            PlayContent::PickoffCaughtStealing { base, .. } |
+            PlayContent::StolenBase { base, .. } => Some(*base),
+            PlayContent::RunnerOut { base, .. } |
+            PlayContent::FieldOut { base, .. } => *base,
+            _ => None,
+        }
+    }
+
+    /// Return the number of the position taken over in a defensive switch or substitution.
+    pub fn position(&self) -> Option<u8> {
+        match self {
+            PlayContent::DefensiveSwitch { position, .. } |
+            PlayContent::DefensiveSub { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+}
+
+#[pymethods]
+impl PlayContent {
+    /// The play type as written after `[PLAY]`, e.g. `"Home Run"`.
+    #[getter(play_type)]
+    fn py_play_type(&self) -> String {
+        self.play_type().to_string()
+    }
+
+    /// The runner of a stolen base, under the name it had before it moved to `runner`. Deprecated.
+    #[getter(scoring_runner)]
+    fn py_stolen_base_scoring_runner(&self) -> Option<SmallString> {
+        match self {
+            PlayContent::StolenBase { runner, .. } => Some(runner.clone()),
+            _ => None,
+        }
+    }
 }
 
+#[pyclass(eq, eq_int)]
 #[derive(Clone, Copy, Debug, Hash, EnumIter, PartialEq, Eq)]
 pub enum PlayType {
     Groundout,
@@ -440,6 +1118,8 @@ pub enum PlayType {
     SacBunt,
     SacBuntDoublePlay,
     FieldError,
+    DefensiveSwitch,
+    DefensiveSub,
     GameAdvisory,
 }
 
@@ -488,6 +1168,8 @@ impl ToString for PlayType {
             PlayType::SacBunt => "Sac Bunt",
             PlayType::SacBuntDoublePlay => "Sac Bunt Double Play",
             PlayType::FieldError => "Field Error",
+            PlayType::DefensiveSwitch => "Defensive Switch",
+            PlayType::DefensiveSub => "Defensive Sub",
             PlayType::GameAdvisory => "Game Advisory",
         }.to_string()
     }
@@ -540,12 +1222,30 @@ impl std::str::FromStr for PlayType {
             "Sac Bunt" => Ok(PlayType::SacBunt),
             "Sac Bunt Double Play" => Ok(PlayType::SacBuntDoublePlay),
             "Field Error" => Ok(PlayType::FieldError),
+            "Defensive Switch" => Ok(PlayType::DefensiveSwitch),
+            "Defensive Sub" => Ok(PlayType::DefensiveSub),
             "Game Advisory" => Ok(PlayType::GameAdvisory),
             _ => Err(format!("Invalid play type: {}", s)),
         }
     }
 }
 
+#[pymethods]
+impl PlayType {
+    /// Parse a play type as written in a game, e.g. `"Home Run"`.
+    #[staticmethod]
+    #[pyo3(name = "from_str")]
+    fn py_from_str(s: &str) -> PyResult<Self> {
+        s.parse::<Self>().map_err(PyValueError::new_err)
+    }
+
+    /// Return a play type as written in a game.
+    #[pyo3(name = "to_str")]
+    fn py_to_str(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl PlayType {
     pub fn requires_base(&self) -> bool {
         matches!(
@@ -558,6 +1258,15 @@ impl PlayType {
         )
     }
 
+    /// Whether the play may name the base the out was recorded at, with `[BASE]`.
+    pub fn allows_base(&self) -> bool {
+        matches!(
+            self,
+            PlayType::RunnerOut |
+            PlayType::FieldOut
+        )
+    }
+
     pub fn requires_batter(&self) -> bool {
         matches!(
             self,
@@ -595,6 +1304,45 @@ impl PlayType {
         )
     }
 
+    /// Return the scorecard abbreviation of a plate appearance's result, e.g. `1B` or `K`, or `None` for plays that
+    /// aren't plate appearances.
+    pub fn result_code(&self) -> Option<&'static str> {
+        let code = match self {
+            PlayType::Groundout => "GO",
+            PlayType::BuntGroundout => "BGO",
+            PlayType::Strikeout => "K",
+            PlayType::Lineout => "LO",
+            PlayType::BuntLineout => "BLO",
+            PlayType::Flyout => "FO",
+            PlayType::PopOut => "PO",
+            PlayType::BuntPopOut => "BPO",
+            PlayType::Forceout => "FRC",
+            PlayType::FieldersChoiceOut => "FCO",
+            PlayType::DoublePlay | PlayType::RunnerDoublePlay => "DP",
+            PlayType::TriplePlay | PlayType::RunnerTriplePlay => "TP",
+            PlayType::GroundedIntoDoublePlay => "GIDP",
+            PlayType::StrikeoutDoublePlay => "KDP",
+            PlayType::BatterOut => "OUT",
+            PlayType::Single => "1B",
+            PlayType::Double => "2B",
+            PlayType::Triple => "3B",
+            PlayType::HomeRun => "HR",
+            PlayType::Walk => "BB",
+            PlayType::IntentWalk => "IBB",
+            PlayType::HitByPitch => "HBP",
+            PlayType::FieldersChoice => "FC",
+            PlayType::CatcherInterference => "CI",
+            PlayType::SacFly => "SF",
+            PlayType::SacFlyDoublePlay => "SFDP",
+            PlayType::SacBunt => "SH",
+            PlayType::SacBuntDoublePlay => "SHDP",
+            PlayType::FieldError => "E",
+            _ => return None,
+        };
+
+        Some(code)
+    }
+
     pub fn requires_pitcher(&self) -> bool {
         matches!(
             self,
@@ -674,10 +1422,22 @@ impl PlayType {
             PlayType::SacFlyDoublePlay |
             PlayType::SacBunt |
             PlayType::SacBuntDoublePlay |
-            PlayType::FieldError
+            PlayType::FieldError |
+            PlayType::DefensiveSwitch |
+            PlayType::DefensiveSub
         )
     }
 
+    /// Whether the play names the number of a position, with `[POSITION]`.
+    pub fn requires_position(&self) -> bool {
+        matches!(self, PlayType::DefensiveSwitch | PlayType::DefensiveSub)
+    }
+
+    /// Whether the play may have no movements, with nothing after its `[MOVEMENTS]` tag.
+    pub fn allows_no_movements(&self) -> bool {
+        matches!(self, PlayType::DefensiveSwitch | PlayType::DefensiveSub)
+    }
+
     pub fn requires_runner(&self) -> bool {
         matches!(
             self,
@@ -702,43 +1462,110 @@ impl PlayType {
             PlayType::SacFlyDoublePlay
         )
     }
+
+    /// Whether the play may name the fielder charged with an error, with an optional `[ERROR_BY]` tag.
+    pub fn allows_error_by(&self) -> bool {
+        matches!(
+            self,
+            PlayType::Error |
+            PlayType::FieldError
+        )
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Movement {
-    pub runner: String,
+    #[pyo3(get)]
+    pub runner: SmallString,
+    #[pyo3(get, name = "from_base")]
     pub from: Base,
+    #[pyo3(get, name = "to_base")]
     pub to: Base,
+    #[pyo3(get)]
     pub out: bool,
+    /// Whether the runner advanced because of an error, written `[on error]`.
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub on_error: bool,
+    /// Where the runner was put out, if written `[out at N]`, which may not be the base they were trying to reach.
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out_at: Option<Base>,
+    /// The fielding positions credited with the out, in the order they handled the ball, e.g. `[6, 4]` for `[out 6-4]`.
+    /// The last fielder made the putout and the others have assists.
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub credits: Vec<u8>,
+}
+
+#[pymethods]
+impl Movement {
+    /// Return the fielding position credited with the putout, if credits were written.
+    pub fn putout(&self) -> Option<u8> {
+        self.credits.last().copied()
+    }
+
+    /// Return the fielding positions credited with assists.
+    pub fn assists(&self) -> Vec<u8> {
+        self.credits.split_last().map_or(Vec::new(), |(_, assists)| assists.to_vec())
+    }
+
+    /// The movement as it's written in a play, e.g. `Movement(Person A 1 -> 2 [out 6-4])`.
+    fn __repr__(&self) -> String {
+        format!("Movement({})", self.to_string())
+    }
 }
 
 impl ToString for Movement {
     fn to_string(&self) -> String {
         format!(
-            "{} {} -> {}{}",
+            "{} {} -> {}{}{}",
             self.runner,
             self.from.to_string(),
             self.to.to_string(),
-            if self.out { " [out]" } else { "" }
+            if self.on_error { " [on error]" } else { "" },
+            if self.out {
+                let mut out = "[out".to_string();
+                if let Some(base) = self.out_at {
+                    out.push_str(&format!(" at {}", base.to_string()));
+                }
+                if !self.credits.is_empty() {
+                    let credits = self.credits.iter().map(|position| position.to_string()).collect::<Vec<_>>();
+                    out.push_str(&format!(" {}", credits.join("-")));
+                }
+                format!(" {}]", out)
+            } else {
+                String::new()
+            }
         )
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct MovementBuilder {
-    runner: Option<String>,
+    runner: Option<SmallString>,
     from: Option<Base>,
     to: Option<Base>,
     out: bool,
+    on_error: bool,
+    out_at: Option<Base>,
+    credits: Vec<u8>,
+}
+
+impl Default for MovementBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl MovementBuilder {
     pub fn new() -> Self {
-        Self { runner: None, from: None, to: None, out: false }
+        Self { runner: None, from: None, to: None, out: false, on_error: false, out_at: None, credits: Vec::new() }
     }
 
     pub fn set_runner(&mut self, runner: String) -> &mut Self {
-        self.runner = Some(runner);
+        self.runner = Some(runner.into());
         self
     }
 
@@ -757,41 +1584,162 @@ impl MovementBuilder {
         self
     }
 
+    pub fn set_out_at(&mut self, base: Base) -> &mut Self {
+        self.out = true;
+        self.out_at = Some(base);
+        self
+    }
+
+    pub fn set_credits(&mut self, credits: Vec<u8>) -> &mut Self {
+        self.credits = credits;
+        self
+    }
+
+    pub fn set_on_error(&mut self) -> &mut Self {
+        self.on_error = true;
+        self
+    }
+
     pub fn build(&self) -> Result<Movement, String> {
         Ok(Movement {
             runner: self.runner.clone().ok_or("Runner is required, not set")?,
             from: self.from.clone().ok_or("From is required, not set")?,
             to: self.to.clone().ok_or("To is required, not set")?,
             out: self.out,
+            on_error: self.on_error,
+            out_at: self.out_at,
+            credits: self.credits.clone(),
         })
     }
 }
 
-#[derive(Clone, Debug)]
+/// A `[TAG] value` entry with a tag the parser doesn't recognise, kept when parsing with unknown tags allowed.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Extension {
+    #[pyo3(get)]
+    pub tag: String,
+    #[pyo3(get)]
+    pub value: String,
+}
+
+#[pyclass]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Play {
+    #[pyo3(get)]
     pub inning: Inning,
+    #[pyo3(get, name = "content")]
     pub play_content: PlayContent,
+    // as written, including every hop of a runner who moves more than once
+    #[pyo3(get)]
     pub movements: Vec<Movement>,
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<Extension>,
+    /// Custom `key=value` annotations from the play's `[META]` trailer, e.g. a model's confidence.
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+    /// The original call, if this play corrects one that was overturned on review. Only the corrected play counts
+    /// towards the game state.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overturned: Option<Box<Play>>,
+    /// The byte offsets of the start and end of the play in the parser's input, if it was parsed. Not part of the
+    /// play's content, so it isn't serialized.
+    #[serde(skip)]
+    pub source_span: Option<(usize, usize)>,
 }
 
-pub struct PlayBuilder {
-    pub inning: Option<Inning>,
-    pub play_type: Option<PlayType>,
-    pub base: Option<Base>,
-    pub batter: Option<String>,
-    pub pitcher: Option<String>,
-    pub catcher: Option<String>,
-    pub fielders: Vec<String>,
-    pub runner: Option<String>,
-    pub scoring_runner: Option<String>,
-    pub movement_builder: MovementBuilder,
-    pub movements: Vec<Movement>,
-}
+#[pymethods]
+impl Play {
+    /// Allow `match play: case Play(inning, PlayContent.Single(batter=b), movements):`.
+    #[classattr]
+    fn __match_args__() -> (&'static str, &'static str, &'static str) {
+        ("inning", "content", "movements")
+    }
 
-impl PlayBuilder {
-    pub fn new() -> Self {
-        Self {
-            inning: None,
+    #[getter]
+    fn play_type(&self) -> String {
+        self.play_content.play_type().to_string()
+    }
+
+    /// The play type as a `PlayType`, to compare without strings.
+    #[getter]
+    fn play_type_enum(&self) -> PlayType {
+        self.play_content.play_type()
+    }
+
+    /// The original call this play replaced on review, if any.
+    #[getter]
+    fn overturned(&self) -> Option<Play> {
+        self.overturned.as_deref().cloned()
+    }
+
+    /// Return the `(start, end)` byte offsets of the play in the text it was parsed from, e.g. to slice out the play's
+    /// text with `text.encode()[start:end]`, or `None` if it wasn't parsed.
+    #[pyo3(name = "source_span")]
+    fn py_source_span(&self) -> Option<(usize, usize)> {
+        self.source_span
+    }
+
+    /// Return the first fielder listed, who fielded the ball first, if the play has fielders.
+    pub fn primary_fielder(&self) -> Option<String> {
+        self.play_content.fielders().first().map(|fielder| fielder.to_string())
+    }
+
+    /// Return the position number of each fielder in the given alignment, in the order they're listed, or `None` for a
+    /// fielder who isn't in it.
+    pub fn fielder_positions(&self, alignment: &DefensiveAlignment) -> Vec<Option<u8>> {
+        self.play_content.fielders().iter().map(|fielder| alignment.position_of(fielder)).collect()
+    }
+
+    /// Return the movements with each runner's hops joined into one movement, as the game state sees them.
+    fn simplified_movements(&self) -> Vec<Movement> {
+        RunnerPositions::empty().simplify_movements(&self.movements)
+    }
+
+    /// Convert the play to nested dicts and lists, with keys in `"snake"` or `"camel"` case
+    /// and play content either `"tagged"` by its type or flattened with a `"type"` `"string"`.
+    #[pyo3(signature = (key_case="snake", enums="tagged"))]
+    fn as_dict(&self, py: Python<'_>, key_case: &str, enums: &str) -> PyResult<PyObject> {
+        let options = ExportOptions::from_strs(key_case, enums).map_err(PyValueError::new_err)?;
+        to_py_object(py, self, options)
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PlayBuilder {
+    pub inning: Option<Inning>,
+    pub play_type: Option<PlayType>,
+    pub base: Option<Base>,
+    pub batter: Option<SmallString>,
+    pub pitcher: Option<SmallString>,
+    pub catcher: Option<SmallString>,
+    pub fielders: Vec<SmallString>,
+    pub runner: Option<SmallString>,
+    pub scoring_runner: Option<SmallString>,
+    pub error_by: Option<SmallString>,
+    pub position: Option<u8>,
+    pub movement_builder: MovementBuilder,
+    pub movements: Vec<Movement>,
+    pub extensions: Vec<Extension>,
+    pub meta: BTreeMap<String, String>,
+    // whether this play is the original call of a play overturned on review
+    pub overturned_call: bool,
+    pub source_span: Option<(usize, usize)>,
+}
+
+impl Default for PlayBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PlayBuilder {
+    pub fn new() -> Self {
+        Self {
+            inning: None,
             play_type: None,
             base: None,
             batter: None,
@@ -800,8 +1748,14 @@ impl PlayBuilder {
             fielders: Vec::new(),
             runner: None,
             scoring_runner: None,
+            error_by: None,
+            position: None,
             movement_builder: MovementBuilder::new(),
             movements: Vec::new(),
+            extensions: Vec::new(),
+            meta: BTreeMap::new(),
+            overturned_call: false,
+            source_span: None,
         }
     }
 
@@ -821,32 +1775,47 @@ impl PlayBuilder {
     }
 
     pub fn set_batter(&mut self, batter: String) -> &mut Self {
-        self.batter = Some(batter);
+        self.batter = Some(batter.into());
         self
     }
 
     pub fn set_pitcher(&mut self, pitcher: String) -> &mut Self {
-        self.pitcher = Some(pitcher);
+        self.pitcher = Some(pitcher.into());
         self
     }
 
     pub fn set_catcher(&mut self, catcher: String) -> &mut Self {
-        self.catcher = Some(catcher);
+        self.catcher = Some(catcher.into());
         self
     }
 
     pub fn add_fielder(&mut self, fielder: String) -> &mut Self {
-        self.fielders.push(fielder);
+        self.fielders.push(fielder.into());
         self
     }
 
     pub fn set_runner(&mut self, runner: String) -> &mut Self {
-        self.runner = Some(runner);
+        self.runner = Some(runner.into());
         self
     }
 
     pub fn set_scoring_runner(&mut self, scoring_runner: String) -> &mut Self {
-        self.scoring_runner = Some(scoring_runner);
+        self.scoring_runner = Some(scoring_runner.into());
+        self
+    }
+
+    pub fn set_error_by(&mut self, error_by: String) -> &mut Self {
+        self.error_by = Some(error_by.into());
+        self
+    }
+
+    pub fn set_position(&mut self, position: u8) -> &mut Self {
+        self.position = Some(position);
+        self
+    }
+
+    pub fn add_meta(&mut self, key: String, value: String) -> &mut Self {
+        self.meta.insert(key, value);
         self
     }
 
@@ -971,10 +1940,12 @@ impl PlayBuilder {
             Some(PlayType::RunnerOut) => PlayContent::RunnerOut {
                 fielders: self.fielders.clone(),
                 runner: self.runner.clone()?,
+                base: self.base,
             },
             Some(PlayType::FieldOut) => PlayContent::FieldOut {
                 fielders: self.fielders.clone(),
                 runner: self.runner.clone()?,
+                base: self.base,
             },
             Some(PlayType::BatterOut) => PlayContent::BatterOut {
                 batter: self.batter.clone()?,
@@ -990,6 +1961,7 @@ impl PlayBuilder {
             Some(PlayType::Error) => PlayContent::Error {
                 pitcher: self.pitcher.clone()?,
                 catcher: self.catcher.clone()?,
+                error_by: self.error_by.clone(),
             },
             Some(PlayType::Single) => PlayContent::Single {
                 batter: self.batter.clone()?,
@@ -1031,7 +2003,7 @@ impl PlayBuilder {
             },
             Some(PlayType::StolenBase) => PlayContent::StolenBase {
                 base: self.base.clone()?,
-                scoring_runner: self.scoring_runner.clone()?,
+                runner: self.runner.clone()?,
             },
             Some(PlayType::SacFly) => PlayContent::SacFly {
                 batter: self.batter.clone()?,
@@ -1049,20 +2021,29 @@ impl PlayBuilder {
                 batter: self.batter.clone()?,
                 pitcher: self.pitcher.clone()?,
                 fielders: self.fielders.clone(),
-                runner: self.scoring_runner.clone()?,
+                runner: self.runner.clone()?,
             },
             Some(PlayType::SacBuntDoublePlay) => PlayContent::SacBuntDoublePlay {
                 batter: self.batter.clone()?,
                 pitcher: self.pitcher.clone()?,
                 fielders: self.fielders.clone(),
-                runner: self.scoring_runner.clone()?,
+                runner: self.runner.clone()?,
             },
             Some(PlayType::FieldError) => PlayContent::FieldError {
                 batter: self.batter.clone()?,
                 pitcher: self.pitcher.clone()?,
                 fielders: self.fielders.clone(),
+                error_by: self.error_by.clone(),
+            },
+            Some(PlayType::DefensiveSwitch) => PlayContent::DefensiveSwitch {
+                fielders: self.fielders.clone(),
+                position: self.position?,
             },
-            Some(PlayType::GameAdvisory) => PlayContent::GameAdvisory,
+            Some(PlayType::DefensiveSub) => PlayContent::DefensiveSub {
+                fielders: self.fielders.clone(),
+                position: self.position?,
+            },
+            Some(PlayType::GameAdvisory) => PlayContent::GameAdvisory(),
             None => return None,
         };
 
@@ -1070,35 +2051,449 @@ impl PlayBuilder {
             inning: self.inning.clone()?,
             play_content,
             movements: self.movements.clone(),
+            extensions: self.extensions.clone(),
+            meta: self.meta.clone(),
+            overturned: None,
+            source_span: self.source_span,
         })
     }
 }
 
+#[pymethods]
+impl PlayBuilder {
+    #[new]
+    fn py_new() -> Self {
+        Self::new()
+    }
+
+    #[pyo3(name = "set_inning")]
+    fn py_set_inning(&mut self, number: u64, top_bottom: &str) -> PyResult<()> {
+        let top_bottom = top_bottom.parse::<TopBottom>().map_err(PyValueError::new_err)?;
+        self.set_inning(Inning { number, top_bottom });
+
+        Ok(())
+    }
+
+    #[pyo3(name = "set_play_type")]
+    fn py_set_play_type(&mut self, play_type: &str) -> PyResult<()> {
+        let play_type = play_type.parse::<PlayType>().map_err(PyValueError::new_err)?;
+        self.set_play_type(play_type);
+
+        Ok(())
+    }
+
+    #[pyo3(name = "set_base")]
+    fn py_set_base(&mut self, base: &str) -> PyResult<()> {
+        let base = base.parse::<Base>().map_err(PyValueError::new_err)?;
+        self.set_base(base);
+
+        Ok(())
+    }
+
+    #[pyo3(name = "set_batter")]
+    fn py_set_batter(&mut self, batter: String) -> PyResult<()> {
+        self.set_batter(validate_player_name(batter)?);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_pitcher")]
+    fn py_set_pitcher(&mut self, pitcher: String) -> PyResult<()> {
+        self.set_pitcher(validate_player_name(pitcher)?);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_catcher")]
+    fn py_set_catcher(&mut self, catcher: String) -> PyResult<()> {
+        self.set_catcher(validate_player_name(catcher)?);
+        Ok(())
+    }
+
+    #[pyo3(name = "add_fielder")]
+    fn py_add_fielder(&mut self, fielder: String) -> PyResult<()> {
+        self.add_fielder(validate_player_name(fielder)?);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_runner")]
+    fn py_set_runner(&mut self, runner: String) -> PyResult<()> {
+        self.set_runner(validate_player_name(runner)?);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_scoring_runner")]
+    fn py_set_scoring_runner(&mut self, scoring_runner: String) -> PyResult<()> {
+        self.set_scoring_runner(validate_player_name(scoring_runner)?);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_error_by")]
+    fn py_set_error_by(&mut self, error_by: String) -> PyResult<()> {
+        self.set_error_by(validate_player_name(error_by)?);
+        Ok(())
+    }
+
+    /// Set the number of the position taken over in a defensive switch or substitution, from 1 for the pitcher to 9 for
+    /// right field.
+    #[pyo3(name = "set_position")]
+    fn py_set_position(&mut self, position: u8) -> PyResult<()> {
+        if !(1..=9).contains(&position) {
+            return Err(PyValueError::new_err(format!("Invalid position: {}", position)));
+        }
+        self.set_position(position);
+
+        Ok(())
+    }
+
+    /// Add a runner movement to the play, e.g. `add_movement("Player", "1", "3")`.
+    #[pyo3(signature = (runner, from, to, out=false))]
+    fn add_movement(&mut self, runner: String, from: &str, to: &str, out: bool) -> PyResult<()> {
+        let runner = validate_player_name(runner)?;
+        let from = from.parse::<Base>().map_err(PyValueError::new_err)?;
+        let to = to.parse::<Base>().map_err(PyValueError::new_err)?;
+
+        self.reset_movement_builder();
+        self.movement_builder.set_runner(runner).set_from(from).set_to(to);
+        if out {
+            self.movement_builder.set_out();
+        }
+        self.build_movement().map_err(PyValueError::new_err)?;
+
+        Ok(())
+    }
+}
+
+/// Check that a name would be accepted by the parser.
+fn validate_player_name(name: String) -> PyResult<String> {
+    if section_matches(&PLAYER_NAME_REGEX, &name) {
+        Ok(name)
+    } else {
+        Err(PyValueError::new_err(format!("Invalid player name: {}", name)))
+    }
+}
+
 #[pyclass]
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Game {
-    context: Context,
-    home_team: Team,
-    away_team: Team,
-    plays: Vec<Play>,
+    #[pyo3(get)]
+    pub context: Context,
+    #[pyo3(get)]
+    pub home_team: Team,
+    #[pyo3(get)]
+    pub away_team: Team,
+    #[pyo3(get)]
+    pub plays: Vec<Play>,
+    #[pyo3(get)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<Extension>,
+}
+
+impl Game {
+    /// Return the names of each team's pinch runners.
+    pub fn pinch_runners(&self) -> PinchRunners {
+        PinchRunners {
+            home: pinch_runner_names(&self.home_team.players),
+            away: pinch_runner_names(&self.away_team.players),
+        }
+    }
+
+    /// Return the state before the first play, with each team's defensive alignment taken from its roster.
+    pub fn initial_state(&self) -> LiveGameState {
+        let mut state = LiveGameState::new();
+        state.set_rosters(&self.home_team.players, &self.away_team.players);
+        state
+    }
+
+    /// Replay every play, returning the state at the end of the game.
+    pub fn replay(&self) -> Result<LiveGameState, String> {
+        self.initial_state().replay_from(&self.plays, &self.pinch_runners())
+    }
+
+    /// Return the home team's final score minus the away team's.
+    pub fn run_differential(&self) -> Result<i64, String> {
+        let state = self.replay()?;
+        Ok(state.home_team_score as i64 - state.away_team_score as i64)
+    }
+
+    /// Return whether the home team won on the last play of the game, in the bottom of the ninth inning or later,
+    /// having not been ahead before it.
+    pub fn was_walk_off(&self) -> Result<bool, String> {
+        // advisories after the final play don't change the score
+        let Some(last) = self.plays.iter().rposition(|play| play.play_content.play_type() != PlayType::GameAdvisory) else {
+            return Ok(false);
+        };
+        let inning = self.plays[last].inning;
+        if inning.top_bottom != TopBottom::Bottom || inning.number < 9 {
+            return Ok(false);
+        }
+
+        let pinch_runners = self.pinch_runners();
+        let before = LiveGameState::replay(&self.plays[..last], &pinch_runners)?;
+        let after = LiveGameState::replay(&self.plays[..=last], &pinch_runners)?;
+        Ok(before.home_team_score <= before.away_team_score && after.home_team_score > after.away_team_score)
+    }
+
+    /// Return whether the game went past the ninth inning.
+    pub fn extra_innings(&self) -> bool {
+        self.plays.iter().any(|play| play.inning.number > 9)
+    }
+
+    /// Return the results of a batter's plate appearances in order, as scorecard abbreviations such as `1B` or `K`.
+    pub fn batter_results(&self, name: &str) -> Vec<&'static str> {
+        self.plays.iter()
+            .filter(|play| play.play_content.batter().is_some_and(|batter| batter == name))
+            .filter_map(|play| play.play_content.play_type().result_code())
+            .collect()
+    }
+
+    /// Return the plays a player is named in, in any role or as a runner in a movement.
+    pub fn plays_by_player(&self, name: &str) -> Vec<&Play> {
+        self.plays.iter()
+            .filter(|play| play.play_content.involves(name) || play.movements.iter().any(|movement| movement.runner == name))
+            .collect()
+    }
+
+    /// Return the plays of a type, e.g. every home run.
+    pub fn plays_of_type(&self, play_type: PlayType) -> Vec<&Play> {
+        self.plays.iter().filter(|play| play.play_content.play_type() == play_type).collect()
+    }
+
+    /// Return the plays of an inning, or of one half of it, found by binary search since plays are in game order.
+    pub fn plays_in_inning(&self, number: u64, top_bottom: Option<TopBottom>) -> &[Play] {
+        // top halves sort before bottom halves
+        let key = |inning: Inning| (inning.number, inning.top_bottom == TopBottom::Bottom);
+        let (start, end) = match top_bottom {
+            Some(top_bottom) => {
+                let half = key(Inning { number, top_bottom });
+                (
+                    self.plays.partition_point(|play| key(play.inning) < half),
+                    self.plays.partition_point(|play| key(play.inning) <= half),
+                )
+            },
+            None => (
+                self.plays.partition_point(|play| play.inning.number < number),
+                self.plays.partition_point(|play| play.inning.number <= number),
+            ),
+        };
+
+        &self.plays[start..end]
+    }
+
+    /// Return a stable hash of the game's content as 16 hex digits, for finding duplicate games. Games parsed from
+    /// text that differs only in whitespace, the order of each play's movements or how each runner's hops are split
+    /// have the same digest, so a game and its canonical text do too.
+    pub fn digest(&self) -> String {
+        let mut game = self.clone();
+        for play in &mut game.plays {
+            simplify_movements(play);
+        }
+
+        // the same content always serializes to the same JSON, and the hash doesn't depend on the platform or release
+        let json = serde_json::to_string(&game).unwrap();
+        let hash = json.bytes().fold(0xcbf29ce484222325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3));
+        format!("{:016x}", hash)
+    }
+}
+
+/// Join each runner's hops in a play into one movement, sorted by the base they start from and then by runner, as the
+/// canonical text writes them, and do the same for any call the play overturned.
+fn simplify_movements(play: &mut Play) {
+    play.movements = play.simplified_movements();
+    if let Some(overturned) = &mut play.overturned {
+        simplify_movements(overturned);
+    }
+}
+
+#[pymethods]
+impl Game {
+    /// Convert the game, including its teams and plays, to nested dicts and lists.
+    /// Takes the same options as `Play.as_dict`.
+    #[pyo3(signature = (key_case="snake", enums="tagged"))]
+    fn to_dict(&self, py: Python<'_>, key_case: &str, enums: &str) -> PyResult<PyObject> {
+        let options = ExportOptions::from_strs(key_case, enums).map_err(PyValueError::new_err)?;
+        to_py_object(py, self, options)
+    }
+
+    #[pyo3(name = "decisions")]
+    fn py_decisions(&self) -> PyResult<Decisions> {
+        decisions(self).map_err(PyValueError::new_err)
+    }
+
+    #[pyo3(name = "run_differential")]
+    fn py_run_differential(&self) -> PyResult<i64> {
+        self.run_differential().map_err(PyValueError::new_err)
+    }
+
+    #[pyo3(name = "was_walk_off")]
+    fn py_was_walk_off(&self) -> PyResult<bool> {
+        self.was_walk_off().map_err(PyValueError::new_err)
+    }
+
+    #[pyo3(name = "extra_innings")]
+    fn py_extra_innings(&self) -> bool {
+        self.extra_innings()
+    }
+
+    #[pyo3(name = "batter_results")]
+    fn py_batter_results(&self, name: &str) -> Vec<&'static str> {
+        self.batter_results(name)
+    }
+
+    /// Return the plays of an inning, or only its `"top"` or `"bottom"` half if `half` is given.
+    #[pyo3(name = "plays_in_inning", signature = (number, half=None))]
+    fn py_plays_in_inning(&self, number: u64, half: Option<&str>) -> PyResult<Vec<Play>> {
+        let top_bottom = half.map(|half| half.parse::<TopBottom>()).transpose().map_err(PyValueError::new_err)?;
+        Ok(self.plays_in_inning(number, top_bottom).to_vec())
+    }
+
+    /// Return the plays a player is named in, as batter, pitcher, fielder, runner or in any other role.
+    #[pyo3(name = "plays_by_player")]
+    fn py_plays_by_player(&self, name: &str) -> Vec<Play> {
+        self.plays_by_player(name).into_iter().cloned().collect()
+    }
+
+    /// Return the plays of a `PlayType`, e.g. `game.plays_of_type(PlayType.HomeRun)`.
+    #[pyo3(name = "plays_of_type")]
+    fn py_plays_of_type(&self, play_type: PlayType) -> Vec<Play> {
+        self.plays_of_type(play_type).into_iter().cloned().collect()
+    }
+
+    #[pyo3(name = "digest")]
+    fn py_digest(&self) -> String {
+        self.digest()
+    }
+
+    /// Write the game as text that parses back to the same game, with each runner's movements as recorded. With
+    /// `join_hops=True`, each runner's hops in a play are joined into one movement, as `canonicalize` writes them.
+    #[pyo3(name = "to_text", signature = (sort_fielders=false, join_hops=false))]
+    fn py_to_text(&self, sort_fielders: bool, join_hops: bool) -> String {
+        to_text(self, sort_fielders, join_hops)
+    }
+
+    /// Convert the game to a JSON string. Takes the same options as `Play.as_dict`.
+    #[pyo3(signature = (key_case="snake", enums="tagged"))]
+    fn to_json(&self, key_case: &str, enums: &str) -> PyResult<String> {
+        let options = ExportOptions::from_strs(key_case, enums).map_err(PyValueError::new_err)?;
+        let value = export_value(self, options).map_err(PyValueError::new_err)?;
+        Ok(value.to_string())
+    }
+
+    /// Load a game from JSON written by `to_json` with the default options, without parsing its text again.
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        serde_json::from_str(json).map_err(|e| PyValueError::new_err(format!("Invalid game JSON: {}", e)))
+    }
+
+    fn __len__(&self) -> usize {
+        self.plays.len()
+    }
+
+    /// Return the play at `index`, counting from the end if it's negative.
+    fn __getitem__(&self, index: isize) -> PyResult<Play> {
+        let position = if index < 0 { index + self.plays.len() as isize } else { index };
+        usize::try_from(position).ok()
+            .and_then(|position| self.plays.get(position))
+            .cloned()
+            .ok_or_else(|| PyIndexError::new_err(format!("Play index out of range: {}", index)))
+    }
+
+    /// Iterate over the plays in order, so `for play in game:` works.
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, self.plays.clone())?.try_iter()
+    }
+
+    /// Convert the game to a subset of the MLB Stats API live feed schema, as nested dicts and lists, for tools written
+    /// against the official feed.
+    fn to_gumbo(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let value = to_gumbo(self).map_err(PyValueError::new_err)?;
+        json_to_py_object(py, &value)
+    }
+
+    /// Convert the game to a live feed JSON string, like `to_gumbo`.
+    fn to_gumbo_json(&self) -> PyResult<String> {
+        Ok(to_gumbo(self).map_err(PyValueError::new_err)?.to_string())
+    }
+
+    /// Return the runs scored in each half-inning, in order, as `(inning, runs)` pairs, the same as
+    /// `Parser.inning_scores()` once the game has been parsed.
+    #[pyo3(name = "inning_scores")]
+    fn py_inning_scores(&self) -> PyResult<Vec<(Inning, u64)>> {
+        Ok(self.replay().map_err(PyValueError::new_err)?.inning_scores)
+    }
+
+    /// Return a summary of each half-inning, in order, with its runs, hits, errors and runners left on base.
+    #[pyo3(name = "half_innings")]
+    fn py_half_innings(&self) -> PyResult<Vec<HalfInningSummary>> {
+        half_innings(self).map_err(PyValueError::new_err)
+    }
+
+    /// Return a row for each play, with the score, outs and runners on base before and after it.
+    #[pyo3(name = "play_by_play")]
+    fn py_play_by_play(&self) -> PyResult<Vec<PlayByPlayRow>> {
+        play_by_play(self).map_err(PyValueError::new_err)
+    }
+
+    /// Render the play-by-play rows as a plain text table, for reading through a game.
+    fn play_by_play_table(&self) -> PyResult<String> {
+        let rows = play_by_play(self).map_err(PyValueError::new_err)?;
+        Ok(render_play_by_play(&rows))
+    }
+
+    /// Convert the game to a Retrosheet event file, with a `play` record for each play.
+    #[pyo3(name = "to_retrosheet")]
+    fn py_to_retrosheet(&self) -> String {
+        to_retrosheet(self)
+    }
+
+    /// Convert the game to an `IndexedGame`, which stores each name only once.
+    fn to_indexed(&self) -> IndexedGame {
+        IndexedGame::from(self)
+    }
 }
 
+fn pinch_runner_names(players: &[Player]) -> Vec<SmallString> {
+    players.iter()
+        .filter(|player| player.position == Position::PinchRunner)
+        .map(|player| player.name.clone())
+        .collect()
+}
+
+#[pyclass]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameBuilder {
     pub game_pk: Option<u64>,
     pub date: Option<String>,
-    pub venue: Option<String>,
-    pub weather_condition: Option<String>,
-    pub weather_temperature: Option<u64>,
-    pub weather_wind_speed: Option<u64>,
-
+    pub venue: Option<SmallString>,
+    pub weather_condition: Option<WeatherCondition>,
+    pub weather_temperature: Option<f64>,
+    pub weather_temperature_unit: Option<TemperatureUnit>,
+    pub weather_wind_speed: Option<f64>,
+    pub weather_wind_speed_unit: Option<SpeedUnit>,
+
+    // rosters are shared between clones of the builder until one of them changes, and plays are shared chunk by chunk
     pub home_team_id: Option<u64>,
-    pub home_team_players: Vec<Player>,
+    pub home_team_players: Arc<Vec<Player>>,
 
     pub away_team_id: Option<u64>,
-    pub away_team_players: Vec<Player>,
+    pub away_team_players: Arc<Vec<Player>>,
 
+    // an in-progress play can't be resumed without its raw text, so it isn't saved
+    #[serde(skip, default = "PlayBuilder::new")]
     pub play_builder: PlayBuilder,
-    pub plays: Vec<Play>,
+    pub plays: PlayList,
+    #[serde(default)]
+    pub extensions: Vec<Extension>,
+    // an overturned call, waiting for the play that corrects it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overturned_call: Option<Play>,
+    // whether `[GAME_START]` has been parsed, so state exported partway through the away roster resumes there
+    #[serde(default)]
+    pub game_started: bool,
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameBuilder {
@@ -1109,13 +2504,18 @@ impl GameBuilder {
             venue: None,
             weather_condition: None,
             weather_temperature: None,
+            weather_temperature_unit: None,
             weather_wind_speed: None,
+            weather_wind_speed_unit: None,
             home_team_id: None,
-            home_team_players: Vec::new(),
+            home_team_players: Arc::new(Vec::new()),
             away_team_id: None,
-            away_team_players: Vec::new(),
+            away_team_players: Arc::new(Vec::new()),
             play_builder: PlayBuilder::new(),
-            plays: Vec::new(),
+            plays: PlayList::new(),
+            extensions: Vec::new(),
+            overturned_call: None,
+            game_started: false,
         }
     }
 
@@ -1131,14 +2531,23 @@ impl GameBuilder {
     }
 
     pub fn set_venue(&mut self, venue: String) -> &mut Self {
-        self.venue = Some(venue);
+        self.venue = Some(venue.into());
         self
     }
 
-    pub fn set_weather(&mut self, condition: String, temperature: u64, wind_speed: u64) -> &mut Self {
+    pub fn set_weather(
+        &mut self,
+        condition: WeatherCondition,
+        temperature: f64,
+        temperature_unit: TemperatureUnit,
+        wind_speed: f64,
+        wind_speed_unit: SpeedUnit,
+    ) -> &mut Self {
         self.weather_condition = Some(condition);
         self.weather_temperature = Some(temperature);
+        self.weather_temperature_unit = Some(temperature_unit);
         self.weather_wind_speed = Some(wind_speed);
+        self.weather_wind_speed_unit = Some(wind_speed_unit);
         self
     }
 
@@ -1149,7 +2558,7 @@ impl GameBuilder {
     }
 
     pub fn add_home_team_player(&mut self, player: Player) -> &mut Self {
-        self.home_team_players.push(player);
+        Arc::make_mut(&mut self.home_team_players).push(player);
         self
     }
 
@@ -1160,7 +2569,7 @@ impl GameBuilder {
     }
 
     pub fn add_away_team_player(&mut self, player: Player) -> &mut Self {
-        self.away_team_players.push(player);
+        Arc::make_mut(&mut self.away_team_players).push(player);
         self
     }
 
@@ -1170,8 +2579,20 @@ impl GameBuilder {
         self
     }
 
+    pub fn push_play(&mut self, play: Play) -> &mut Self {
+        self.plays.push(play);
+        self
+    }
+
+    /// Build the play in progress. An overturned call is held back until the play that corrects it is built.
     pub fn build_play(&mut self) -> Option<&mut Self> {
-        self.plays.push(self.play_builder.build()?);
+        let mut play = self.play_builder.build()?;
+        play.overturned = self.overturned_call.take().map(Box::new);
+        if self.play_builder.overturned_call {
+            self.overturned_call = Some(play);
+        } else {
+            self.push_play(play);
+        }
         self.reset_play_builder();
 
         Some(self)
@@ -1185,7 +2606,9 @@ impl GameBuilder {
         let venue = self.venue.clone()?;
         let weather_condition = self.weather_condition.clone()?;
         let weather_temperature = self.weather_temperature?;
+        let weather_temperature_unit = self.weather_temperature_unit?;
         let weather_wind_speed = self.weather_wind_speed?;
+        let weather_wind_speed_unit = self.weather_wind_speed_unit?;
         let home_team_id = self.home_team_id?;
         let away_team_id = self.away_team_id?;
 
@@ -1197,19 +2620,21 @@ impl GameBuilder {
             weather: Weather {
                 condition: weather_condition,
                 temperature: weather_temperature,
+                temperature_unit: weather_temperature_unit,
                 wind_speed: weather_wind_speed,
+                wind_speed_unit: weather_wind_speed_unit,
             },
         };
 
         // create teams
         let home_team = Team {
             team_id: home_team_id,
-            players: self.home_team_players.clone(),
+            players: self.home_team_players.to_vec(),
         };
 
         let away_team = Team {
             team_id: away_team_id,
-            players: self.away_team_players.clone(),
+            players: self.away_team_players.to_vec(),
         };
 
         // return the fully constructed Game
@@ -1217,7 +2642,522 @@ impl GameBuilder {
             context,
             home_team,
             away_team,
-            plays: self.plays.clone(),
+            plays: self.plays.to_vec(),
+            extensions: self.extensions.clone(),
         })
     }
+    /// Return the names of each team's pinch runners.
+    pub fn pinch_runners(&self) -> PinchRunners {
+        PinchRunners {
+            home: pinch_runner_names(&self.home_team_players),
+            away: pinch_runner_names(&self.away_team_players),
+        }
+    }
+
+    /// Replay the plays from an empty state, checking them the same way the parser does.
+    pub fn replay_plays(&self) -> Result<LiveGameState, String> {
+        let mut state = LiveGameState::new();
+        state.set_rosters(&self.home_team_players, &self.away_team_players);
+        state.replay_from(&self.plays, &self.pinch_runners())
+    }
+}
+
+#[pymethods]
+impl GameBuilder {
+    #[new]
+    fn py_new() -> Self {
+        Self::new()
+    }
+
+    #[pyo3(name = "set_game_pk")]
+    fn py_set_game_pk(&mut self, game_pk: u64) -> PyResult<()> {
+        if !section_matches(&CONTEXT_SECTION_GAME_REGEX, &format!("[GAME] {}", game_pk)) {
+            return Err(PyValueError::new_err(format!("Invalid game pk: {}", game_pk)));
+        }
+
+        self.set_game_pk(game_pk);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_date")]
+    fn py_set_date(&mut self, date: String) -> PyResult<()> {
+        if !section_matches(&CONTEXT_SECTION_DATE_REGEX, &format!("[DATE] {}", date)) {
+            return Err(PyValueError::new_err(format!("Invalid date: {}", date)));
+        }
+
+        self.set_date(date);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_venue")]
+    fn py_set_venue(&mut self, venue: String) -> PyResult<()> {
+        if !section_matches(&CONTEXT_SECTION_VENUE_REGEX, &format!("[VENUE] {}", venue)) {
+            return Err(PyValueError::new_err(format!("Invalid venue: {}", venue)));
+        }
+
+        self.set_venue(venue);
+        Ok(())
+    }
+
+    /// Set the weather, e.g. `set_weather("Clear", -2.5, 12, temperature_unit="C", wind_speed_unit="kph")`.
+    #[pyo3(name = "set_weather", signature = (condition, temperature, wind_speed, temperature_unit="F", wind_speed_unit="mph"))]
+    fn py_set_weather(
+        &mut self,
+        condition: String,
+        temperature: f64,
+        wind_speed: f64,
+        temperature_unit: &str,
+        wind_speed_unit: &str,
+    ) -> PyResult<()> {
+        let temperature_unit = temperature_unit.parse::<TemperatureUnit>().map_err(PyValueError::new_err)?;
+        let wind_speed_unit = wind_speed_unit.parse::<SpeedUnit>().map_err(PyValueError::new_err)?;
+
+        let weather = format!(
+            "[WEATHER] {} {}{} {}{}",
+            condition,
+            temperature,
+            temperature_unit.to_string(),
+            wind_speed,
+            wind_speed_unit.to_string(),
+        );
+        if !section_matches(&CONTEXT_SECTION_WEATHER_REGEX, &weather) {
+            return Err(PyValueError::new_err(format!("Invalid weather: {}", weather)));
+        }
+
+        self.set_weather(condition.as_str().into(), temperature, temperature_unit, wind_speed, wind_speed_unit);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_home_team_id")]
+    fn py_set_home_team_id(&mut self, team_id: u64) -> PyResult<()> {
+        validate_team_id(team_id)?;
+        self.set_home_team_id(team_id);
+        Ok(())
+    }
+
+    /// Add a player to the home team, e.g. `add_home_team_player("PITCHER", "Player")`.
+    #[pyo3(name = "add_home_team_player")]
+    fn py_add_home_team_player(&mut self, position: &str, name: String) -> PyResult<()> {
+        let player = player_from_parts(position, name)?;
+        self.add_home_team_player(player);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_away_team_id")]
+    fn py_set_away_team_id(&mut self, team_id: u64) -> PyResult<()> {
+        validate_team_id(team_id)?;
+        self.set_away_team_id(team_id);
+        Ok(())
+    }
+
+    /// Add a player to the away team, e.g. `add_away_team_player("CATCHER", "Player")`.
+    #[pyo3(name = "add_away_team_player")]
+    fn py_add_away_team_player(&mut self, position: &str, name: String) -> PyResult<()> {
+        let player = player_from_parts(position, name)?;
+        self.add_away_team_player(player);
+        Ok(())
+    }
+
+    /// Build a play and add it to the game.
+    fn add_play(&mut self, play: PyRef<'_, PlayBuilder>) -> PyResult<()> {
+        let play_type = play.play_type.ok_or_else(|| PyValueError::new_err("Play type is required, not set"))?;
+        if play_type.requires_fielders() && play.fielders.is_empty() {
+            return Err(PyValueError::new_err(format!("{} play requires at least one fielder", play_type.to_string())));
+        }
+
+        let play = play.build().ok_or_else(|| PyValueError::new_err(format!(
+            "{} play is missing required fields",
+            play_type.to_string(),
+        )))?;
+        self.push_play(play);
+
+        Ok(())
+    }
+
+    /// Build the game, checking that every section is complete and the plays are consistent.
+    #[pyo3(name = "build")]
+    fn py_build(&self) -> PyResult<Game> {
+        let game = self.build().ok_or_else(|| PyValueError::new_err("Game is missing required context or team fields"))?;
+        self.replay_plays().map_err(PyValueError::new_err)?;
+
+        Ok(game)
+    }
+
+    /// Export the builder state as JSON, so it can be resumed elsewhere.
+    pub fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(self).map_err(|e| PyValueError::new_err(format!("Failed to export builder state: {}", e)))
+    }
+
+    /// Import builder state previously exported with `to_json`.
+    #[staticmethod]
+    pub fn from_json(json: &str) -> PyResult<Self> {
+        serde_json::from_str(json).map_err(|e| PyValueError::new_err(format!("Invalid builder state: {}", e)))
+    }
+}
+
+fn validate_team_id(team_id: u64) -> PyResult<()> {
+    if section_matches(&TEAM_SECTION_TEAM_REGEX, &format!("[TEAM] {}", team_id)) {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(format!("Invalid team id: {}", team_id)))
+    }
+}
+
+fn player_from_parts(position: &str, name: String) -> PyResult<Player> {
+    let position = position.parse::<Position>().map_err(PyValueError::new_err)?;
+    if !section_matches(&TEAM_SECTION_PLAYER_REGEX, &format!("[{}] {}", position.to_string(), name)) {
+        return Err(PyValueError::new_err(format!("Invalid player name: {}", name)));
+    }
+
+    Ok(Player { position, name: name.into() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_game_builder() -> GameBuilder {
+        let mut builder = GameBuilder::new();
+        builder.py_set_game_pk(1).unwrap();
+        builder.py_set_date("2024-04-01".to_string()).unwrap();
+        builder.py_set_venue("Example Park".to_string()).unwrap();
+        builder.py_set_weather("Clear".to_string(), 70.0, 5.0, "F", "mph").unwrap();
+        builder.py_set_home_team_id(1).unwrap();
+        builder.py_add_home_team_player("PITCHER", "Person A".to_string()).unwrap();
+        builder.py_set_away_team_id(2).unwrap();
+        builder.py_add_away_team_player("PITCHER", "Person C".to_string()).unwrap();
+
+        builder
+    }
+
+    fn single(batter: &str, movements: &[(&str, &str, &str)]) -> PlayBuilder {
+        let mut play = PlayBuilder::new();
+        play.py_set_inning(1, "top").unwrap();
+        play.py_set_play_type("Single").unwrap();
+        play.py_set_batter(batter.to_string()).unwrap();
+        play.py_set_pitcher("Person A".to_string()).unwrap();
+        for (runner, from, to) in movements {
+            play.add_movement(runner.to_string(), from, to, false).unwrap();
+        }
+
+        play
+    }
+
+    #[test]
+    fn convert_weather_units() {
+        let weather = Weather {
+            condition: WeatherCondition::Clear,
+            temperature: 50.0,
+            temperature_unit: TemperatureUnit::Fahrenheit,
+            wind_speed: 10.0,
+            wind_speed_unit: SpeedUnit::MilesPerHour,
+        };
+
+        let metric = weather.in_units(UnitSystem::Metric);
+        assert_eq!(metric.temperature, 10.0);
+        assert_eq!(metric.temperature_unit, TemperatureUnit::Celsius);
+        assert!((metric.wind_speed - 16.09344).abs() < 1e-9);
+
+        let imperial = metric.in_units(UnitSystem::Imperial);
+        assert_eq!(imperial.temperature, 50.0);
+        assert!((imperial.wind_speed - 10.0).abs() < 1e-9);
+        assert_eq!(imperial.wind_speed_unit, SpeedUnit::MilesPerHour);
+    }
+
+    #[test]
+    fn builder_rejects_invalid_values() {
+        let mut builder = GameBuilder::new();
+        assert!(builder.py_set_game_pk(1234567890).is_err());
+        assert!(builder.py_set_date("April 1st".to_string()).is_err());
+        assert!(builder.py_set_weather("Clear".to_string(), 1000.0, 5.0, "F", "mph").is_err());
+        assert!(builder.py_set_weather("Clear".to_string(), 70.0, -5.0, "F", "mph").is_err());
+        assert!(builder.py_set_weather("Clear".to_string(), 70.0, 5.0, "K", "mph").is_err());
+        assert!(builder.py_set_home_team_id(1000000).is_err());
+        assert!(builder.py_add_home_team_player("XX", "Person A".to_string()).is_err());
+        assert!(builder.py_add_home_team_player("PITCHER", "Person 1".to_string()).is_err());
+
+        let mut play = PlayBuilder::new();
+        assert!(play.py_set_inning(1, "middle").is_err());
+        assert!(play.py_set_play_type("Not A Play").is_err());
+        assert!(play.add_movement("Person D".to_string(), "home", "5", false).is_err());
+    }
+
+    #[test]
+    fn builder_validates_plays() {
+        let mut builder = example_game_builder();
+        builder.push_play(single("Person D", &[("Person D", "home", "1")]).build().unwrap());
+        builder.push_play(single("Person E", &[("Person E", "home", "1"), ("Person D", "1", "2")]).build().unwrap());
+        assert!(builder.replay_plays().is_ok());
+        assert_eq!(builder.build().unwrap().plays.len(), 2);
+
+        builder.push_play(single("Person F", &[("Person F", "home", "1"), ("Person Z", "1", "2")]).build().unwrap());
+        assert!(builder.replay_plays().is_err());
+    }
+
+    #[test]
+    fn game_outcome_accessors() {
+        let home_run = |inning: u64, top_bottom: &str, batter: &str| {
+            let mut play = single(batter, &[(batter, "home", "home")]);
+            play.py_set_inning(inning, top_bottom).unwrap();
+            play.py_set_play_type("Home Run").unwrap();
+            play.build().unwrap()
+        };
+
+        let mut builder = example_game_builder();
+        builder.push_play(home_run(1, "top", "Person D"));
+        builder.push_play(home_run(9, "bottom", "Person B"));
+        let game = builder.build().unwrap();
+        // tying the game isn't a walk-off
+        assert_eq!(game.run_differential().unwrap(), 0);
+        assert!(!game.was_walk_off().unwrap());
+        assert!(!game.extra_innings());
+
+        builder.push_play(home_run(10, "bottom", "Person B"));
+        let game = builder.build().unwrap();
+        assert_eq!(game.run_differential().unwrap(), 1);
+        assert!(game.was_walk_off().unwrap());
+        assert!(game.extra_innings());
+
+        builder.push_play(home_run(11, "top", "Person D"));
+        assert!(!builder.build().unwrap().was_walk_off().unwrap());
+    }
+
+    #[test]
+    fn batter_results() {
+        let mut builder = example_game_builder();
+        builder.push_play(single("Person D", &[("Person D", "home", "1")]).build().unwrap());
+        let mut play = single("Person E", &[("Person E", "home", "home")]);
+        play.py_set_play_type("Strikeout").unwrap();
+        builder.push_play(play.build().unwrap());
+        let mut play = PlayBuilder::new();
+        play.py_set_inning(1, "top").unwrap();
+        play.py_set_play_type("Wild Pitch").unwrap();
+        play.py_set_pitcher("Person A".to_string()).unwrap();
+        play.py_set_runner("Person D".to_string()).unwrap();
+        play.add_movement("Person D".to_string(), "1", "2", false).unwrap();
+        builder.push_play(play.build().unwrap());
+        let mut play = single("Person D", &[("Person D", "home", "home")]);
+        play.py_set_play_type("Home Run").unwrap();
+        builder.push_play(play.build().unwrap());
+        let game = builder.build().unwrap();
+
+        assert_eq!(game.batter_results("Person D"), vec!["1B", "HR"]);
+        assert_eq!(game.batter_results("Person E"), vec!["K"]);
+        assert!(game.batter_results("Person F").is_empty());
+    }
+
+    #[test]
+    fn plays_in_inning() {
+        let mut builder = example_game_builder();
+        for (inning, top_bottom) in [(1, "top"), (1, "top"), (1, "bottom"), (2, "top"), (3, "bottom")] {
+            let mut play = single("Person D", &[]);
+            play.py_set_inning(inning, top_bottom).unwrap();
+            builder.push_play(play.build().unwrap());
+        }
+        let game = builder.build().unwrap();
+
+        assert_eq!(game.plays_in_inning(1, None).len(), 3);
+        assert_eq!(game.plays_in_inning(1, Some(TopBottom::Top)).len(), 2);
+        assert_eq!(game.plays_in_inning(1, Some(TopBottom::Bottom)).len(), 1);
+        assert_eq!(game.plays_in_inning(2, Some(TopBottom::Bottom)).len(), 0);
+        assert_eq!(game.plays_in_inning(3, None)[0].inning.top_bottom, TopBottom::Bottom);
+        assert!(game.plays_in_inning(4, None).is_empty());
+    }
+
+    #[test]
+    fn plays_by_player_and_type() {
+        let mut builder = example_game_builder();
+        builder.push_play(single("Person D", &[("Person D", "home", "1")]).build().unwrap());
+        let mut play = single("Person E", &[("Person E", "home", "home"), ("Person D", "1", "home")]);
+        play.py_set_play_type("Home Run").unwrap();
+        builder.push_play(play.build().unwrap());
+        let game = builder.build().unwrap();
+
+        assert_eq!(game.plays_by_player("Person D").len(), 2);
+        assert_eq!(game.plays_by_player("Person E").len(), 1);
+        assert_eq!(game.plays_by_player("Person A").len(), 2);
+        assert!(game.plays_by_player("Person F").is_empty());
+        assert_eq!(game.plays_of_type(PlayType::HomeRun)[0].play_content.batter().unwrap(), "Person E");
+        assert!(game.plays_of_type(PlayType::Walk).is_empty());
+    }
+
+    #[test]
+    fn digest_ignores_movement_order() {
+        let game = |plays: Vec<Play>| {
+            let mut builder = example_game_builder();
+            for play in plays {
+                builder.push_play(play);
+            }
+            builder.build().unwrap()
+        };
+
+        let first = single("Person D", &[("Person D", "home", "1")]).build().unwrap();
+        let second = single("Person E", &[("Person E", "home", "1"), ("Person D", "1", "2")]).build().unwrap();
+        let reordered = single("Person E", &[("Person D", "1", "2"), ("Person E", "home", "1")]).build().unwrap();
+        let different = single("Person E", &[("Person E", "home", "1"), ("Person D", "1", "3")]).build().unwrap();
+
+        let digest = game(vec![first.clone(), second]).digest();
+        assert_eq!(digest.len(), 16);
+        assert_eq!(digest, game(vec![first.clone(), reordered]).digest());
+        assert_ne!(digest, game(vec![first, different]).digest());
+    }
+
+    #[test]
+    fn canonical_text_has_the_same_digest() {
+        let parse = |text: &str| {
+            let mut parser = crate::parser::Parser::new(false);
+            parser.options.skip_validation = true;
+            parser.parse_input(text).unwrap();
+            parser.complete().unwrap()
+        };
+        for path in crate::corpus::game_paths(std::path::Path::new("test_data")).unwrap() {
+            let text = std::fs::read_to_string(path).unwrap();
+            let canonical = crate::parser::canonical::canonicalize(&text, false).unwrap();
+            assert_eq!(parse(&canonical).digest(), parse(&text).digest());
+        }
+    }
+
+    #[test]
+    fn play_keeps_every_hop() {
+        let play = single("Person E", &[("Person E", "home", "1"), ("Person D", "1", "2"), ("Person D", "2", "3")]).build().unwrap();
+        assert_eq!(play.movements.len(), 3);
+        assert_eq!(play.simplified_movements(), vec![
+            Movement { runner: "Person E".into(), from: Base::Home, to: Base::First, out: false, on_error: false, out_at: None, credits: Vec::new() },
+            Movement { runner: "Person D".into(), from: Base::First, to: Base::Third, out: false, on_error: false, out_at: None, credits: Vec::new() },
+        ]);
+    }
+
+    #[test]
+    fn movements_are_readable_from_python() {
+        use pyo3::prelude::{Bound, PyAnyMethods};
+
+        let play = single("Person E", &[("Person E", "home", "1")]).build().unwrap();
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let movement = Bound::new(py, play.movements[0].clone()).unwrap();
+            assert_eq!(movement.getattr("runner").unwrap().extract::<String>().unwrap(), "Person E");
+            assert_eq!(movement.getattr("from_base").unwrap().extract::<Base>().unwrap(), Base::Home);
+            assert_eq!(movement.getattr("to_base").unwrap().extract::<Base>().unwrap(), Base::First);
+            assert!(!movement.getattr("out").unwrap().extract::<bool>().unwrap());
+            assert_eq!(movement.repr().unwrap().to_string(), "Movement(Person E home -> 1)");
+        });
+    }
+
+    #[test]
+    fn value_types_round_trip_through_strings() {
+        use pyo3::prelude::PyAnyMethods;
+
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let home_run = py.get_type::<PlayType>().call_method1("from_str", ("Home Run",)).unwrap();
+            assert_eq!(home_run.extract::<PlayType>().unwrap(), PlayType::HomeRun);
+            assert_eq!(home_run.call_method0("to_str").unwrap().extract::<String>().unwrap(), "Home Run");
+            assert!(py.get_type::<PlayType>().call_method1("from_str", ("Homer",)).is_err());
+
+            let inning = py.get_type::<Inning>().call_method1("from_str", ("7 bottom",)).unwrap();
+            assert_eq!(inning.extract::<Inning>().unwrap(), Inning { number: 7, top_bottom: TopBottom::Bottom });
+            assert_eq!(inning.call_method0("to_str").unwrap().extract::<String>().unwrap(), "7 bottom");
+            let shortstop = py.get_type::<Position>().call_method1("from_str", ("SHORTSTOP",)).unwrap();
+            assert_eq!(shortstop.extract::<Position>().unwrap(), Position::Shortstop);
+        });
+        assert!("7".parse::<Inning>().is_err());
+        assert!("x top".parse::<Inning>().is_err());
+    }
+
+    #[test]
+    fn stolen_base_runner() {
+        let mut play = PlayBuilder::new();
+        play.py_set_inning(1, "top").unwrap();
+        play.py_set_play_type("Stolen Base").unwrap();
+        play.set_base(Base::Second);
+        play.set_runner("Person D".to_string());
+        assert_eq!(play.build().unwrap().play_content, PlayContent::StolenBase { base: Base::Second, runner: "Person D".into() });
+
+        // older exports stored the runner as `scoring_runner`
+        let content: PlayContent = serde_json::from_str(r#"{"StolenBase": {"base": "Second", "scoring_runner": "Person D"}}"#).unwrap();
+        assert_eq!(content, PlayContent::StolenBase { base: Base::Second, runner: "Person D".into() });
+    }
+
+    #[test]
+    fn builder_state_round_trips_through_json() {
+        let mut builder = example_game_builder();
+        builder.push_play(single("Person D", &[("Person D", "home", "1")]).build().unwrap());
+
+        let resumed = GameBuilder::from_json(&builder.to_json().unwrap()).unwrap();
+        assert_eq!(resumed.venue, builder.venue);
+        assert_eq!(resumed.away_team_players.len(), 1);
+        assert_eq!(resumed.plays[0].movements, builder.plays[0].movements);
+        assert_eq!(resumed.replay_plays().unwrap().runner_positions.first, Some("Person D".into()));
+    }
+
+    #[test]
+    fn game_round_trips_through_json() {
+        let mut parser = crate::parser::Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+
+        let loaded = Game::from_json(&game.to_json("snake", "tagged").unwrap()).unwrap();
+        assert_eq!(loaded.digest(), game.digest());
+        assert_eq!(to_text(&loaded, false, true), to_text(&game, false, true));
+        assert_eq!(loaded.context.weather.condition, game.context.weather.condition);
+
+        assert!(Game::from_json(&game.to_json("camel", "tagged").unwrap()).is_err());
+    }
+
+    #[test]
+    fn to_text_parses_back_to_the_same_game() {
+        let parse = |text: &str| {
+            let mut parser = crate::parser::Parser::new(false);
+            parser.options.skip_validation = true;
+            parser.parse_input(text).unwrap();
+            parser.complete().unwrap()
+        };
+        pyo3::prepare_freethreaded_python();
+        for path in crate::corpus::game_paths(std::path::Path::new("test_data")).unwrap() {
+            let game = parse(&std::fs::read_to_string(path).unwrap());
+            let text = Python::with_gil(|py| {
+                Bound::new(py, game.clone()).unwrap().call_method0("to_text").unwrap().extract::<String>().unwrap()
+            });
+            assert_eq!(serde_json::to_value(parse(&text)).unwrap(), serde_json::to_value(&game).unwrap());
+        }
+    }
+
+    #[test]
+    fn games_iterate_over_their_plays() {
+        let mut builder = example_game_builder();
+        builder.push_play(single("Person E", &[("Person E", "home", "1")]).build().unwrap());
+        builder.push_play(single("Person F", &[("Person F", "home", "1"), ("Person E", "1", "2")]).build().unwrap());
+        let game = builder.build().unwrap();
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let game = Bound::new(py, game).unwrap();
+            assert_eq!(game.len().unwrap(), 2);
+            let batter = |play: Bound<'_, pyo3::PyAny>| play.extract::<Play>().unwrap().play_content.batter().unwrap().to_string();
+            assert_eq!(batter(game.get_item(-1).unwrap()), "Person F");
+            assert!(game.get_item(2).unwrap_err().is_instance_of::<PyIndexError>(py));
+            let batters = game.try_iter().unwrap().map(|play| batter(play.unwrap())).collect::<Vec<_>>();
+            assert_eq!(batters, vec!["Person E", "Person F"]);
+        });
+    }
+
+    #[test]
+    fn game_fields_are_readable_from_python() {
+        use pyo3::prelude::{Bound, PyAnyMethods};
+
+        let game = example_game_builder().build().unwrap();
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let game = Bound::new(py, game).unwrap();
+            let home_team = game.getattr("home_team").unwrap();
+            assert_eq!(home_team.getattr("team_id").unwrap().extract::<u64>().unwrap(), 1);
+            let player = home_team.getattr("players").unwrap().get_item(0).unwrap();
+            assert_eq!(player.getattr("name").unwrap().extract::<String>().unwrap(), "Person A");
+            assert_eq!(player.getattr("position").unwrap().extract::<String>().unwrap(), "PITCHER");
+            assert_eq!(game.getattr("away_team").unwrap().getattr("team_id").unwrap().extract::<u64>().unwrap(), 2);
+
+            let context = game.getattr("context").unwrap();
+            assert_eq!(context.getattr("venue").unwrap().extract::<String>().unwrap(), "Example Park");
+            assert_eq!(context.getattr("weather").unwrap().getattr("condition").unwrap().extract::<String>().unwrap(), "Clear");
+        });
+    }
 }
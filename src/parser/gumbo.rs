@@ -0,0 +1,256 @@
+use std::collections::BTreeMap;
+
+use serde_json::{json, Value};
+
+use super::game::{Base, Game, Movement, PlayType, Position, Team, TopBottom, UnitSystem};
+use super::scoring::team_errors;
+use super::state::LiveGameState;
+
+/// Convert a game to a subset of the MLB Stats API live feed (GUMBO) schema: `gamePk`, `gameData` with the date, teams,
+/// venue and weather, and `liveData` with every play under `plays.allPlays`, a `linescore`, and the rosters under
+/// `boxscore`. Players are identified by `fullName` only, since the text format has no player ids.
+pub fn to_gumbo(game: &Game) -> Result<Value, String> {
+    let pinch_runners = game.pinch_runners();
+    let mut state = LiveGameState::new();
+    let mut all_plays = Vec::new();
+    // runs scored by (away, home) in each inning
+    let mut innings = BTreeMap::<u64, (u64, u64)>::new();
+    for (index, play) in game.plays.iter().enumerate() {
+        let (away_before, home_before) = (state.away_team_score, state.home_team_score);
+        state.set_inning(play.inning);
+        state.process_play(play, &pinch_runners, false)
+            .map_err(|e| format!("Inning {}: {}", play.inning.to_string(), e))?;
+
+        let runs = innings.entry(play.inning.number).or_default();
+        runs.0 += state.away_team_score - away_before;
+        runs.1 += state.home_team_score - home_before;
+
+        let content = &play.play_content;
+        let play_type = content.play_type();
+        let event_type = event_type(play_type, content.base());
+        let runners = play.movements.iter()
+            .map(|movement| runner(movement, &event_type))
+            .collect::<Vec<_>>();
+
+        all_plays.push(json!({
+            "result": {
+                "type": if content.batter().is_some() { "atBat" } else { "action" },
+                "event": play_type.to_string(),
+                "eventType": event_type,
+                "awayScore": state.away_team_score,
+                "homeScore": state.home_team_score,
+                "isOut": play.movements.iter().any(|movement| movement.out),
+            },
+            "about": {
+                "atBatIndex": index,
+                "halfInning": play.inning.top_bottom.to_string(),
+                "isTopInning": play.inning.top_bottom == TopBottom::Top,
+                "inning": play.inning.number,
+            },
+            "count": {
+                "outs": state.outs,
+            },
+            "matchup": {
+                "batter": content.batter().map(|name| json!({ "fullName": name.as_str() })),
+                "pitcher": content.pitcher().map(|name| json!({ "fullName": name.as_str() })),
+            },
+            "runners": runners,
+        }));
+    }
+
+    let weather = game.context.weather.in_units(UnitSystem::Imperial);
+    let (away_errors, home_errors) = team_errors(game);
+    let teams = |away: Value, home: Value| json!({ "away": away, "home": home });
+
+    Ok(json!({
+        "gamePk": game.context.game_pk,
+        "gameData": {
+            "game": {
+                "pk": game.context.game_pk,
+            },
+            "datetime": {
+                "officialDate": game.context.date,
+            },
+            "teams": teams(
+                json!({ "id": game.away_team.team_id }),
+                json!({ "id": game.home_team.team_id }),
+            ),
+            "venue": {
+                "name": game.context.venue.as_str(),
+            },
+            "weather": {
+                "condition": weather.condition.to_string(),
+                "temp": format!("{}", weather.temperature),
+                "wind": format!("{} mph", weather.wind_speed),
+            },
+        },
+        "liveData": {
+            "plays": {
+                "allPlays": all_plays,
+            },
+            "linescore": {
+                "currentInning": game.plays.last().map_or(0, |play| play.inning.number),
+                "innings": innings.iter().map(|(number, (away, home))| json!({
+                    "num": number,
+                    "away": { "runs": away },
+                    "home": { "runs": home },
+                })).collect::<Vec<_>>(),
+                "teams": teams(
+                    json!({ "runs": state.away_team_score, "errors": away_errors }),
+                    json!({ "runs": state.home_team_score, "errors": home_errors }),
+                ),
+            },
+            "boxscore": {
+                "teams": teams(boxscore_team(&game.away_team), boxscore_team(&game.home_team)),
+            },
+        },
+    }))
+}
+
+fn boxscore_team(team: &Team) -> Value {
+    let players = team.players.iter()
+        .map(|player| json!({
+            "person": { "fullName": player.name.as_str() },
+            "position": { "abbreviation": position_abbreviation(player.position) },
+        }))
+        .collect::<Vec<_>>();
+
+    json!({
+        "team": { "id": team.team_id },
+        "players": players,
+    })
+}
+
+fn runner(movement: &Movement, event_type: &str) -> Value {
+    let start = base_code(movement.from);
+    // the base the out was made at, if written, or else the base the runner was heading for
+    let out_at = movement.out_at.unwrap_or(movement.to);
+    let (end, out_base) = match (movement.out, movement.from, movement.to) {
+        // a batter put out before reaching base is out at first
+        (true, Base::Home, Base::Home) if movement.out_at.is_none() => (None, Some("1B")),
+        (true, _, _) if out_at == Base::Home => (None, Some("4B")),
+        (true, _, _) => (None, base_code(out_at)),
+        (false, _, Base::Home) => (Some("score"), None),
+        (false, _, to) => (base_code(to), None),
+    };
+
+    let credits = movement.credits.iter()
+        .enumerate()
+        .map(|(index, position)| json!({
+            "position": { "code": position.to_string() },
+            "credit": if index + 1 == movement.credits.len() { "f_putout" } else { "f_assist" },
+        }))
+        .collect::<Vec<_>>();
+
+    json!({
+        "movement": {
+            "originBase": start,
+            "start": start,
+            "end": end,
+            "outBase": out_base,
+            "isOut": movement.out,
+        },
+        "details": {
+            "eventType": event_type,
+            "runner": { "fullName": movement.runner.as_str() },
+        },
+        "credits": credits,
+    })
+}
+
+/// Return a base as the live feed writes it, or `None` for home plate, where a batter starts.
+fn base_code(base: Base) -> Option<&'static str> {
+    match base {
+        Base::Home => None,
+        Base::First => Some("1B"),
+        Base::Second => Some("2B"),
+        Base::Third => Some("3B"),
+    }
+}
+
+/// Return the live feed's `eventType` for a play, e.g. `home_run` or `stolen_base_2b`.
+fn event_type(play_type: PlayType, base: Option<Base>) -> String {
+    let with_base = |event: &str| match base {
+        Some(Base::Home) => format!("{}_home", event),
+        Some(base) => format!("{}_{}", event, base_code(base).unwrap().to_lowercase()),
+        None => event.to_string(),
+    };
+
+    match play_type {
+        PlayType::Groundout | PlayType::BuntGroundout | PlayType::Lineout | PlayType::BuntLineout | PlayType::Flyout
+            | PlayType::PopOut | PlayType::BuntPopOut | PlayType::FieldOut => "field_out".to_string(),
+        PlayType::Forceout => "force_out".to_string(),
+        PlayType::Pickoff => with_base("pickoff"),
+        PlayType::PickoffError => with_base("pickoff_error"),
+        PlayType::CaughtStealing => with_base("caught_stealing"),
+        PlayType::PickoffCaughtStealing => with_base("pickoff_caught_stealing"),
+        PlayType::StolenBase => with_base("stolen_base"),
+        PlayType::RunnerOut | PlayType::BatterOut => "other_out".to_string(),
+        PlayType::CatcherInterference => "catcher_interf".to_string(),
+        _ => play_type.to_string().to_lowercase().replace(' ', "_"),
+    }
+}
+
+fn position_abbreviation(position: Position) -> &'static str {
+    match position {
+        Position::Pitcher => "P",
+        Position::Catcher => "C",
+        Position::FirstBase => "1B",
+        Position::SecondBase => "2B",
+        Position::ThirdBase => "3B",
+        Position::Shortstop => "SS",
+        Position::LeftField => "LF",
+        Position::CenterField => "CF",
+        Position::RightField => "RF",
+        Position::DesignatedHitter => "DH",
+        Position::PinchHitter => "PH",
+        Position::PinchRunner => "PR",
+        Position::TwoWayPlayer => "TWP",
+        Position::Outfield => "OF",
+        Position::Infield => "IF",
+        Position::Utility => "UTIL",
+        Position::ReliefPitcher => "RP",
+        Position::StartingPitcher => "SP",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn export_live_feed() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+        let state = LiveGameState::replay(&game.plays, &game.pinch_runners()).unwrap();
+
+        let feed = to_gumbo(&game).unwrap();
+        assert_eq!(feed["gamePk"], 748231);
+        assert_eq!(feed["gameData"]["teams"]["home"]["id"], game.home_team.team_id);
+
+        let plays = feed["liveData"]["plays"]["allPlays"].as_array().unwrap();
+        assert_eq!(plays.len(), game.plays.len());
+        assert_eq!(plays[0]["result"]["event"], "Strikeout");
+        assert_eq!(plays[0]["result"]["eventType"], "strikeout");
+        assert_eq!(plays[0]["matchup"]["batter"]["fullName"], "Garrett Hampson");
+        assert_eq!(plays[0]["about"]["halfInning"], "top");
+        assert_eq!(plays.last().unwrap()["result"]["homeScore"], state.home_team_score);
+
+        let linescore = &feed["liveData"]["linescore"];
+        assert_eq!(linescore["teams"]["away"]["runs"], state.away_team_score);
+        assert_eq!(linescore["teams"]["home"]["errors"], team_errors(&game).1);
+        let innings = linescore["innings"].as_array().unwrap();
+        let home_runs = innings.iter().map(|inning| inning["home"]["runs"].as_u64().unwrap()).sum::<u64>();
+        assert_eq!(home_runs, state.home_team_score);
+    }
+
+    #[test]
+    fn event_types() {
+        assert_eq!(event_type(PlayType::HomeRun, None), "home_run");
+        assert_eq!(event_type(PlayType::StolenBase, Some(Base::Second)), "stolen_base_2b");
+        assert_eq!(event_type(PlayType::CaughtStealing, Some(Base::Home)), "caught_stealing_home");
+        assert_eq!(event_type(PlayType::Flyout, None), "field_out");
+    }
+}
@@ -0,0 +1,104 @@
+use pyo3::prelude::pyclass;
+
+use super::game::{Game, Inning, PlayType};
+use super::scoring::is_error;
+use super::state::{LiveGameState, RunnerPositions};
+
+/// What happened in one half-inning.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HalfInningSummary {
+    #[pyo3(get)]
+    pub inning: Inning,
+    #[pyo3(get)]
+    pub runs: u64,
+    #[pyo3(get)]
+    pub hits: u64,
+    /// Errors committed by the fielding team.
+    #[pyo3(get)]
+    pub errors: u64,
+    /// The runners still on base after the last play of the half-inning.
+    #[pyo3(get)]
+    pub left_on_base: u64,
+    #[pyo3(get)]
+    pub batters_faced: u64,
+}
+
+impl HalfInningSummary {
+    fn new(inning: Inning) -> Self {
+        Self {
+            inning,
+            runs: 0,
+            hits: 0,
+            errors: 0,
+            left_on_base: 0,
+            batters_faced: 0,
+        }
+    }
+}
+
+/// Return a summary of each half-inning in the game, in order, replaying it to count the runs and runners left on base.
+pub fn half_innings(game: &Game) -> Result<Vec<HalfInningSummary>, String> {
+    let pinch_runners = game.pinch_runners();
+    let mut state = LiveGameState::new();
+    let mut summaries = Vec::<HalfInningSummary>::new();
+    for play in &game.plays {
+        state.set_inning(play.inning);
+        let runs_before = state.away_team_score + state.home_team_score;
+        state.process_play(play, &pinch_runners, false)
+            .map_err(|e| format!("Inning {}: {}", play.inning.to_string(), e))?;
+
+        if summaries.last().is_none_or(|summary| summary.inning != play.inning) {
+            summaries.push(HalfInningSummary::new(play.inning));
+        }
+        let summary = summaries.last_mut().unwrap();
+
+        let play_type = play.play_content.play_type();
+        summary.runs += state.away_team_score + state.home_team_score - runs_before;
+        if matches!(play_type, PlayType::Single | PlayType::Double | PlayType::Triple | PlayType::HomeRun) {
+            summary.hits += 1;
+        }
+        if is_error(play_type) {
+            summary.errors += 1;
+        }
+        if play_type.requires_batter() {
+            summary.batters_faced += 1;
+        }
+        summary.left_on_base = runners_on_base(&state.runner_positions);
+    }
+
+    Ok(summaries)
+}
+
+fn runners_on_base(runner_positions: &RunnerPositions) -> u64 {
+    [&runner_positions.first, &runner_positions.second, &runner_positions.third]
+        .iter()
+        .filter(|runner| runner.is_some())
+        .count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+    use crate::parser::game::TopBottom;
+
+    #[test]
+    fn summaries_add_up_to_the_game() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+
+        let summaries = half_innings(&game).unwrap();
+        assert_eq!(summaries[0].inning, Inning { number: 1, top_bottom: TopBottom::Top });
+        assert_eq!(summaries[1].inning, Inning { number: 1, top_bottom: TopBottom::Bottom });
+
+        let state = game.replay().unwrap();
+        assert_eq!(summaries.iter().map(|summary| summary.runs).sum::<u64>(), state.away_team_score + state.home_team_score);
+        let (away_errors, home_errors) = crate::parser::scoring::team_errors(&game);
+        assert_eq!(summaries.iter().map(|summary| summary.errors).sum::<u64>(), away_errors + home_errors);
+        let batters = game.plays.iter().filter(|play| play.play_content.play_type().requires_batter()).count() as u64;
+        assert_eq!(summaries.iter().map(|summary| summary.batters_faced).sum::<u64>(), batters);
+        assert!(summaries.iter().all(|summary| summary.left_on_base <= 3 && summary.hits <= summary.batters_faced));
+    }
+}
@@ -0,0 +1,247 @@
+use std::collections::{BTreeMap, HashMap};
+
+use pyo3::{prelude::{pyclass, pymethods, PyResult}, exceptions::PyValueError};
+
+use super::game::{Base, Context, Extension, Game, Inning, Movement, Play, PlayBuilder, PlayType, Player, Position, Team};
+use super::small_string::SmallString;
+
+/// A movement whose runner is an index into the game's names.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedMovement {
+    pub runner: u32,
+    pub from: Base,
+    pub to: Base,
+    pub out: bool,
+    pub on_error: bool,
+    pub out_at: Option<Base>,
+    pub credits: Vec<u8>,
+}
+
+/// A play whose players are indices into the game's names.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndexedPlay {
+    pub inning: Inning,
+    pub play_type: PlayType,
+    pub base: Option<Base>,
+    pub batter: Option<u32>,
+    pub pitcher: Option<u32>,
+    pub catcher: Option<u32>,
+    pub fielders: Vec<u32>,
+    pub runner: Option<u32>,
+    pub scoring_runner: Option<u32>,
+    pub error_by: Option<u32>,
+    pub position: Option<u8>,
+    pub movements: Vec<IndexedMovement>,
+    pub extensions: Vec<Extension>,
+    pub meta: BTreeMap<String, String>,
+    pub overturned: Option<Box<IndexedPlay>>,
+}
+
+/// A game that stores each player's name once, with the rosters and plays referring to players by index, for
+/// holding many games in less memory. Convert back with `to_game`.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct IndexedGame {
+    #[pyo3(get)]
+    pub context: Context,
+    #[pyo3(get)]
+    pub home_team_id: u64,
+    #[pyo3(get)]
+    pub away_team_id: u64,
+    // every name in the game, roster players first in roster order
+    #[pyo3(get)]
+    pub names: Vec<SmallString>,
+    pub home_players: Vec<(Position, u32)>,
+    pub away_players: Vec<(Position, u32)>,
+    pub plays: Vec<IndexedPlay>,
+    pub extensions: Vec<Extension>,
+}
+
+/// Assigns each distinct name the next index, the first time it's seen.
+#[derive(Default)]
+struct NameTable {
+    names: Vec<SmallString>,
+    indices: HashMap<SmallString, u32>,
+}
+
+impl NameTable {
+    fn index(&mut self, name: &SmallString) -> u32 {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+
+        let index = self.names.len() as u32;
+        self.names.push(name.clone());
+        self.indices.insert(name.clone(), index);
+
+        index
+    }
+
+    /// Index a play's players, and those of the call it overturned.
+    fn play(&mut self, play: &Play) -> IndexedPlay {
+        let content = &play.play_content;
+        IndexedPlay {
+            inning: play.inning,
+            play_type: content.play_type(),
+            base: content.base(),
+            batter: content.batter().map(|name| self.index(name)),
+            pitcher: content.pitcher().map(|name| self.index(name)),
+            catcher: content.catcher().map(|name| self.index(name)),
+            fielders: content.fielders().iter().map(|name| self.index(name)).collect(),
+            runner: content.runner().map(|name| self.index(name)),
+            scoring_runner: content.scoring_runner().map(|name| self.index(name)),
+            error_by: content.error_by().map(|name| self.index(name)),
+            position: content.position(),
+            movements: play.movements.iter().map(|movement| IndexedMovement {
+                runner: self.index(&movement.runner),
+                from: movement.from,
+                to: movement.to,
+                out: movement.out,
+                on_error: movement.on_error,
+                out_at: movement.out_at,
+                credits: movement.credits.clone(),
+            }).collect(),
+            extensions: play.extensions.clone(),
+            meta: play.meta.clone(),
+            overturned: play.overturned.as_deref().map(|overturned| Box::new(self.play(overturned))),
+        }
+    }
+}
+
+impl From<&Game> for IndexedGame {
+    fn from(game: &Game) -> Self {
+        let mut table = NameTable::default();
+        let mut index_players = |team: &Team| team.players.iter()
+            .map(|player| (player.position, table.index(&player.name)))
+            .collect::<Vec<_>>();
+        let home_players = index_players(&game.home_team);
+        let away_players = index_players(&game.away_team);
+
+        let plays = game.plays.iter().map(|play| table.play(play)).collect();
+
+        Self {
+            context: game.context.clone(),
+            home_team_id: game.home_team.team_id,
+            away_team_id: game.away_team.team_id,
+            names: table.names,
+            home_players,
+            away_players,
+            plays,
+            extensions: game.extensions.clone(),
+        }
+    }
+}
+
+impl IndexedGame {
+    fn name(&self, index: u32) -> Result<SmallString, String> {
+        self.names.get(index as usize).cloned().ok_or(format!("Name index {} is out of range", index))
+    }
+
+    fn team(&self, team_id: u64, players: &[(Position, u32)]) -> Result<Team, String> {
+        let players = players.iter()
+            .map(|&(position, index)| Ok(Player { position, name: self.name(index)? }))
+            .collect::<Result<_, String>>()?;
+
+        Ok(Team { team_id, players })
+    }
+
+    fn play(&self, play: &IndexedPlay) -> Result<Play, String> {
+        let name = |index: Option<u32>| index.map(|index| self.name(index)).transpose();
+
+        let mut builder = PlayBuilder::new();
+        builder.inning = Some(play.inning);
+        builder.play_type = Some(play.play_type);
+        builder.base = play.base;
+        builder.batter = name(play.batter)?;
+        builder.pitcher = name(play.pitcher)?;
+        builder.catcher = name(play.catcher)?;
+        builder.fielders = play.fielders.iter().map(|&index| self.name(index)).collect::<Result<_, _>>()?;
+        builder.runner = name(play.runner)?;
+        builder.scoring_runner = name(play.scoring_runner)?;
+        builder.error_by = name(play.error_by)?;
+        builder.position = play.position;
+        builder.movements = play.movements.iter().map(|movement| Ok(Movement {
+            runner: self.name(movement.runner)?,
+            from: movement.from,
+            to: movement.to,
+            out: movement.out,
+            on_error: movement.on_error,
+            out_at: movement.out_at,
+            credits: movement.credits.clone(),
+        })).collect::<Result<_, String>>()?;
+        builder.extensions = play.extensions.clone();
+        builder.meta = play.meta.clone();
+
+        let mut built = builder.build().ok_or(format!("Inning {}: {} is missing a player", play.inning.to_string(), play.play_type.to_string()))?;
+        built.overturned = play.overturned.as_deref().map(|overturned| self.play(overturned).map(Box::new)).transpose()?;
+
+        Ok(built)
+    }
+
+    /// Rebuild the full game, with every name in place.
+    pub fn to_game(&self) -> Result<Game, String> {
+        Ok(Game {
+            context: self.context.clone(),
+            home_team: self.team(self.home_team_id, &self.home_players)?,
+            away_team: self.team(self.away_team_id, &self.away_players)?,
+            plays: self.plays.iter().map(|play| self.play(play)).collect::<Result<_, _>>()?,
+            extensions: self.extensions.clone(),
+        })
+    }
+}
+
+#[pymethods]
+impl IndexedGame {
+    #[staticmethod]
+    #[pyo3(name = "from_game")]
+    fn py_from_game(game: &Game) -> Self {
+        Self::from(game)
+    }
+
+    /// Rebuild the full game, with every name in place.
+    #[pyo3(name = "to_game")]
+    fn py_to_game(&self) -> PyResult<Game> {
+        self.to_game().map_err(PyValueError::new_err)
+    }
+
+    fn __len__(&self) -> usize {
+        self.plays.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{export, Parser};
+
+    #[test]
+    fn round_trip_through_indices() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+
+        let indexed = IndexedGame::from(&game);
+        assert_eq!(indexed.plays.len(), game.plays.len());
+        // each name is stored once
+        let mut names = indexed.names.clone();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), indexed.names.len());
+        assert_eq!(indexed.names[indexed.home_players[0].1 as usize], game.home_team.players[0].name);
+
+        assert_eq!(
+            export::export_value(&indexed.to_game().unwrap(), export::ExportOptions::default()),
+            export::export_value(&game, export::ExportOptions::default()),
+        );
+    }
+
+    #[test]
+    fn out_of_range_index_is_an_error() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let mut indexed = IndexedGame::from(&parser.complete().unwrap());
+
+        indexed.plays[0].batter = Some(indexed.names.len() as u32);
+        assert!(indexed.to_game().is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::ops::Deref;
+
+/// The input waiting to be parsed. Consuming a section only moves the start of the buffer forward, rather than copying
+/// the rest of the input, so parsing a whole game handed over at once takes time linear in its length. The consumed
+/// text is dropped once it makes up most of the buffer, when more input arrives, except for any text still kept.
+#[derive(Clone, Debug, Default)]
+pub struct InputBuffer {
+    text: String,
+    start: usize,
+    // where the text returned by `kept` starts, if any is kept
+    kept: Option<usize>,
+}
+
+impl InputBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.text.push(c);
+    }
+
+    /// Remove the last character waiting to be parsed, if there is one.
+    pub fn pop(&mut self) -> Option<char> {
+        if self.text.len() == self.start {
+            return None;
+        }
+
+        self.text.pop()
+    }
+
+    /// Return where the text waiting to be parsed starts, for `keep_from`. It stays valid until the buffer is compacted.
+    pub fn position(&self) -> usize {
+        self.start
+    }
+
+    /// Keep the text from `position` onwards, even once it's consumed, until another position is kept.
+    pub fn keep_from(&mut self, position: usize) {
+        self.kept = Some(position);
+    }
+
+    /// Return the text kept by `keep_from`, if any.
+    pub fn kept(&self) -> &str {
+        self.kept.map_or("", |kept| &self.text[kept..])
+    }
+
+    /// Consume the first `index` bytes and any whitespace after them.
+    pub fn consume(&mut self, index: usize) {
+        self.start += index;
+        let rest = &self.text[self.start..];
+        self.start += rest.len() - rest.trim_start().len();
+    }
+
+    /// Drop the consumed text that isn't kept if it makes up most of the buffer, so the buffer doesn't grow with the
+    /// whole game.
+    pub fn compact(&mut self) {
+        let end = self.kept.map_or(self.start, |kept| kept.min(self.start));
+        if end > self.text.len() / 2 {
+            self.text.drain(..end);
+            self.start -= end;
+            self.kept = self.kept.map(|kept| kept - end);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.start = 0;
+        self.kept = None;
+    }
+}
+
+impl Deref for InputBuffer {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text[self.start..]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consuming_moves_the_start() {
+        let mut buffer = InputBuffer::new();
+        "[GAME] 1 [DATE]".chars().for_each(|c| buffer.push(c));
+        buffer.consume(8);
+        assert_eq!(&*buffer, "[DATE]");
+
+        buffer.consume(6);
+        assert_eq!(buffer.pop(), None);
+        buffer.compact();
+        buffer.push('x');
+        assert_eq!((&*buffer, buffer.text.as_str()), ("x", "x"));
+    }
+
+    #[test]
+    fn kept_text_survives_compaction() {
+        let mut buffer = InputBuffer::new();
+        "[PLAY] Walk;\n[INNING]".chars().for_each(|c| buffer.push(c));
+        buffer.consume(11);
+        let play_end = buffer.position() + 1;
+        buffer.consume(1);
+        buffer.keep_from(play_end);
+        buffer.compact();
+        assert_eq!((&*buffer, buffer.kept()), ("[INNING]", "\n[INNING]"));
+
+        buffer.consume(8);
+        buffer.compact();
+        assert_eq!(buffer.text, "\n[INNING]");
+    }
+}
@@ -0,0 +1,158 @@
+use pyo3::{prelude::{pyclass, pymethods, Bound, Py, PyAnyMethods, PyResult}, exceptions::PyTypeError, types::{PyDict, PyDictMethods}};
+
+/// Where the parser writes its debug output.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugSink {
+    Stdout,
+    Stderr,
+}
+
+/// Options for `Parser(...)` and `Parser.with_options`, all off by default.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct ParserOptions {
+    /// Reject values outside those MLB reports, such as an unknown weather condition, and any venue not in `venues`.
+    #[pyo3(get, set)]
+    pub strict: bool,
+    /// The venues accepted in strict mode.
+    #[pyo3(get, set)]
+    pub venues: Option<Vec<String>>,
+    /// Keep unrecognised `[TAG] value` entries as extensions on the play or game instead of stopping the parse.
+    #[pyo3(get, set)]
+    pub allow_unknown_tags: bool,
+    /// Accept any run of spaces and newlines wherever the format has a single space or newline.
+    #[pyo3(get, set)]
+    pub lenient_whitespace: bool,
+    /// Only allow the batter due up (or a pinch hitter) in generated regexes once a team has been through its order.
+    #[pyo3(get, set)]
+    pub constrain_next_batter: bool,
+    /// The most input that may be buffered without completing a section.
+    #[pyo3(get, set)]
+    pub max_buffer_bytes: Option<usize>,
+    /// Where to write debug output, if anywhere.
+    #[pyo3(get, set)]
+    pub debug_sink: Option<DebugSink>,
+    /// Only parse plays, without checking them against the rosters or the runners on base, which is faster for
+    /// trusted data. The live game state, and so `Parser.game_state()`, is then not kept up to date.
+    #[pyo3(get, set)]
+    pub skip_validation: bool,
+    /// Require every player named in a play to be in one of the team sections. The plays received along with
+    /// `[GAME_START]` are checked up front, so each undeclared name in them is reported together.
+    #[pyo3(get, set)]
+    pub require_declared_players: bool,
+    /// Warn about a temperature outside 0–120°F or a wind speed above 60 mph, even in strict mode, since unusual real
+    /// weather can fall outside those ranges.
+    #[pyo3(get, set)]
+    pub check_weather_ranges: bool,
+    /// Require each team to have a pitcher and a catcher and to list no player twice. Every violation is reported
+    /// together, rejecting the game in strict mode.
+    #[pyo3(get, set)]
+    pub check_rosters: bool,
+    /// The `(min, max)` number of players each team must have, checked along with `check_rosters`.
+    #[pyo3(get, set)]
+    pub roster_size: Option<(usize, usize)>,
+    /// Keep a fielder named more than once in a play only the first time.
+    #[pyo3(get, set)]
+    pub dedupe_fielders: bool,
+}
+
+impl ParserOptions {
+    pub fn set_strict(&mut self, strict: bool) -> &mut Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn set_venues(&mut self, venues: Vec<String>) -> &mut Self {
+        self.venues = Some(venues);
+        self
+    }
+
+    pub fn set_allow_unknown_tags(&mut self, allow_unknown_tags: bool) -> &mut Self {
+        self.allow_unknown_tags = allow_unknown_tags;
+        self
+    }
+
+    pub fn set_lenient_whitespace(&mut self, lenient_whitespace: bool) -> &mut Self {
+        self.lenient_whitespace = lenient_whitespace;
+        self
+    }
+
+    pub fn set_constrain_next_batter(&mut self, constrain_next_batter: bool) -> &mut Self {
+        self.constrain_next_batter = constrain_next_batter;
+        self
+    }
+
+    pub fn set_max_buffer_bytes(&mut self, max_buffer_bytes: usize) -> &mut Self {
+        self.max_buffer_bytes = Some(max_buffer_bytes);
+        self
+    }
+
+    pub fn set_debug_sink(&mut self, debug_sink: DebugSink) -> &mut Self {
+        self.debug_sink = Some(debug_sink);
+        self
+    }
+
+    pub fn set_skip_validation(&mut self, skip_validation: bool) -> &mut Self {
+        self.skip_validation = skip_validation;
+        self
+    }
+
+    pub fn set_require_declared_players(&mut self, require_declared_players: bool) -> &mut Self {
+        self.require_declared_players = require_declared_players;
+        self
+    }
+
+    pub fn set_check_weather_ranges(&mut self, check_weather_ranges: bool) -> &mut Self {
+        self.check_weather_ranges = check_weather_ranges;
+        self
+    }
+
+    pub fn set_check_rosters(&mut self, check_rosters: bool) -> &mut Self {
+        self.check_rosters = check_rosters;
+        self
+    }
+
+    pub fn set_roster_size(&mut self, min: usize, max: usize) -> &mut Self {
+        self.roster_size = Some((min, max));
+        self
+    }
+
+    pub fn set_dedupe_fielders(&mut self, dedupe_fielders: bool) -> &mut Self {
+        self.dedupe_fielders = dedupe_fielders;
+        self
+    }
+
+    /// Return these options with each keyword argument set on them by name, so every option can be passed to
+    /// `ParserOptions(...)` or `Parser(...)` without listing them again. `print_debug=True` is kept as a shorthand for
+    /// `debug_sink=Stdout`.
+    pub fn with_kwargs(self, kwargs: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let options = Py::new(kwargs.py(), self)?.into_bound(kwargs.py());
+        for (key, value) in kwargs.iter() {
+            let name = key.extract::<String>()?;
+            if name == "print_debug" {
+                let sink = value.extract::<bool>()?.then_some(DebugSink::Stdout);
+                options.borrow_mut().debug_sink = sink;
+            } else if options.hasattr(name.as_str())? {
+                options.setattr(name.as_str(), value)?;
+            } else {
+                return Err(PyTypeError::new_err(format!("Unknown parser option: {}", name)));
+            }
+        }
+
+        Ok(options.borrow().clone())
+    }
+}
+
+#[pymethods]
+impl ParserOptions {
+    /// Create options from keyword arguments named after the fields, e.g. `ParserOptions(strict=True)`.
+    #[new]
+    #[pyo3(signature = (**kwargs))]
+    fn py_new(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        match kwargs {
+            Some(kwargs) => Self::default().with_kwargs(kwargs),
+            None => Ok(Self::default()),
+        }
+    }
+}
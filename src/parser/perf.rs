@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How many times something ran and how long it took in total.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Timing {
+    pub calls: u64,
+    pub total: Duration,
+}
+
+/// Time spent parsing, by kind of section (e.g. `"Plays"`) and by the regex tried for each section (e.g. `"Plays(Batter())"`).
+#[derive(Clone, Debug, Default)]
+pub struct PerfStats {
+    pub sections: HashMap<String, Timing>,
+    pub regexes: HashMap<String, Timing>,
+}
+
+impl PerfStats {
+    pub fn record(&mut self, section: &str, regex: String, start: Instant) {
+        let elapsed = start.elapsed();
+        for timing in [self.sections.entry(section.to_string()).or_default(), self.regexes.entry(regex).or_default()] {
+            timing.calls += 1;
+            timing.total += elapsed;
+        }
+    }
+
+    /// Return each group of timings as `{name: (calls, seconds)}`.
+    pub fn as_map(&self) -> HashMap<String, HashMap<String, (u64, f64)>> {
+        let seconds = |timings: &HashMap<String, Timing>| timings.iter()
+            .map(|(name, timing)| (name.clone(), (timing.calls, timing.total.as_secs_f64())))
+            .collect();
+
+        HashMap::from([
+            ("sections".to_string(), seconds(&self.sections)),
+            ("regexes".to_string(), seconds(&self.regexes)),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+
+    #[test]
+    fn record_section_timings() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+
+        let stats = parser.perf_stats();
+        let (calls, seconds) = stats["sections"]["Plays"];
+        assert!(calls > 0 && seconds > 0.0);
+        assert_eq!(stats["regexes"]["Context(Game)"].0, 1);
+        assert_eq!(
+            stats["sections"].values().map(|(calls, _)| calls).sum::<u64>(),
+            stats["regexes"].values().map(|(calls, _)| calls).sum::<u64>(),
+        );
+    }
+}
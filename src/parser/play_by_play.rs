@@ -0,0 +1,194 @@
+use pyo3::prelude::pyclass;
+
+use super::game::{Base, Game, Movement, Play, TopBottom};
+use super::state::{LiveGameState, RunnerPositions};
+
+/// A row of a play-by-play table, with the situation before and after the play.
+#[pyclass]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayByPlayRow {
+    /// e.g. `t1` for the top of the first.
+    #[pyo3(get)]
+    pub inning: String,
+    /// The score before the play, away team first, e.g. `0-2`.
+    #[pyo3(get)]
+    pub score: String,
+    #[pyo3(get)]
+    pub outs_before: u64,
+    /// The runners on base before the play, e.g. `1-3` for first and third.
+    #[pyo3(get)]
+    pub bases_before: String,
+    /// One `R` for each run and one `O` for each out on the play.
+    #[pyo3(get)]
+    pub runs_outs: String,
+    #[pyo3(get)]
+    pub batting_team: u64,
+    #[pyo3(get)]
+    pub batter: String,
+    #[pyo3(get)]
+    pub pitcher: String,
+    #[pyo3(get)]
+    pub description: String,
+    #[pyo3(get)]
+    pub outs_after: u64,
+    #[pyo3(get)]
+    pub bases_after: String,
+}
+
+const HEADERS: [&str; 11] = ["Inn", "Score", "Out", "RoB", "R/O", "@Bat", "Batter", "Pitcher", "Play Description", "Out After", "RoB After"];
+
+impl PlayByPlayRow {
+    fn cells(&self) -> [String; 11] {
+        [
+            self.inning.clone(),
+            self.score.clone(),
+            self.outs_before.to_string(),
+            self.bases_before.clone(),
+            self.runs_outs.clone(),
+            self.batting_team.to_string(),
+            self.batter.clone(),
+            self.pitcher.clone(),
+            self.description.clone(),
+            self.outs_after.to_string(),
+            self.bases_after.clone(),
+        ]
+    }
+}
+
+/// Return a row for each play in the game, replaying it to find the score, outs and runners around each play.
+pub fn play_by_play(game: &Game) -> Result<Vec<PlayByPlayRow>, String> {
+    let pinch_runners = game.pinch_runners();
+    let mut state = LiveGameState::new();
+    let mut rows = Vec::new();
+    for play in &game.plays {
+        state.set_inning(play.inning);
+        let before = state.clone();
+        state.process_play(play, &pinch_runners, false)
+            .map_err(|e| format!("Inning {}: {}", play.inning.to_string(), e))?;
+
+        let runs = (state.away_team_score + state.home_team_score) - (before.away_team_score + before.home_team_score);
+        let outs = state.outs - before.outs;
+        let (prefix, batting_team) = match play.inning.top_bottom {
+            TopBottom::Top => ("t", game.away_team.team_id),
+            TopBottom::Bottom => ("b", game.home_team.team_id),
+        };
+
+        rows.push(PlayByPlayRow {
+            inning: format!("{}{}", prefix, play.inning.number),
+            score: format!("{}-{}", before.away_team_score, before.home_team_score),
+            outs_before: before.outs,
+            bases_before: bases(&before.runner_positions),
+            runs_outs: "R".repeat(runs as usize) + &"O".repeat(outs as usize),
+            batting_team,
+            batter: play.play_content.batter().map_or(String::new(), |batter| batter.to_string()),
+            pitcher: play.play_content.pitcher().map_or(String::new(), |pitcher| pitcher.to_string()),
+            description: description(play),
+            outs_after: state.outs,
+            bases_after: bases(&state.runner_positions),
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Render the rows as a plain text table with a header, one line per play.
+pub fn render_play_by_play(rows: &[PlayByPlayRow]) -> String {
+    let cells = rows.iter().map(PlayByPlayRow::cells).collect::<Vec<_>>();
+    let widths = HEADERS.iter()
+        .enumerate()
+        .map(|(column, header)| cells.iter().map(|row| row[column].chars().count()).fold(header.len(), usize::max))
+        .collect::<Vec<_>>();
+
+    let line = |row: &[String]| row.iter()
+        .zip(&widths)
+        .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+        .collect::<Vec<_>>()
+        .join("  ")
+        .trim_end()
+        .to_string();
+
+    let mut lines = vec![line(&HEADERS.map(String::from))];
+    lines.extend(cells.iter().map(|row| line(row)));
+    lines.join("\n") + "\n"
+}
+
+fn bases(runner_positions: &RunnerPositions) -> String {
+    [
+        (&runner_positions.first, '1'),
+        (&runner_positions.second, '2'),
+        (&runner_positions.third, '3'),
+    ]
+        .iter()
+        .map(|(runner, base)| if runner.is_some() { *base } else { '-' })
+        .collect()
+}
+
+/// Describe a play in words, e.g. `Single; Bobby Witt Jr. to 3B; Salvador Perez Scores`.
+fn description(play: &Play) -> String {
+    let content = &play.play_content;
+    let mut description = content.play_type().to_string();
+    if !content.fielders().is_empty() {
+        let fielders = content.fielders().iter().map(|fielder| fielder.as_str()).collect::<Vec<_>>();
+        description.push_str(&format!(" ({})", fielders.join(", ")));
+    }
+
+    let notes = RunnerPositions::empty().simplify_movements(&play.movements).iter()
+        .filter_map(runner_note)
+        .collect::<Vec<_>>();
+    if !notes.is_empty() {
+        description.push_str("; ");
+        description.push_str(&notes.join("; "));
+    }
+
+    description
+}
+
+/// Describe where a runner ended up, or nothing for a batter whose fate is the play itself.
+fn runner_note(movement: &Movement) -> Option<String> {
+    let base = match movement.out_at.unwrap_or(movement.to) {
+        Base::First => "1B",
+        Base::Second => "2B",
+        Base::Third => "3B",
+        Base::Home => "Home",
+    };
+
+    match (movement.from, movement.to, movement.out) {
+        (Base::Home, Base::Home, true) | (Base::Home, Base::First, false) => None,
+        (_, Base::Home, false) => Some(format!("{} Scores", movement.runner)),
+        (_, _, true) => Some(format!("{} out at {}", movement.runner, base)),
+        (_, _, false) => Some(format!("{} to {}", movement.runner, base)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn rows_track_the_situation() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+
+        let rows = play_by_play(&game).unwrap();
+        assert_eq!(rows.len(), game.plays.len());
+        assert_eq!(rows[0].inning, "t1");
+        assert_eq!(rows[0].score, "0-0");
+        assert_eq!(rows[0].bases_before, "---");
+        assert_eq!(rows[0].runs_outs, "O");
+        assert_eq!(rows[0].batter, "Garrett Hampson");
+        assert_eq!(rows[0].outs_after, 1);
+        // each play starts where the last one in the half-inning left off
+        for pair in rows.windows(2).filter(|pair| pair[0].inning == pair[1].inning) {
+            assert_eq!(pair[0].outs_after, pair[1].outs_before);
+            assert_eq!(pair[0].bases_after, pair[1].bases_before);
+        }
+
+        let table = render_play_by_play(&rows);
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), rows.len() + 1);
+        assert!(lines[0].starts_with("Inn  Score  Out  RoB"));
+        assert!(lines[1].starts_with("t1   0-0    0    ---  O"));
+    }
+}
@@ -0,0 +1,179 @@
+use std::iter::Flatten;
+use std::ops::Index;
+use std::sync::Arc;
+use std::vec;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use super::game::Play;
+
+/// The number of plays in each shared chunk.
+const CHUNK_LEN: usize = 32;
+
+/// The plays parsed so far. Each full chunk of plays is frozen behind an `Arc` that links to the chunk before it, so a
+/// clone shares every full chunk with the original and copies at most one chunk of plays, however long the game is.
+#[derive(Clone, Debug, Default)]
+pub struct PlayList {
+    frozen: Option<Arc<Chunk>>,
+    tail: Vec<Play>,
+}
+
+#[derive(Debug)]
+struct Chunk {
+    plays: Vec<Play>,
+    previous: Option<Arc<Chunk>>,
+    // the number of plays in this chunk and every chunk before it
+    len: usize,
+}
+
+pub type Iter<'a> = Flatten<vec::IntoIter<&'a [Play]>>;
+
+impl PlayList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, play: Play) {
+        self.tail.push(play);
+        if self.tail.len() == CHUNK_LEN {
+            let len = self.len();
+            self.frozen = Some(Arc::new(Chunk {
+                plays: std::mem::take(&mut self.tail),
+                previous: self.frozen.take(),
+                len,
+            }));
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.frozen_len() + self.tail.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn frozen_len(&self) -> usize {
+        self.frozen.as_ref().map_or(0, |chunk| chunk.len)
+    }
+
+    pub fn last(&self) -> Option<&Play> {
+        self.tail.last().or_else(|| self.frozen.as_ref().and_then(|chunk| chunk.plays.last()))
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Play> {
+        if index >= self.frozen_len() {
+            return self.tail.get(index - self.frozen_len());
+        }
+
+        let mut chunk = self.frozen.as_deref();
+        while let Some(current) = chunk {
+            let start = current.len - current.plays.len();
+            if index >= start {
+                return current.plays.get(index - start);
+            }
+            chunk = current.previous.as_deref();
+        }
+
+        None
+    }
+
+    /// Return the plays in order, as slices of consecutive plays.
+    fn chunks(&self) -> Vec<&[Play]> {
+        let mut chunks = vec![self.tail.as_slice()];
+        let mut chunk = self.frozen.as_deref();
+        while let Some(current) = chunk {
+            chunks.push(&current.plays);
+            chunk = current.previous.as_deref();
+        }
+        chunks.reverse();
+
+        chunks
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        self.chunks().into_iter().flatten()
+    }
+
+    pub fn to_vec(&self) -> Vec<Play> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl Index<usize> for PlayList {
+    type Output = Play;
+
+    fn index(&self, index: usize) -> &Play {
+        match self.get(index) {
+            Some(play) => play,
+            None => panic!("play index {} out of range for {} plays", index, self.len()),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a PlayList {
+    type Item = &'a Play;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Iter<'a> {
+        self.iter()
+    }
+}
+
+impl FromIterator<Play> for PlayList {
+    fn from_iter<I: IntoIterator<Item = Play>>(plays: I) -> Self {
+        let mut list = Self::new();
+        plays.into_iter().for_each(|play| list.push(play));
+
+        list
+    }
+}
+
+// stored as a plain list of plays, the same as the `Vec` it replaced
+impl Serialize for PlayList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for PlayList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Play>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::game::{Inning, PlayBuilder, PlayType, TopBottom};
+
+    fn play(number: u64) -> Play {
+        let mut play = PlayBuilder::new();
+        play.set_inning(Inning { number, top_bottom: TopBottom::Top })
+            .set_play_type(PlayType::Strikeout)
+            .set_batter("Person D".to_string())
+            .set_pitcher("Person A".to_string());
+
+        play.build().unwrap()
+    }
+
+    #[test]
+    fn clones_share_full_chunks() {
+        let mut plays = (1..=CHUNK_LEN as u64 + 1).map(play).collect::<PlayList>();
+        let clone = plays.clone();
+        assert!(Arc::ptr_eq(plays.frozen.as_ref().unwrap(), clone.frozen.as_ref().unwrap()));
+        assert_eq!(clone.tail.len(), 1);
+
+        plays.push(play(100));
+        assert_eq!((plays.len(), clone.len()), (CHUNK_LEN + 2, CHUNK_LEN + 1));
+        assert_eq!(clone.last().unwrap().inning.number, CHUNK_LEN as u64 + 1);
+    }
+
+    #[test]
+    fn plays_stay_in_order_across_chunks() {
+        let plays = (1..=2 * CHUNK_LEN as u64 + 3).map(play).collect::<PlayList>();
+        let numbers = plays.iter().map(|play| play.inning.number).collect::<Vec<_>>();
+        assert_eq!(numbers, (1..=2 * CHUNK_LEN as u64 + 3).collect::<Vec<_>>());
+        assert_eq!(plays[CHUNK_LEN].inning.number, CHUNK_LEN as u64 + 1);
+        assert_eq!(plays.iter().rev().nth(1).unwrap().inning.number, 2 * CHUNK_LEN as u64 + 2);
+        assert!(plays.get(2 * CHUNK_LEN + 3).is_none());
+    }
+}
@@ -0,0 +1,298 @@
+use std::collections::HashMap;
+
+use super::game::{Base, Game, Play, PlayType, Position, Team, TopBottom, UnitSystem, WeatherCondition};
+use super::small_string::SmallString;
+use super::state::{DefensiveAlignment, RunnerPositions};
+
+/// Assigns each player a Retrosheet-style id: the first four letters of their last name, their first initial, and a
+/// number to tell apart players who would otherwise share an id, e.g. `judga001`.
+#[derive(Default)]
+struct PlayerIds {
+    ids: HashMap<SmallString, String>,
+    counts: HashMap<String, u32>,
+}
+
+impl PlayerIds {
+    fn id(&mut self, name: &SmallString) -> String {
+        if let Some(id) = self.ids.get(name) {
+            return id.clone();
+        }
+
+        let mut words = name.split_whitespace();
+        let first = words.next().unwrap_or_default();
+        let last = words.last().unwrap_or(first);
+        let letters = |word: &str| word.chars().filter(char::is_ascii_alphabetic).map(|c| c.to_ascii_lowercase()).collect::<String>();
+        let prefix = format!("{:-<4.4}{:-<1.1}", letters(last), letters(first));
+
+        let count = self.counts.entry(prefix.clone()).or_default();
+        *count += 1;
+        let id = format!("{}{:03}", prefix, count);
+        self.ids.insert(name.clone(), id.clone());
+
+        id
+    }
+}
+
+/// Convert a game to a Retrosheet event file: `id`, `version` and `info` records, a `start` record for each player in
+/// the starting lineups, and a `play` record for each play. Player ids are made up from names, the count and pitches
+/// are unknown (`??`), and team ids stand in for team codes.
+pub fn to_retrosheet(game: &Game) -> String {
+    let mut ids = PlayerIds::default();
+    let mut lines = vec![
+        format!("id,{}{}0", game.home_team.team_id, game.context.date.replace('-', "")),
+        "version,2".to_string(),
+        format!("info,visteam,{}", game.away_team.team_id),
+        format!("info,hometeam,{}", game.home_team.team_id),
+        format!("info,date,{}", game.context.date.replace('-', "/")),
+        format!("info,site,{}", game.context.venue),
+    ];
+
+    let weather = game.context.weather.in_units(UnitSystem::Imperial);
+    lines.push(format!("info,temp,{}", weather.temperature.round()));
+    lines.push(format!("info,windspeed,{}", weather.wind_speed.round()));
+    lines.push(format!("info,sky,{}", sky(&weather.condition)));
+
+    for (team_code, team, top_bottom) in [(0, &game.away_team, TopBottom::Top), (1, &game.home_team, TopBottom::Bottom)] {
+        for (player, batting_order) in starters(game, team, top_bottom) {
+            lines.push(format!(
+                "start,{},\"{}\",{},{},{}",
+                ids.id(&player.0),
+                player.0,
+                team_code,
+                batting_order,
+                player.1.number().unwrap_or(0),
+            ));
+        }
+    }
+
+    // each team's alignment, by team code, to find where the players in a switch or substitution end up
+    let mut alignments = [&game.away_team, &game.home_team].map(|team| DefensiveAlignment::from_roster(&team.players));
+    for (index, play) in game.plays.iter().enumerate() {
+        if play.play_content.play_type() == PlayType::GameAdvisory {
+            continue;
+        }
+        let alignment = match play.inning.top_bottom {
+            TopBottom::Top => &mut alignments[1],
+            TopBottom::Bottom => &mut alignments[0],
+        };
+        alignment.record_play(play);
+        if play.play_content.position().is_some() {
+            // the fielding team makes the change, and each fielder's place in the batting order isn't known. a fielder
+            // taken out of the game has no new position
+            let team_code = match play.inning.top_bottom {
+                TopBottom::Top => 1,
+                TopBottom::Bottom => 0,
+            };
+            for fielder in play.play_content.fielders() {
+                if let Some(position) = alignment.position_of(fielder) {
+                    lines.push(format!("sub,{},\"{}\",{},0,{}", ids.id(fielder), fielder, team_code, position));
+                }
+            }
+            continue;
+        }
+
+        // a play without a batter, such as a stolen base, happens during the next plate appearance of the half-inning
+        let batter = game.plays[index..].iter()
+            .take_while(|next| next.inning == play.inning)
+            .find_map(|next| next.play_content.batter())
+            .map_or(String::new(), |batter| ids.id(batter));
+        let (team_code, fielding_team) = match play.inning.top_bottom {
+            TopBottom::Top => (0, &game.home_team),
+            TopBottom::Bottom => (1, &game.away_team),
+        };
+
+        lines.push(format!(
+            "play,{},{},{},??,,{}",
+            play.inning.number,
+            team_code,
+            batter,
+            event(play, fielding_team),
+        ));
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Return each team's starters with their batting order: the first nine players to bat, and a starting pitcher who
+/// doesn't bat, with batting order 0.
+fn starters(game: &Game, team: &Team, top_bottom: TopBottom) -> Vec<((SmallString, Position), usize)> {
+    let mut batters = Vec::<&SmallString>::new();
+    for play in game.plays.iter().filter(|play| play.inning.top_bottom == top_bottom) {
+        if let Some(batter) = play.play_content.batter() {
+            if !batters.contains(&batter) {
+                batters.push(batter);
+            }
+        }
+        if batters.len() == 9 {
+            break;
+        }
+    }
+
+    let position = |name: &SmallString| team.players.iter()
+        .find(|player| &player.name == name)
+        .map_or(Position::DesignatedHitter, |player| player.position);
+    let mut starters = batters.iter()
+        .enumerate()
+        .map(|(index, &name)| ((name.clone(), position(name)), index + 1))
+        .collect::<Vec<_>>();
+
+    let pitcher = team.players.iter()
+        .find(|player| matches!(player.position, Position::Pitcher | Position::StartingPitcher));
+    if let Some(pitcher) = pitcher.filter(|pitcher| !batters.contains(&&pitcher.name)) {
+        starters.push(((pitcher.name.clone(), pitcher.position), 0));
+    }
+
+    starters
+}
+
+/// Return the event field of a play record: the play itself, then any runner advances, e.g. `S7.2-H;B-1`.
+fn event(play: &Play, fielding_team: &Team) -> String {
+    let content = &play.play_content;
+    let fielders = content.fielders().iter()
+        .filter_map(|name| fielding_team.players.iter().find(|player| &player.name == name))
+        .filter_map(|player| player.position.number())
+        .map(|position| position.to_string())
+        .collect::<String>();
+    let first_fielder = fielders.chars().next().map_or(String::new(), String::from);
+    // the fielder charged with an error, if named, or else the first fielder to touch the ball
+    let error_fielder = content.error_by()
+        .and_then(|name| fielding_team.players.iter().find(|player| &player.name == name))
+        .and_then(|player| player.position.number())
+        .map_or(first_fielder.clone(), |position| position.to_string());
+    let base = content.base().map_or("", base_code);
+
+    let description = match content.play_type() {
+        PlayType::Groundout => format!("{}/G", fielders),
+        PlayType::BuntGroundout => format!("{}/BG", fielders),
+        PlayType::Lineout => format!("{}/L", fielders),
+        PlayType::BuntLineout => format!("{}/BL", fielders),
+        PlayType::Flyout => format!("{}/F", fielders),
+        PlayType::PopOut => format!("{}/P", fielders),
+        PlayType::BuntPopOut => format!("{}/BP", fielders),
+        PlayType::Forceout => format!("{}/FO", fielders),
+        PlayType::FieldersChoiceOut | PlayType::FieldersChoice => format!("FC{}", first_fielder),
+        PlayType::DoublePlay | PlayType::RunnerDoublePlay => format!("{}/DP", fielders),
+        PlayType::TriplePlay | PlayType::RunnerTriplePlay => format!("{}/TP", fielders),
+        PlayType::GroundedIntoDoublePlay => format!("{}/GDP", fielders),
+        PlayType::Strikeout => "K".to_string(),
+        PlayType::StrikeoutDoublePlay => "K/DP".to_string(),
+        PlayType::Pickoff => format!("PO{}", base),
+        PlayType::PickoffError => format!("PO{}(E{})", base, error_fielder),
+        PlayType::CaughtStealing => format!("CS{}({})", base, fielders),
+        PlayType::PickoffCaughtStealing => format!("POCS{}({})", base, fielders),
+        PlayType::StolenBase => format!("SB{}", base),
+        PlayType::WildPitch => "WP".to_string(),
+        PlayType::PassedBall => "PB".to_string(),
+        PlayType::Balk => "BK".to_string(),
+        PlayType::RunnerOut | PlayType::FieldOut | PlayType::BatterOut if !fielders.is_empty() => fielders,
+        PlayType::RunnerOut | PlayType::FieldOut | PlayType::BatterOut => "OA".to_string(),
+        PlayType::Error | PlayType::FieldError => format!("E{}", error_fielder),
+        PlayType::Single => format!("S{}", first_fielder),
+        PlayType::Double => format!("D{}", first_fielder),
+        PlayType::Triple => format!("T{}", first_fielder),
+        PlayType::HomeRun => "HR".to_string(),
+        PlayType::Walk => "W".to_string(),
+        PlayType::IntentWalk => "IW".to_string(),
+        PlayType::HitByPitch => "HP".to_string(),
+        PlayType::CatcherInterference => "C/E2".to_string(),
+        PlayType::SacFly => format!("{}/SF", fielders),
+        PlayType::SacFlyDoublePlay => format!("{}/SF/DP", fielders),
+        PlayType::SacBunt => format!("{}/SH", fielders),
+        PlayType::SacBuntDoublePlay => format!("{}/SH/DP", fielders),
+        PlayType::DefensiveSwitch | PlayType::DefensiveSub | PlayType::GameAdvisory => "NP".to_string(),
+    };
+
+    // the batter being put out before reaching base is already part of the play
+    let advances = RunnerPositions::empty().simplify_movements(&play.movements).iter()
+        .filter(|movement| !(movement.from == Base::Home && movement.to == Base::Home && movement.out))
+        .map(|movement| format!(
+            "{}{}{}{}",
+            if movement.from == Base::Home { "B" } else { base_code(movement.from) },
+            if movement.out { "X" } else { "-" },
+            base_code(movement.out_at.unwrap_or(movement.to)),
+            if movement.credits.is_empty() {
+                String::new()
+            } else {
+                format!("({})", movement.credits.iter().map(|position| position.to_string()).collect::<String>())
+            },
+        ))
+        .collect::<Vec<_>>();
+
+    if advances.is_empty() {
+        description
+    } else {
+        format!("{}.{}", description, advances.join(";"))
+    }
+}
+
+fn base_code(base: Base) -> &'static str {
+    match base {
+        Base::First => "1",
+        Base::Second => "2",
+        Base::Third => "3",
+        Base::Home => "H",
+    }
+}
+
+fn sky(condition: &WeatherCondition) -> &'static str {
+    match condition {
+        WeatherCondition::Clear | WeatherCondition::Sunny => "sunny",
+        WeatherCondition::PartlyCloudy | WeatherCondition::Cloudy => "cloudy",
+        WeatherCondition::Overcast | WeatherCondition::Drizzle | WeatherCondition::Rain | WeatherCondition::Snow => "overcast",
+        WeatherCondition::Dome | WeatherCondition::RoofClosed => "dome",
+        WeatherCondition::Other(_) => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn export_event_file() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+
+        let retrosheet = to_retrosheet(&game);
+        let lines = retrosheet.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], format!("id,{}{}0", game.home_team.team_id, game.context.date.replace('-', "")));
+        assert!(lines.contains(&"version,2"));
+
+        let plays = lines.iter().filter(|line| line.starts_with("play,")).collect::<Vec<_>>();
+        assert_eq!(plays[0], &"play,1,0,hampg001,??,,K");
+        let starters = lines.iter().filter(|line| line.starts_with("start,")).count();
+        assert!(starters >= 18);
+    }
+
+    #[test]
+    fn fielding_changes_become_sub_records() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(concat!(
+            "[GAME] 1 [DATE] 2024-04-01 [VENUE] example [WEATHER] Clear 70 5\n\n",
+            "[TEAM] 1\n[PITCHER] Person A\n[SECOND_BASE] Person G\n[SHORTSTOP] Person B\n[UTILITY] Person J\n\n",
+            "[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n",
+            "[INNING] 1 top [PLAY] Defensive Sub [FIELDERS] Person J, Person B [POSITION] 6 [MOVEMENTS] ;\n",
+            "[INNING] 1 top [PLAY] Defensive Switch [FIELDERS] Person J, Person G [POSITION] 4 [MOVEMENTS] ;\n",
+        )).unwrap();
+        let game = parser.game_builder.build().unwrap();
+
+        let retrosheet = to_retrosheet(&game);
+        let subs = retrosheet.lines().filter(|line| line.starts_with("sub,")).collect::<Vec<_>>();
+        assert_eq!(subs, [
+            "sub,j---p001,\"Person J\",1,0,6",
+            "sub,j---p001,\"Person J\",1,0,4",
+            "sub,g---p001,\"Person G\",1,0,6",
+        ]);
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        let mut ids = PlayerIds::default();
+        assert_eq!(ids.id(&"Aaron Judge".into()), "judga001");
+        assert_eq!(ids.id(&"Aaron Judgement".into()), "judga002");
+        assert_eq!(ids.id(&"Aaron Judge".into()), "judga001");
+        assert_eq!(ids.id(&"Ji Li".into()), "li--j001");
+    }
+}
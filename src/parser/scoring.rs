@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::pyclass;
+
+use super::game::{Base, Game, Inning, Play, PlayType, TopBottom};
+use super::small_string::SmallString;
+use super::state::{LiveGameState, RunnerPositions};
+
+/// A run, charged to the pitcher who put the runner on base.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChargedRun {
+    pub inning: Inning,
+    pub runner: SmallString,
+    pub pitcher: SmallString,
+    pub earned: bool,
+}
+
+/// The pitchers credited with the win, loss, and save. All are `None` if the game ended in a tie.
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Decisions {
+    #[pyo3(get)]
+    pub winning_pitcher: Option<String>,
+    #[pyo3(get)]
+    pub losing_pitcher: Option<String>,
+    #[pyo3(get)]
+    pub save: Option<String>,
+}
+
+/// Whether a play is an error charged to the fielding team.
+pub fn is_error(play_type: PlayType) -> bool {
+    matches!(
+        play_type,
+        PlayType::Error |
+        PlayType::FieldError |
+        PlayType::PickoffError |
+        PlayType::CatcherInterference
+    )
+}
+
+/// Return the number of errors charged to the (away, home) team.
+pub fn team_errors(game: &Game) -> (u64, u64) {
+    let mut errors = (0, 0);
+    for play in game.plays.iter().filter(|play| is_error(play.play_content.play_type())) {
+        // the fielding team commits the error
+        match play.inning.top_bottom {
+            TopBottom::Top => errors.1 += 1,
+            TopBottom::Bottom => errors.0 += 1,
+        }
+    }
+
+    errors
+}
+
+/// Charge every run in the game to a pitcher, and decide whether it was earned.
+///
+/// A run is charged to the pitcher who faced the runner when they reached base. It's unearned if the runner reached on
+/// an error or catcher's interference, if it scored on an error or passed ball, or if it scored after the fielding team
+/// would have made the third out without its errors. This follows official scoring, except that reaching base on an
+/// error is the only way a runner's later advances are treated as unearned.
+pub fn charged_runs(game: &Game) -> Vec<ChargedRun> {
+    let mut runs = Vec::new();
+    // the pitcher each runner on base is charged to, and whether they reached base without an error
+    let mut responsible = HashMap::<SmallString, (SmallString, bool)>::new();
+    let mut pitcher = None::<SmallString>;
+    let mut inning = None;
+    // outs made in the half-inning, plus the errors that would have been outs
+    let mut outs_and_errors = 0;
+
+    for play in &game.plays {
+        if inning != Some(play.inning) {
+            responsible.clear();
+            outs_and_errors = 0;
+            inning = Some(play.inning);
+        }
+        if let Some(play_pitcher) = play.play_content.pitcher() {
+            pitcher = Some(play_pitcher.clone());
+        }
+        let Some(pitcher) = &pitcher else {
+            continue;
+        };
+
+        let play_type = play.play_content.play_type();
+        let reached_on_error = matches!(play_type, PlayType::FieldError | PlayType::CatcherInterference);
+        let movements = RunnerPositions::empty().simplify_movements(&play.movements);
+        let inning_over = outs_and_errors >= 3;
+        let scored_without_error = !is_error(play_type) && play_type != PlayType::PassedBall && !inning_over;
+        for movement in &movements {
+            if movement.to == Base::Home && !movement.out {
+                let (charged_to, reached_cleanly) = responsible.remove(&movement.runner)
+                    .unwrap_or_else(|| (pitcher.clone(), !reached_on_error));
+                runs.push(ChargedRun {
+                    inning: play.inning,
+                    runner: movement.runner.clone(),
+                    pitcher: charged_to,
+                    earned: reached_cleanly && scored_without_error,
+                });
+            } else if movement.out {
+                responsible.remove(&movement.runner);
+            } else if movement.from == Base::Home {
+                let reached_cleanly = !reached_on_error && !inning_over;
+                responsible.insert(movement.runner.clone(), (pitcher.clone(), reached_cleanly));
+            }
+        }
+
+        outs_and_errors += movements.iter().filter(|movement| movement.out).count();
+        if is_error(play_type) {
+            outs_and_errors += 1;
+        }
+    }
+
+    runs
+}
+
+/// Decide the winning, losing, and saving pitchers.
+///
+/// The winner is the winning team's pitcher of record when it took the lead for good, and the loser is the pitcher
+/// charged with the go-ahead run. The winning team's last pitcher earns a save if they didn't get the win and either
+/// entered with a lead of at most three runs or got at least nine outs. Unlike official scoring, a starter who leaves
+/// before the fifth inning can still get the win.
+pub fn decisions(game: &Game) -> Result<Decisions, String> {
+    let pinch_runners = game.pinch_runners();
+    let mut state = LiveGameState::new();
+    // the (away, home) score after each play
+    let mut scores = Vec::with_capacity(game.plays.len());
+    for play in &game.plays {
+        state.set_inning(play.inning);
+        state.process_play(play, &pinch_runners, false)
+            .map_err(|e| format!("Inning {}: {}", play.inning.to_string(), e))?;
+        scores.push((state.away_team_score, state.home_team_score));
+    }
+
+    let (away, home) = (state.away_team_score, state.home_team_score);
+    // the half-inning the winning team bats in
+    let winners_bat = match away.cmp(&home) {
+        std::cmp::Ordering::Equal => return Ok(Decisions::default()),
+        std::cmp::Ordering::Greater => TopBottom::Top,
+        std::cmp::Ordering::Less => TopBottom::Bottom,
+    };
+    let losing_score = away.min(home);
+    let winners_score = |(away, home): (u64, u64)| if winners_bat == TopBottom::Top { away } else { home };
+    let lead = |(away, home): (u64, u64)| match winners_bat {
+        TopBottom::Top => away as i64 - home as i64,
+        TopBottom::Bottom => home as i64 - away as i64,
+    };
+    let winners_pitcher = |play| fielding_pitcher(play, winners_bat);
+
+    // the go-ahead run is the one more than the losing team's final score, so the lead was never given up after it
+    let go_ahead = scores.iter().position(|&score| winners_score(score) > losing_score)
+        .ok_or("The winning team never took the lead".to_string())?;
+    let winning_pitcher = game.plays[..=go_ahead].iter().rev().find_map(winners_pitcher)
+        // the winning team took the lead before it pitched, so the starter gets the win
+        .or_else(|| game.plays.iter().find_map(winners_pitcher));
+    let losing_pitcher = charged_runs(game).into_iter()
+        .filter(|run| run.inning.top_bottom == winners_bat)
+        .nth(losing_score as usize)
+        .map(|run| run.pitcher);
+
+    let finisher = game.plays.iter().rev().find_map(winners_pitcher);
+    let save = finisher.filter(|finisher| Some(*finisher) != winning_pitcher).filter(|finisher| {
+        let entry = game.plays.iter().position(|play| winners_pitcher(play) == Some(finisher)).unwrap();
+        let lead_on_entry = lead(if entry == 0 { (0, 0) } else { scores[entry - 1] });
+        let outs = game.plays.iter()
+            .filter(|play| winners_pitcher(play) == Some(finisher))
+            .map(|play| RunnerPositions::empty().simplify_movements(&play.movements).iter().filter(|movement| movement.out).count())
+            .sum::<usize>();
+
+        lead_on_entry > 0 && (lead_on_entry <= 3 || outs >= 9)
+    });
+
+    Ok(Decisions {
+        winning_pitcher: winning_pitcher.map(|pitcher| pitcher.to_string()),
+        losing_pitcher: losing_pitcher.map(|pitcher| pitcher.to_string()),
+        save: save.map(|pitcher| pitcher.to_string()),
+    })
+}
+
+/// Return the play's pitcher if the team that bats in the `batting` half is in the field.
+fn fielding_pitcher(play: &Play, batting: TopBottom) -> Option<&SmallString> {
+    if play.inning.top_bottom == batting {
+        return None;
+    }
+
+    play.play_content.pitcher()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn parse_game(plays: &str) -> Game {
+        let mut parser = Parser::new(false);
+        parser.parse_input(&format!(
+            "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n[SHORTSTOP] Person B\n\n[TEAM] 2\n[PITCHER] Person C\n[PITCHER] Person H\n\n[GAME_START]\n{}[GAME_END]",
+            plays,
+        )).unwrap();
+        parser.complete().unwrap()
+    }
+
+    #[test]
+    fn runs_after_an_error_are_unearned() {
+        let game = parse_game(concat!(
+            "[INNING] 1 top [PLAY] Field Error [BATTER] Person D [PITCHER] Person A [FIELDERS] Person B [ERROR_BY] Person B [MOVEMENTS] Person D home -> 1;\n",
+            "[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home, Person D 1 -> home;\n",
+        ));
+        assert_eq!(game.plays[0].play_content.error_by().map(|name| name.as_str()), Some("Person B"));
+        assert_eq!(team_errors(&game), (0, 1));
+
+        let runs = charged_runs(&game);
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|run| run.pitcher == "Person A"));
+        assert!(!runs.iter().find(|run| run.runner == "Person D").unwrap().earned);
+        assert!(runs.iter().find(|run| run.runner == "Person E").unwrap().earned);
+    }
+
+    #[test]
+    fn decisions_follow_the_lead() {
+        let game = parse_game(concat!(
+            "[INNING] 1 top [PLAY] Home Run [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> home;\n",
+            "[INNING] 1 bottom [PLAY] Strikeout [BATTER] Person B [PITCHER] Person C [MOVEMENTS] Person B home -> home [out];\n",
+            "[INNING] 2 bottom [PLAY] Strikeout [BATTER] Person A [PITCHER] Person H [MOVEMENTS] Person A home -> home [out];\n",
+        ));
+        assert_eq!(decisions(&game).unwrap(), Decisions {
+            winning_pitcher: Some("Person C".to_string()),
+            losing_pitcher: Some("Person A".to_string()),
+            save: Some("Person H".to_string()),
+        });
+
+        let game = parse_game("[INNING] 1 bottom [PLAY] Strikeout [BATTER] Person B [PITCHER] Person C [MOVEMENTS] Person B home -> home [out];\n");
+        assert_eq!(decisions(&game).unwrap(), Decisions::default());
+    }
+
+    #[test]
+    fn runs_after_the_third_out_would_have_been_made_are_unearned() {
+        let game = parse_game(concat!(
+            "[INNING] 1 top [PLAY] Strikeout [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> home [out];\n",
+            "[INNING] 1 top [PLAY] Strikeout [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home [out];\n",
+            "[INNING] 1 top [PLAY] Field Error [BATTER] Person F [PITCHER] Person A [FIELDERS] Person B [MOVEMENTS] Person F home -> 1;\n",
+            "[INNING] 1 top [PLAY] Home Run [BATTER] Person G [PITCHER] Person A [MOVEMENTS] Person G home -> home, Person F 1 -> home;\n",
+        ));
+
+        let runs = charged_runs(&game);
+        assert_eq!(runs.len(), 2);
+        assert!(runs.iter().all(|run| !run.earned));
+    }
+}
@@ -0,0 +1,140 @@
+use std::borrow::Borrow;
+use std::convert::Infallible;
+use std::fmt;
+use std::ops::Deref;
+
+use compact_str::CompactString;
+use pyo3::{prelude::{Bound, FromPyObject, IntoPyObject, PyAny, PyAnyMethods, PyResult, Python}, types::PyString};
+use serde::{Deserialize, Serialize};
+
+/// A string stored inline when it's short, as most names are, so holding many games doesn't mean many small
+/// allocations. It converts to and from a Python `str`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SmallString(CompactString);
+
+impl SmallString {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl Deref for SmallString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SmallString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for SmallString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for SmallString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<&str> for SmallString {
+    fn from(s: &str) -> Self {
+        Self(CompactString::new(s))
+    }
+}
+
+impl From<String> for SmallString {
+    fn from(s: String) -> Self {
+        Self(CompactString::from(s))
+    }
+}
+
+impl From<&String> for SmallString {
+    fn from(s: &String) -> Self {
+        Self::from(s.as_str())
+    }
+}
+
+impl From<SmallString> for String {
+    fn from(s: SmallString) -> Self {
+        s.0.into_string()
+    }
+}
+
+impl PartialEq<str> for SmallString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for SmallString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for SmallString {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<SmallString> for String {
+    fn eq(&self, other: &SmallString) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl PartialEq<SmallString> for &str {
+    fn eq(&self, other: &SmallString) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl<'py> IntoPyObject<'py> for SmallString {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        Ok(PyString::new(py, self.as_str()))
+    }
+}
+
+impl<'py> IntoPyObject<'py> for &SmallString {
+    type Target = PyString;
+    type Output = Bound<'py, PyString>;
+    type Error = Infallible;
+
+    fn into_pyobject(self, py: Python<'py>) -> Result<Self::Output, Self::Error> {
+        Ok(PyString::new(py, self.as_str()))
+    }
+}
+
+impl FromPyObject<'_> for SmallString {
+    fn extract_bound(ob: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(Self::from(ob.extract::<String>()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_names_are_inline() {
+        let name = SmallString::from("Shohei Ohtani");
+        assert!(!name.0.is_heap_allocated());
+        assert_eq!(name, "Shohei Ohtani");
+        assert_eq!(String::from(name.clone()), "Shohei Ohtani".to_string());
+        assert_eq!(serde_json::to_string(&name).unwrap(), "\"Shohei Ohtani\"");
+    }
+}
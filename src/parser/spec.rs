@@ -0,0 +1,203 @@
+use pyo3::prelude::{pyfunction, PyObject, PyResult, Python};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use super::export::{to_py_object, ExportOptions};
+use super::game::{PlayType, Position, SpeedUnit, TemperatureUnit, WeatherCondition};
+use super::{
+    escape_regex, BASE_NAME, COMMA_SPACE, CONTEXT_SECTION_DATE_REGEX, CONTEXT_SECTION_GAME_REGEX, CONTEXT_SECTION_VENUE_REGEX,
+    CONTEXT_SECTION_WEATHER_REGEX, LINE_BREAK, MOVEMENT_OUT, PLAYER_NAME, PLAY_SECTION_ARROW, PLAY_SECTION_BASE_REGEX,
+    PLAY_SECTION_BATTER_REGEX, PLAY_SECTION_CATCHER_REGEX, PLAY_SECTION_ERROR_BY_REGEX, PLAY_SECTION_FIELDERS_TAG,
+    PLAY_SECTION_GAME_END, PLAY_SECTION_GAME_START, PLAY_SECTION_INNING_REGEX, PLAY_SECTION_META_REGEX, PLAY_SECTION_MOVEMENTS_TAG,
+    PLAY_SECTION_ON_ERROR, PLAY_SECTION_OVERTURNED, PLAY_SECTION_PITCHER_REGEX, PLAY_SECTION_PLAY_END,
+    PLAY_SECTION_PLAY_REGEX, PLAY_SECTION_POSITION_REGEX, PLAY_SECTION_RUNNER_REGEX, PLAY_SECTION_SCORING_RUNNER_REGEX, TEAM_SECTION_PLAYER_REGEX,
+    TEAM_SECTION_TEAM_REGEX,
+};
+
+/// A machine-readable description of the format, built from the regexes and play type rules the parser uses.
+#[derive(Clone, Debug, Serialize)]
+pub struct FormatSpec {
+    /// The context tags, in order, on the first line.
+    pub context: Vec<TagSpec>,
+    pub weather_conditions: Vec<String>,
+    pub temperature_units: Vec<String>,
+    pub speed_units: Vec<String>,
+    /// The two team sections, home team first.
+    pub team: TeamSpec,
+    pub plays: PlaysSpec,
+    pub play_types: Vec<PlayTypeSpec>,
+    pub movements: MovementsSpec,
+    pub player_name: String,
+    pub line_break: String,
+}
+
+/// A tag and a regex matching the tag along with its value.
+#[derive(Clone, Debug, Serialize)]
+pub struct TagSpec {
+    pub tag: String,
+    pub pattern: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TeamSpec {
+    pub team: TagSpec,
+    /// The tags a player can be listed under, one player per line.
+    pub positions: Vec<String>,
+    pub player: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct PlaysSpec {
+    pub game_start: String,
+    pub game_end: String,
+    /// The tag before a play that was overturned on review, which is followed by the play that replaced it.
+    pub overturned: String,
+    pub play_end: String,
+    /// Every tag a play can have, in the order they must appear. Which are required depends on the play type.
+    pub tags: Vec<TagSpec>,
+}
+
+/// The tags a play type requires or allows, besides `[INNING]`, `[PLAY]` and `[MOVEMENTS]`.
+#[derive(Clone, Debug, Serialize)]
+pub struct PlayTypeSpec {
+    pub name: String,
+    pub required: Vec<String>,
+    pub optional: Vec<String>,
+    /// Whether the play ends right after its type, with no `[MOVEMENTS]` or end of play, e.g. a game advisory.
+    pub no_movements: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MovementsSpec {
+    pub tag: String,
+    /// How a movement is written, e.g. `Person A 1 -> 2 [out at 2 6-4]`.
+    pub syntax: String,
+    pub separator: String,
+    pub base: String,
+    pub arrow: String,
+    pub on_error: String,
+    pub out: String,
+}
+
+fn tag_spec(tag: &str, pattern: &str) -> TagSpec {
+    TagSpec {
+        tag: format!("[{}]", tag),
+        pattern: pattern.trim_start_matches('^').to_string(),
+    }
+}
+
+fn play_type_spec(play_type: PlayType) -> PlayTypeSpec {
+    let tags = [
+        ("[BASE]", play_type.requires_base()),
+        ("[BATTER]", play_type.requires_batter()),
+        ("[PITCHER]", play_type.requires_pitcher()),
+        ("[CATCHER]", play_type.requires_catcher()),
+        (PLAY_SECTION_FIELDERS_TAG, play_type.requires_fielders()),
+        ("[POSITION]", play_type.requires_position()),
+        ("[RUNNER]", play_type.requires_runner()),
+        ("[SCORING_RUNNER]", play_type.requires_scoring_runner()),
+    ];
+    let required = tags.iter()
+        .filter(|(_, required)| *required)
+        .map(|(tag, _)| tag.to_string())
+        .collect();
+    let optional = [("[BASE]", play_type.allows_base()), ("[ERROR_BY]", play_type.allows_error_by())].iter()
+        .filter(|(_, allowed)| *allowed)
+        .map(|(tag, _)| tag.to_string())
+        .collect();
+
+    PlayTypeSpec {
+        name: play_type.to_string(),
+        required,
+        optional,
+        no_movements: play_type == PlayType::GameAdvisory,
+    }
+}
+
+/// Describe the format the parser accepts.
+pub fn format_spec() -> FormatSpec {
+    FormatSpec {
+        context: vec![
+            tag_spec("GAME", CONTEXT_SECTION_GAME_REGEX.as_str()),
+            tag_spec("DATE", CONTEXT_SECTION_DATE_REGEX.as_str()),
+            tag_spec("VENUE", CONTEXT_SECTION_VENUE_REGEX.as_str()),
+            tag_spec("WEATHER", CONTEXT_SECTION_WEATHER_REGEX.as_str()),
+        ],
+        weather_conditions: WeatherCondition::KNOWN.iter().map(|condition| condition.to_string()).collect(),
+        temperature_units: [TemperatureUnit::Fahrenheit, TemperatureUnit::Celsius].iter().map(|unit| unit.to_string()).collect(),
+        speed_units: [SpeedUnit::MilesPerHour, SpeedUnit::KilometersPerHour].iter().map(|unit| unit.to_string()).collect(),
+        team: TeamSpec {
+            team: tag_spec("TEAM", TEAM_SECTION_TEAM_REGEX.as_str()),
+            positions: Position::iter().map(|position| format!("[{}]", position.to_string())).collect(),
+            player: TEAM_SECTION_PLAYER_REGEX.as_str().trim_start_matches('^').to_string(),
+        },
+        plays: PlaysSpec {
+            game_start: PLAY_SECTION_GAME_START.to_string(),
+            game_end: PLAY_SECTION_GAME_END.to_string(),
+            overturned: PLAY_SECTION_OVERTURNED.to_string(),
+            play_end: PLAY_SECTION_PLAY_END.to_string(),
+            tags: vec![
+                tag_spec("INNING", PLAY_SECTION_INNING_REGEX.as_str()),
+                tag_spec("PLAY", PLAY_SECTION_PLAY_REGEX.as_str()),
+                tag_spec("BASE", PLAY_SECTION_BASE_REGEX.as_str()),
+                tag_spec("BATTER", PLAY_SECTION_BATTER_REGEX.as_str()),
+                tag_spec("PITCHER", PLAY_SECTION_PITCHER_REGEX.as_str()),
+                tag_spec("CATCHER", PLAY_SECTION_CATCHER_REGEX.as_str()),
+                tag_spec("FIELDERS", &format!("{} {}({}{})*", escape_regex(PLAY_SECTION_FIELDERS_TAG), PLAYER_NAME, COMMA_SPACE, PLAYER_NAME)),
+                tag_spec("RUNNER", PLAY_SECTION_RUNNER_REGEX.as_str()),
+                tag_spec("SCORING_RUNNER", PLAY_SECTION_SCORING_RUNNER_REGEX.as_str()),
+                tag_spec("ERROR_BY", PLAY_SECTION_ERROR_BY_REGEX.as_str()),
+                tag_spec("POSITION", PLAY_SECTION_POSITION_REGEX.as_str()),
+                tag_spec("MOVEMENTS", &escape_regex(PLAY_SECTION_MOVEMENTS_TAG)),
+                tag_spec("META", PLAY_SECTION_META_REGEX.as_str()),
+            ],
+        },
+        play_types: PlayType::iter().map(play_type_spec).collect(),
+        movements: MovementsSpec {
+            tag: PLAY_SECTION_MOVEMENTS_TAG.to_string(),
+            syntax: format!("<runner> <base> {} <base>[ {}][ <out>]", PLAY_SECTION_ARROW, PLAY_SECTION_ON_ERROR),
+            separator: COMMA_SPACE.to_string(),
+            // the parser allows stray spaces around a base, which generators shouldn't write
+            base: BASE_NAME.trim_start_matches(" ?").trim_end_matches(" ?").to_string(),
+            arrow: PLAY_SECTION_ARROW.to_string(),
+            on_error: PLAY_SECTION_ON_ERROR.to_string(),
+            out: MOVEMENT_OUT.to_string(),
+        },
+        player_name: PLAYER_NAME.to_string(),
+        line_break: LINE_BREAK.to_string(),
+    }
+}
+
+/// Return a machine-readable description of the format as nested dicts and lists: the tags of each section with
+/// regexes for their values, the tags each play type requires or allows, and the movement syntax. It's built from the
+/// parser's own definitions, so generators that read it stay in step with the parser.
+#[pyfunction(name = "format_spec")]
+pub fn py_format_spec(py: Python<'_>) -> PyResult<PyObject> {
+    to_py_object(py, &format_spec(), ExportOptions::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fancy_regex::Regex;
+
+    #[test]
+    fn spec_matches_the_parser() {
+        let spec = format_spec();
+        let weather = Regex::new(&format!("^{}$", spec.context[3].pattern)).unwrap();
+        assert!(weather.is_match("[WEATHER] Partly Cloudy 70F 5mph").unwrap());
+        assert!(spec.weather_conditions.contains(&"Roof Closed".to_string()));
+        assert!(spec.team.positions.contains(&"[PINCH_RUNNER]".to_string()));
+
+        let groundout = spec.play_types.iter().find(|play_type| play_type.name == "Groundout").unwrap();
+        assert_eq!(groundout.required, vec!["[BATTER]", "[PITCHER]", "[FIELDERS]"]);
+        assert_eq!(groundout.optional, Vec::<String>::new());
+        assert_eq!(spec.play_types.len(), PlayType::iter().count());
+        let runner_out = spec.play_types.iter().find(|play_type| play_type.name == "Runner Out").unwrap();
+        assert_eq!(runner_out.optional, vec!["[BASE]"]);
+
+        let out = Regex::new(&format!("^{}$", spec.movements.out)).unwrap();
+        assert!(out.is_match("[out at 2 6-4]").unwrap());
+        assert!(Regex::new(&format!("^{}$", spec.movements.base)).unwrap().is_match("home").unwrap());
+    }
+}
@@ -0,0 +1,626 @@
+use std::collections::{HashMap, HashSet};
+
+use pyo3::prelude::{pyclass, pymethods};
+
+use super::diagnostics::{Diagnostic, DiagnosticKind};
+use super::game::{Base, BaseComparison, Inning, Movement, Play, PlayContent, PlayType, Player, Position, TopBottom};
+use super::small_string::SmallString;
+
+#[derive(Clone, Debug)]
+pub struct RunnerPositions {
+    pub first: Option<SmallString>,
+    pub second: Option<SmallString>,
+    pub third: Option<SmallString>,
+}
+
+impl RunnerPositions {
+    pub fn empty() -> Self {
+        Self {
+            first: None,
+            second: None,
+            third: None,
+        }
+    }
+
+    /// Return the runner on a base, if there is one.
+    pub fn runner_on(&self, base: Base) -> Option<&SmallString> {
+        match base {
+            Base::Home => None,
+            Base::First => self.first.as_ref(),
+            Base::Second => self.second.as_ref(),
+            Base::Third => self.third.as_ref(),
+        }
+    }
+
+    /// Return the base a runner is on, if they're on one.
+    pub fn base_of(&self, runner: &SmallString) -> Option<Base> {
+        [Base::First, Base::Second, Base::Third].into_iter().find(|base| self.runner_on(*base) == Some(runner))
+    }
+
+    /// Check each runner's movements, in the order they were written, form a single chain: every movement starts where
+    /// the last one ended, and nothing follows a movement that puts the runner out or brings them home.
+    pub fn validate_movements(movements: &[Movement]) -> Result<(), Diagnostic> {
+        for (index, movement) in movements.iter().enumerate() {
+            let Some(previous) = movements[..index].iter().rev().find(|m| m.runner == movement.runner) else {
+                continue;
+            };
+
+            if previous.out {
+                return Err(Diagnostic::new(DiagnosticKind::BrokenMovementChain, format!("Runner {} moves again after being put out", movement.runner)));
+            }
+            if previous.to == Base::Home {
+                return Err(Diagnostic::new(DiagnosticKind::BrokenMovementChain, format!("Runner {} moves again after reaching home", movement.runner)));
+            }
+            if movement.from != previous.to {
+                return Err(Diagnostic::new(DiagnosticKind::BrokenMovementChain, format!(
+                    "Runner {} moves {} -> {} and then {} -> {}",
+                    movement.runner,
+                    previous.from.to_string(),
+                    previous.to.to_string(),
+                    movement.from.to_string(),
+                    movement.to.to_string(),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Group any chains of movements by the same runner into a single movement, ordered by starting base and then runner.
+    pub fn simplify_movements(&self, movements: &[Movement]) -> Vec<Movement> {
+        let runners = HashSet::<SmallString>::from_iter(movements.iter().map(|m| m.runner.clone()));
+
+        let mut simplified_movements = Vec::new();
+        for runner in runners {
+            let froms = movements.iter().filter(|m| m.runner == runner).map(|m| m.from).collect::<Vec<_>>();
+            let tos = movements.iter().filter(|m| m.runner == runner).map(|m| m.to).collect::<Vec<_>>();
+
+            let from = froms.iter().min_by(|a, b| a.compare(b, BaseComparison::From)).unwrap();
+            let to = tos.iter().max_by(|a, b| a.compare(b, BaseComparison::To)).unwrap();
+
+            let out = movements.iter().any(|m| m.runner == runner && m.out);
+            let on_error = movements.iter().any(|m| m.runner == runner && m.on_error);
+            let out_at = movements.iter().find(|m| m.runner == runner && m.out_at.is_some()).and_then(|m| m.out_at);
+            let credits = movements.iter().find(|m| m.runner == runner && m.out).map_or(Vec::new(), |m| m.credits.clone());
+
+            simplified_movements.push(Movement { runner, from: *from, to: *to, out, on_error, out_at, credits });
+        }
+
+        // the runners come out of a set in any order, so sort them to keep the result the same from run to run
+        simplified_movements.sort_by(|a, b| a.from.compare(&b.from, BaseComparison::From).then_with(|| a.runner.cmp(&b.runner)));
+        simplified_movements
+    }
+
+    /// Put a pinch runner on a base in place of the runner on it.
+    pub fn substitute(&mut self, base: Base, pinch_runner: &SmallString) -> Result<(), Diagnostic> {
+        let on_base = match base {
+            Base::Home => return Err(Diagnostic::new(DiagnosticKind::RunnerNotOnBase, format!("Pinch runner {} cannot take over home", pinch_runner))),
+            Base::First => &mut self.first,
+            Base::Second => &mut self.second,
+            Base::Third => &mut self.third,
+        };
+        if on_base.is_none() {
+            return Err(Diagnostic::new(DiagnosticKind::RunnerNotOnBase, format!("Pinch runner {} has no runner on base {} to replace", pinch_runner, base.to_string())));
+        }
+        *on_base = Some(pinch_runner.clone());
+
+        Ok(())
+    }
+
+    /// Move the runners, returning the number of runs scored. A runner who reaches home scores and leaves the bases. A
+    /// pinch runner who isn't on base yet first takes over the base their movement starts from.
+    pub fn process_movements(&mut self, movements: &[Movement], pinch_runners: &[SmallString]) -> Result<u64, Diagnostic> {
+        // simplifying would hide a broken chain, so check the movements as written first
+        Self::validate_movements(movements)?;
+        let movements = self.simplify_movements(movements);
+
+        let mut on_base = self.clone();
+        for movement in &movements {
+            if movement.from != Base::Home && pinch_runners.contains(&movement.runner) && on_base.base_of(&movement.runner).is_none() {
+                on_base.substitute(movement.from, &movement.runner)?;
+            }
+        }
+
+        let mut new_runner_positions = on_base.clone();
+        let mut runs = 0;
+
+        // every runner who moves leaves their starting base, even if they are put out
+        for movement in &movements {
+            match movement.from {
+                Base::First => new_runner_positions.first = None,
+                Base::Second => new_runner_positions.second = None,
+                Base::Third => new_runner_positions.third = None,
+                Base::Home => (),
+            }
+        }
+
+        for movement in movements {
+            // check the bases are in the correct order
+            match (movement.from, movement.to) {
+                (Base::Third, Base::Second) => return Err(Diagnostic::new(DiagnosticKind::RunnerMovesBackwards, "Cannot move runner from third to second")),
+                (Base::Third, Base::First) => return Err(Diagnostic::new(DiagnosticKind::RunnerMovesBackwards, "Cannot move runner from third to first")),
+                (Base::Second, Base::First) => return Err(Diagnostic::new(DiagnosticKind::RunnerMovesBackwards, "Cannot move runner from second to first")),
+                _ => (),
+            }
+
+            // check the runner does exist on the starting base, any pinch runner having already taken theirs over
+            match movement.from {
+                Base::First => match &on_base.first {
+                    Some(runner) => if &movement.runner != runner {
+                        return Err(Diagnostic::new(DiagnosticKind::RunnerNotOnBase, format!("Runner {} is not on first base", movement.runner)));
+                    },
+                    None => return Err(Diagnostic::new(DiagnosticKind::RunnerNotOnBase, "No runner is on first base")),
+                },
+                Base::Second => match &on_base.second {
+                    Some(runner) => if &movement.runner != runner {
+                        return Err(Diagnostic::new(DiagnosticKind::RunnerNotOnBase, format!("Runner {} is not on second base", movement.runner)));
+                    },
+                    None => return Err(Diagnostic::new(DiagnosticKind::RunnerNotOnBase, "No runner is on second base")),
+                },
+                Base::Third => match &on_base.third {
+                    Some(runner) => if &movement.runner != runner {
+                        return Err(Diagnostic::new(DiagnosticKind::RunnerNotOnBase, format!("Runner {} is not on third base", movement.runner)));
+                    },
+                    None => return Err(Diagnostic::new(DiagnosticKind::RunnerNotOnBase, "No runner is on third base")),
+                },
+                Base::Home => (),
+            }
+
+            // if the runner is not out, move the runner to the new base
+            if !movement.out {
+                match movement.to {
+                    Base::First => new_runner_positions.first = Some(movement.runner.clone()),
+                    Base::Second => new_runner_positions.second = Some(movement.runner.clone()),
+                    Base::Third => new_runner_positions.third = Some(movement.runner.clone()),
+                    Base::Home => runs += 1,
+                }
+            }
+        }
+
+        // update the runner positions
+        *self = new_runner_positions;
+
+        Ok(runs)
+    }
+}
+
+const LINEUP_SIZE: usize = 9;
+
+/// A team's batting order, learned from the order its players first come to bat.
+#[derive(Clone, Debug, Default)]
+pub struct BattingOrder {
+    pub batters: Vec<SmallString>,
+    // the spot in the order that is due up next
+    pub next: usize,
+}
+
+impl BattingOrder {
+    /// Record a completed plate appearance. Once the order is full, a new batter takes the spot that was due up,
+    /// as a pinch hitter does.
+    pub fn record(&mut self, batter: &str) {
+        if let Some(index) = self.batters.iter().position(|b| b == batter) {
+            self.next = (index + 1) % LINEUP_SIZE;
+        } else if self.batters.len() < LINEUP_SIZE {
+            self.batters.push(batter.into());
+            self.next = self.batters.len() % LINEUP_SIZE;
+        } else {
+            self.batters[self.next] = batter.into();
+            self.next = (self.next + 1) % LINEUP_SIZE;
+        }
+    }
+
+    /// Return the batter due up next, once every spot in the order has batted.
+    pub fn due_batter(&self) -> Option<&SmallString> {
+        if self.batters.len() < LINEUP_SIZE {
+            return None;
+        }
+
+        self.batters.get(self.next)
+    }
+}
+
+// the positions in the field, by number, from the pitcher to right field
+const FIELD_POSITIONS: [Position; 9] = [
+    Position::Pitcher,
+    Position::Catcher,
+    Position::FirstBase,
+    Position::SecondBase,
+    Position::ThirdBase,
+    Position::Shortstop,
+    Position::LeftField,
+    Position::CenterField,
+    Position::RightField,
+];
+
+/// Which player is at each fielding position, starting from the roster and following substitutions: a pitcher or
+/// catcher named in a play, or a fielder at their roster position.
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct DefensiveAlignment {
+    // the player at each position, by number minus one
+    players: [Option<SmallString>; 9],
+    // each rostered fielder's position number
+    roster: HashMap<SmallString, u8>,
+    // players a defensive substitution has taken out of the game
+    left_game: HashSet<SmallString>,
+}
+
+impl DefensiveAlignment {
+    /// Start with the first player listed at each position.
+    pub fn from_roster(players: &[Player]) -> Self {
+        let mut alignment = Self::default();
+        for player in players {
+            let Some(number) = player.position.number().filter(|number| *number <= 9) else {
+                continue;
+            };
+            alignment.roster.entry(player.name.clone()).or_insert(number);
+            if alignment.players[number as usize - 1].is_none() && alignment.position_of(&player.name).is_none() {
+                alignment.players[number as usize - 1] = Some(player.name.clone());
+            }
+        }
+
+        alignment
+    }
+
+    /// Whether the alignment started from a roster, so that an empty position means no one is there.
+    pub fn has_roster(&self) -> bool {
+        !self.roster.is_empty()
+    }
+
+    /// Return the player at a position, numbered from 1 for the pitcher to 9 for right field.
+    pub fn player_at(&self, number: u8) -> Option<&SmallString> {
+        self.players.get((number as usize).checked_sub(1)?)?.as_ref()
+    }
+
+    /// Return the number of the position a player is at, if they're in the field.
+    pub fn position_of(&self, name: &str) -> Option<u8> {
+        self.players.iter().position(|player| player.as_deref() == Some(name)).map(|index| index as u8 + 1)
+    }
+
+    /// Whether a defensive substitution has taken a player out of the game.
+    pub fn has_left_game(&self, name: &str) -> bool {
+        self.left_game.contains(name)
+    }
+
+    /// Return the players in the field.
+    pub fn fielders(&self) -> impl Iterator<Item = &SmallString> {
+        self.players.iter().flatten()
+    }
+
+    /// Put a player at a position, moving them from wherever else they were.
+    pub fn substitute(&mut self, number: u8, name: &SmallString) {
+        if let Some(previous) = self.position_of(name) {
+            self.players[previous as usize - 1] = None;
+        }
+        self.players[number as usize - 1] = Some(name.clone());
+    }
+
+    /// Follow the substitutions a play implies for the fielding team.
+    pub fn record_play(&mut self, play: &Play) {
+        let content = &play.play_content;
+        match content {
+            PlayContent::DefensiveSwitch { fielders, position } => {
+                // the second fielder, if any, takes the position the first left
+                let left = fielders.first().and_then(|fielder| self.position_of(fielder));
+                if let Some(fielder) = fielders.first() {
+                    self.substitute(*position, fielder);
+                }
+                if let (Some(fielder), Some(number)) = (fielders.get(1), left) {
+                    self.substitute(number, fielder);
+                }
+                return;
+            },
+            PlayContent::DefensiveSub { fielders, position } => {
+                // the fielder replaced, if any, leaves the game
+                if let Some(replaced) = fielders.get(1) {
+                    if let Some(number) = self.position_of(replaced) {
+                        self.players[number as usize - 1] = None;
+                    }
+                    self.left_game.insert(replaced.clone());
+                }
+                if let Some(fielder) = fielders.first() {
+                    self.substitute(*position, fielder);
+                }
+                return;
+            },
+            _ => {},
+        }
+
+        if let Some(pitcher) = content.pitcher() {
+            self.substitute(1, pitcher);
+        }
+        if let Some(catcher) = content.catcher() {
+            self.substitute(2, catcher);
+        }
+        for fielder in content.fielders().iter().chain(content.error_by()) {
+            // a fielder already in the field may have moved, so only bring in fielders from the bench
+            if self.position_of(fielder).is_none() && !self.has_left_game(fielder) {
+                if let Some(&number) = self.roster.get(fielder) {
+                    self.substitute(number, fielder);
+                }
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl DefensiveAlignment {
+    /// Return the player at a position, numbered from 1 for the pitcher to 9 for right field.
+    #[pyo3(name = "player_at")]
+    fn py_player_at(&self, number: u8) -> Option<String> {
+        self.player_at(number).map(|player| player.to_string())
+    }
+
+    /// Return the number of the position a player is at, if they're in the field.
+    #[pyo3(name = "position_of")]
+    fn py_position_of(&self, name: &str) -> Option<u8> {
+        self.position_of(name)
+    }
+
+    /// Return the player at each filled position, keyed by position, e.g. `{"SHORTSTOP": "Bobby Witt Jr."}`.
+    fn to_dict(&self) -> HashMap<String, String> {
+        FIELD_POSITIONS.iter()
+            .zip(&self.players)
+            .filter_map(|(position, player)| Some((position.to_string(), player.as_ref()?.to_string())))
+            .collect()
+    }
+}
+
+/// The pinch runners on each team's roster.
+#[derive(Clone, Debug, Default)]
+pub struct PinchRunners {
+    pub home: Vec<SmallString>,
+    pub away: Vec<SmallString>,
+}
+
+impl PinchRunners {
+    /// Return the pinch runners on the roster of the team that bats in the given half of an inning.
+    pub fn batting(&self, top_bottom: TopBottom) -> &[SmallString] {
+        match top_bottom {
+            TopBottom::Top => &self.away,
+            TopBottom::Bottom => &self.home,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LiveGameState {
+    pub runner_positions: RunnerPositions,
+    pub inning: Inning,
+    pub outs: u64,
+    pub home_team_score: u64,
+    pub away_team_score: u64,
+    // the runs scored in each half-inning with a play so far, in order
+    pub inning_scores: Vec<(Inning, u64)>,
+    pub current_batter: Option<SmallString>,
+    pub current_pitcher: Option<SmallString>,
+    pub home_batting_order: BattingOrder,
+    pub away_batting_order: BattingOrder,
+    pub home_alignment: DefensiveAlignment,
+    pub away_alignment: DefensiveAlignment,
+    // pinch runners who have taken over a base, and so are regular runners from then on
+    pub entered_pinch_runners: HashSet<SmallString>,
+    // problems that strict mode would have rejected
+    pub warnings: Vec<Diagnostic>,
+}
+
+impl Default for LiveGameState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LiveGameState {
+    pub fn new() -> Self {
+        Self {
+            runner_positions: RunnerPositions::empty(),
+            inning: Inning { number: 1, top_bottom: TopBottom::Top },
+            outs: 0,
+            home_team_score: 0,
+            away_team_score: 0,
+            inning_scores: Vec::new(),
+            current_batter: None,
+            current_pitcher: None,
+            home_batting_order: BattingOrder::default(),
+            away_batting_order: BattingOrder::default(),
+            home_alignment: DefensiveAlignment::default(),
+            away_alignment: DefensiveAlignment::default(),
+            entered_pinch_runners: HashSet::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Start each team's defensive alignment from its roster.
+    pub fn set_rosters(&mut self, home_players: &[Player], away_players: &[Player]) {
+        self.home_alignment = DefensiveAlignment::from_roster(home_players);
+        self.away_alignment = DefensiveAlignment::from_roster(away_players);
+    }
+
+    /// Return the defensive alignment of the team in the field.
+    pub fn fielding_alignment(&self) -> &DefensiveAlignment {
+        self.fielding_alignment_in(self.inning.top_bottom)
+    }
+
+    /// Return the defensive alignment of the team in the field in the given half of an inning.
+    pub fn fielding_alignment_in(&self, top_bottom: TopBottom) -> &DefensiveAlignment {
+        match top_bottom {
+            TopBottom::Top => &self.home_alignment,
+            TopBottom::Bottom => &self.away_alignment,
+        }
+    }
+
+    /// Return the pinch runners who may take over a base in the current half-inning: those of the team at bat who
+    /// haven't already entered the game.
+    pub fn eligible_pinch_runners(&self, pinch_runners: &PinchRunners) -> Vec<SmallString> {
+        pinch_runners.batting(self.inning.top_bottom).iter()
+            .filter(|name| !self.entered_pinch_runners.contains(*name))
+            .cloned()
+            .collect()
+    }
+
+    /// Replay a sequence of plays from the start of a game.
+    pub fn replay(plays: &[Play], pinch_runners: &PinchRunners) -> Result<Self, String> {
+        Self::new().replay_from(plays, pinch_runners)
+    }
+
+    /// Replay a sequence of plays from this state, e.g. one with its rosters set.
+    pub fn replay_from<'a>(mut self, plays: impl IntoIterator<Item = &'a Play>, pinch_runners: &PinchRunners) -> Result<Self, String> {
+        for play in plays {
+            self.set_inning(play.inning);
+            if let Err(e) = self.process_play(play, pinch_runners, false) {
+                return Err(format!("Inning {}: {}", play.inning.to_string(), e));
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Return the batting order of the team that bats in the given half of an inning.
+    pub fn batting_order(&self, top_bottom: TopBottom) -> &BattingOrder {
+        match top_bottom {
+            TopBottom::Top => &self.away_batting_order,
+            TopBottom::Bottom => &self.home_batting_order,
+        }
+    }
+
+    /// Move to a new inning, clearing the bases and outs if the half-inning has changed, whether its number or its half.
+    pub fn set_inning(&mut self, inning: Inning) {
+        if self.inning != inning {
+            self.runner_positions = RunnerPositions::empty();
+            self.outs = 0;
+        }
+        self.inning = inning;
+    }
+
+    /// Whether the game could end after the plays so far: the ninth or a later inning is over with a winner, or the
+    /// home team has taken the lead in the bottom half of one.
+    pub fn could_end(&self) -> bool {
+        if self.inning.number < 9 {
+            return false;
+        }
+
+        let (home, away) = (self.home_team_score, self.away_team_score);
+        match self.inning.top_bottom {
+            // the home team doesn't bat in the bottom half if it's already ahead
+            TopBottom::Top => self.outs >= 3 && home > away,
+            TopBottom::Bottom => home > away || (self.outs >= 3 && home != away),
+        }
+    }
+
+    /// Apply a completed play, updating the runners, outs, score, and players involved.
+    /// More than three outs in a half-inning, a batter reaching base on a strikeout without `[on error]`, crediting an out
+    /// to a position no one in the defensive alignment plays, or switching the position of a player who isn't in the field,
+    /// is an error in strict mode, and a warning otherwise.
+    pub fn process_play(&mut self, play: &Play, pinch_runners: &PinchRunners, strict: bool) -> Result<(), Diagnostic> {
+        // a runner can't be put out at a base they've already passed
+        if let (PlayType::RunnerOut | PlayType::FieldOut, Some(base)) = (play.play_content.play_type(), play.play_content.base()) {
+            let runner = play.play_content.runner().unwrap();
+            if let Some(on) = self.runner_positions.base_of(runner) {
+                if base.compare(&on, BaseComparison::To) == std::cmp::Ordering::Less {
+                    return Err(Diagnostic::new(DiagnosticKind::ImpossibleBase, format!(
+                        "Runner {} is on {} and cannot be put out at {}",
+                        runner,
+                        on.to_string(),
+                        base.to_string(),
+                    )));
+                }
+            }
+        }
+
+        let eligible_pinch_runners = self.eligible_pinch_runners(pinch_runners);
+        let runs = self.runner_positions.process_movements(&play.movements, &eligible_pinch_runners)?;
+        for movement in &play.movements {
+            if eligible_pinch_runners.contains(&movement.runner) {
+                self.entered_pinch_runners.insert(movement.runner.clone());
+            }
+        }
+
+        // a batter who strikes out can only reach base because of a dropped third strike, which is an error
+        if matches!(play.play_content.play_type(), PlayType::Strikeout | PlayType::StrikeoutDoublePlay) {
+            let batter = play.play_content.batter();
+            let reached_cleanly = self.runner_positions.simplify_movements(&play.movements).into_iter()
+                .find(|movement| Some(&movement.runner) == batter && !movement.out && !movement.on_error && movement.from == Base::Home);
+            if let Some(movement) = reached_cleanly {
+                let diagnostic = Diagnostic::new(
+                    DiagnosticKind::MissingOnError,
+                    format!("Batter {} reaches base on a strikeout without [on error]", movement.runner),
+                );
+                if strict {
+                    return Err(diagnostic);
+                }
+                self.warnings.push(diagnostic.as_warning().in_inning(self.inning));
+            }
+        }
+
+        let previous_outs = self.outs;
+        for movement in self.runner_positions.simplify_movements(&play.movements) {
+            if movement.out {
+                self.outs += 1;
+            }
+        }
+
+        // only the play that goes past the third out is reported, not every play after it
+        if previous_outs <= 3 && self.outs > 3 {
+            let diagnostic = Diagnostic::new(DiagnosticKind::TooManyOuts, format!("{} outs in a half-inning", self.outs));
+            if strict {
+                return Err(diagnostic);
+            }
+            self.warnings.push(diagnostic.as_warning().in_inning(self.inning));
+        }
+
+        match self.inning.top_bottom {
+            TopBottom::Top => self.away_team_score += runs,
+            TopBottom::Bottom => self.home_team_score += runs,
+        }
+        if self.inning_scores.last().is_none_or(|(inning, _)| *inning != self.inning) {
+            self.inning_scores.push((self.inning, 0));
+        }
+        self.inning_scores.last_mut().unwrap().1 += runs;
+
+        if let Some(batter) = play.play_content.batter() {
+            self.current_batter = Some(batter.clone());
+            match self.inning.top_bottom {
+                TopBottom::Top => self.away_batting_order.record(batter),
+                TopBottom::Bottom => self.home_batting_order.record(batter),
+            }
+        }
+        if let Some(pitcher) = play.play_content.pitcher() {
+            self.current_pitcher = Some(pitcher.clone());
+        }
+
+        let alignment = match self.inning.top_bottom {
+            TopBottom::Top => &mut self.home_alignment,
+            TopBottom::Bottom => &mut self.away_alignment,
+        };
+        // a defensive switch only moves players already in the field
+        if let PlayContent::DefensiveSwitch { fielders, .. } = &play.play_content {
+            let not_in_field = fielders.iter().find(|fielder| alignment.has_roster() && alignment.position_of(fielder).is_none());
+            if let Some(fielder) = not_in_field {
+                let diagnostic = Diagnostic::new(
+                    DiagnosticKind::FielderNotInField,
+                    format!("{} is not in the field to switch positions", fielder),
+                );
+                if strict {
+                    return Err(diagnostic);
+                }
+                self.warnings.push(diagnostic.as_warning().in_inning(self.inning));
+            }
+        }
+        alignment.record_play(play);
+        // without a roster, the alignment only knows who has appeared in a play
+        if alignment.has_roster() {
+            let missing = play.movements.iter()
+                .flat_map(|movement| movement.credits.iter())
+                .find(|number| alignment.player_at(**number).is_none());
+            if let Some(number) = missing {
+                let diagnostic = Diagnostic::new(DiagnosticKind::NoFielderAtPosition, format!("No fielder is at position {} to credit", number));
+                if strict {
+                    return Err(diagnostic);
+                }
+                self.warnings.push(diagnostic.as_warning().in_inning(self.inning));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use pyo3::prelude::{pyclass, pymethods};
+
+use super::diagnostics::Diagnostic;
+
+/// The outcome of `Parser.validate`: whether the text is a complete, valid game, and why not if it isn't.
+/// The counts and score cover the plays parsed before any error.
+#[pyclass]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    #[pyo3(get)]
+    pub ok: bool,
+    #[pyo3(get)]
+    pub errors: Vec<Diagnostic>,
+    #[pyo3(get)]
+    pub warnings: Vec<Diagnostic>,
+    #[pyo3(get)]
+    pub plays: usize,
+    #[pyo3(get)]
+    pub innings: u64,
+    #[pyo3(get)]
+    pub home_team_score: u64,
+    #[pyo3(get)]
+    pub away_team_score: u64,
+}
+
+#[pymethods]
+impl ValidationReport {
+    fn __bool__(&self) -> bool {
+        self.ok
+    }
+}
@@ -0,0 +1,106 @@
+/// Venues that have hosted MLB regular season games in recent seasons.
+pub const MLB_VENUES: [&str; 38] = [
+    "American Family Field",
+    "Angel Stadium",
+    "Busch Stadium",
+    "Chase Field",
+    "Citi Field",
+    "Citizens Bank Park",
+    "Comerica Park",
+    "Coors Field",
+    "Daikin Park",
+    "Dodger Stadium",
+    "Estadio Alfredo Harp Helu",
+    "Fenway Park",
+    "Globe Life Field",
+    "Gocheok Sky Dome",
+    "Great American Ball Park",
+    "Guaranteed Rate Field",
+    "Kauffman Stadium",
+    "London Stadium",
+    "loanDepot park",
+    "Minute Maid Park",
+    "Nationals Park",
+    "Oakland Coliseum",
+    "Oracle Park",
+    "Oriole Park at Camden Yards",
+    "Petco Park",
+    "PNC Park",
+    "Progressive Field",
+    "Rate Field",
+    "Rickwood Field",
+    "Rogers Centre",
+    "Sutter Health Park",
+    "T-Mobile Park",
+    "Target Field",
+    "Tokyo Dome",
+    "Tropicana Field",
+    "Truist Park",
+    "Wrigley Field",
+    "Yankee Stadium",
+];
+
+/// A set of known venue names to check parsed venues against.
+#[derive(Clone, Debug)]
+pub struct VenueRegistry {
+    venues: Vec<String>,
+}
+
+impl VenueRegistry {
+    pub fn new(venues: Vec<String>) -> Self {
+        Self { venues }
+    }
+
+    /// Check that `venue` is known, suggesting the closest known venue if it isn't.
+    pub fn validate(&self, venue: &str) -> Result<(), String> {
+        if self.venues.iter().any(|known| known == venue) {
+            return Ok(());
+        }
+
+        let closest = self.venues.iter()
+            .map(|known| (edit_distance(&known.to_lowercase(), &venue.to_lowercase()), known))
+            .min_by_key(|(distance, _)| *distance);
+
+        match closest {
+            // only suggest venues that are a plausible typo of the given one
+            Some((distance, known)) if distance <= venue.chars().count() / 2 => {
+                Err(format!("Unknown venue: {} (did you mean {}?)", venue, known))
+            },
+            _ => Err(format!("Unknown venue: {}", venue)),
+        }
+    }
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_venue() {
+        let registry = VenueRegistry::new(MLB_VENUES.iter().map(|venue| venue.to_string()).collect());
+
+        assert!(registry.validate("Dodger Stadium").is_ok());
+        assert_eq!(
+            registry.validate("Dodgers Stadium").unwrap_err(),
+            "Unknown venue: Dodgers Stadium (did you mean Dodger Stadium?)",
+        );
+        assert_eq!(registry.validate("Moon Base").unwrap_err(), "Unknown venue: Moon Base");
+    }
+}
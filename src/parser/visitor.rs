@@ -0,0 +1,92 @@
+use super::game::{Context, Game, Inning, Movement, Play, Player, Team};
+
+/// Callbacks for each part of a game, in the order they appear in the game text.
+/// Every method does nothing by default, so an analysis only implements the parts it needs.
+pub trait GameVisitor {
+    fn visit_context(&mut self, _context: &Context) {}
+
+    fn visit_team(&mut self, _team: &Team, _home: bool) {}
+
+    fn visit_player(&mut self, _player: &Player, _home: bool) {}
+
+    /// Called before the first play of each half-inning.
+    fn visit_inning(&mut self, _inning: &Inning) {}
+
+    fn visit_play(&mut self, _play: &Play) {}
+
+    fn visit_movement(&mut self, _play: &Play, _movement: &Movement) {}
+}
+
+impl Game {
+    /// Walk the game with a visitor, calling it for each part of the game in order.
+    pub fn walk<V: GameVisitor>(&self, visitor: &mut V) {
+        visitor.visit_context(&self.context);
+
+        for (team, home) in [(&self.home_team, true), (&self.away_team, false)] {
+            visitor.visit_team(team, home);
+            for player in &team.players {
+                visitor.visit_player(player, home);
+            }
+        }
+
+        let mut current_inning = None;
+        for play in &self.plays {
+            if current_inning != Some(play.inning) {
+                visitor.visit_inning(&play.inning);
+                current_inning = Some(play.inning);
+            }
+
+            visitor.visit_play(play);
+            for movement in &play.movements {
+                visitor.visit_movement(play, movement);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[derive(Default)]
+    struct Counter {
+        players: usize,
+        innings: usize,
+        plays: usize,
+        movements: usize,
+    }
+
+    impl GameVisitor for Counter {
+        fn visit_player(&mut self, _player: &Player, _home: bool) {
+            self.players += 1;
+        }
+
+        fn visit_inning(&mut self, _inning: &Inning) {
+            self.innings += 1;
+        }
+
+        fn visit_play(&mut self, _play: &Play) {
+            self.plays += 1;
+        }
+
+        fn visit_movement(&mut self, _play: &Play, _movement: &Movement) {
+            self.movements += 1;
+        }
+    }
+
+    #[test]
+    fn walk_visits_every_part() {
+        let mut parser = Parser::new(false);
+        parser.parse_input(include_str!("../../test_data/748231.txt")).unwrap();
+        let game = parser.complete().unwrap();
+
+        let mut counter = Counter::default();
+        game.walk(&mut counter);
+
+        assert_eq!(counter.players, game.home_team.players.len() + game.away_team.players.len());
+        assert_eq!(counter.plays, game.plays.len());
+        assert_eq!(counter.movements, game.plays.iter().map(|play| play.movements.len()).sum::<usize>());
+        assert!(counter.innings >= 17);
+    }
+}
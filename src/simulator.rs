@@ -0,0 +1,167 @@
+use pyo3::{prelude::{pyclass, pymethods, PyRef, PyRefMut, PyResult}, exceptions::PyValueError};
+
+use crate::parser::game::Game;
+use crate::parser::small_string::SmallString;
+use crate::parser::state::{DefensiveAlignment, LiveGameState, PinchRunners};
+
+/// A snapshot of the reconstructed game state after a play.
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct GameState {
+    #[pyo3(get)]
+    pub(crate) play_index: usize,
+    #[pyo3(get)]
+    pub(crate) inning: u64,
+    #[pyo3(get)]
+    pub(crate) top_bottom: String,
+    #[pyo3(get)]
+    pub(crate) home_team_score: u64,
+    #[pyo3(get)]
+    pub(crate) away_team_score: u64,
+    #[pyo3(get)]
+    pub(crate) outs: u64,
+    #[pyo3(get)]
+    pub(crate) first: Option<SmallString>,
+    #[pyo3(get)]
+    pub(crate) second: Option<SmallString>,
+    #[pyo3(get)]
+    pub(crate) third: Option<SmallString>,
+    #[pyo3(get)]
+    pub(crate) batter: Option<SmallString>,
+    #[pyo3(get)]
+    pub(crate) pitcher: Option<SmallString>,
+    /// Who is at each position for the team in the field.
+    #[pyo3(get)]
+    pub(crate) fielding_alignment: DefensiveAlignment,
+}
+
+impl GameState {
+    pub(crate) fn from_live_state(play_index: usize, state: &LiveGameState) -> Self {
+        Self {
+            play_index,
+            inning: state.inning.number,
+            top_bottom: state.inning.top_bottom.to_string(),
+            home_team_score: state.home_team_score,
+            away_team_score: state.away_team_score,
+            outs: state.outs,
+            first: state.runner_positions.first.clone(),
+            second: state.runner_positions.second.clone(),
+            third: state.runner_positions.third.clone(),
+            batter: state.current_batter.clone(),
+            pitcher: state.current_pitcher.clone(),
+            fielding_alignment: state.fielding_alignment().clone(),
+        }
+    }
+}
+
+/// Replays a completed game play-by-play, reconstructing the live state after each play.
+#[pyclass]
+pub struct GameSimulator {
+    game: Game,
+    pinch_runners: PinchRunners,
+    live_game_state: LiveGameState,
+    next_play_index: usize,
+}
+
+impl GameSimulator {
+    pub fn from_game(game: Game) -> Self {
+        Self {
+            pinch_runners: game.pinch_runners(),
+            live_game_state: game.initial_state(),
+            game,
+            next_play_index: 0,
+        }
+    }
+
+    /// Apply the next play, returning the resulting state or `None` if the game is over.
+    pub fn step(&mut self) -> Result<Option<GameState>, String> {
+        let Some(play) = self.game.plays.get(self.next_play_index) else {
+            return Ok(None);
+        };
+
+        self.live_game_state.set_inning(play.inning);
+        if let Err(e) = self.live_game_state.process_play(play, &self.pinch_runners, false) {
+            return Err(format!("Inning {}: {}", play.inning.to_string(), e));
+        }
+
+        let state = GameState::from_live_state(self.next_play_index, &self.live_game_state);
+        self.next_play_index += 1;
+
+        Ok(Some(state))
+    }
+}
+
+#[pymethods]
+impl GameSimulator {
+    #[new]
+    fn new(game: PyRef<'_, Game>) -> Self {
+        Self::from_game(game.clone())
+    }
+
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<GameState>> {
+        slf.step().map_err(PyValueError::new_err)
+    }
+
+    /// Rewind the simulator to the start of the game.
+    pub fn reset(&mut self) {
+        self.live_game_state = self.game.initial_state();
+        self.next_play_index = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    #[test]
+    fn simulate_full_game() {
+        let mut parser = Parser::new(false);
+        let input = include_str!("../test_data/748231.txt");
+        parser.parse_input(input).unwrap();
+
+        let game = parser.complete().unwrap();
+        let play_count = game.plays.len();
+        let mut simulator = GameSimulator::from_game(game);
+
+        let mut states = Vec::new();
+        while let Some(state) = simulator.step().unwrap() {
+            assert!(state.outs <= 3);
+            states.push(state);
+        }
+
+        assert_eq!(states.len(), play_count);
+
+        let last = states.last().unwrap();
+        assert_eq!(last.home_team_score, parser.live_game_state.home_team_score);
+        assert_eq!(last.away_team_score, parser.live_game_state.away_team_score);
+    }
+
+    #[test]
+    fn simulate_tracks_runners_and_score() {
+        let mut parser = Parser::new(false);
+        let input = "[GAME] 0 [DATE] 0000-00-00 [VENUE] example [WEATHER] example 0 0\n\n[TEAM] 1\n[PITCHER] Person A\n\n[TEAM] 2\n[PITCHER] Person C\n\n[GAME_START]\n[INNING] 1 top [PLAY] Single [BATTER] Person D [PITCHER] Person A [MOVEMENTS] Person D home -> 1;\n[INNING] 1 top [PLAY] Home Run [BATTER] Person E [PITCHER] Person A [MOVEMENTS] Person E home -> home, Person D 1 -> home;\n[INNING] 1 top [PLAY] Strikeout [BATTER] Person F [PITCHER] Person A [MOVEMENTS] Person F home -> home [out];\n[GAME_END]";
+        parser.parse_input(input).unwrap();
+
+        let mut simulator = GameSimulator::from_game(parser.complete().unwrap());
+
+        let state = simulator.step().unwrap().unwrap();
+        assert_eq!(state.first, Some("Person D".into()));
+        assert_eq!(state.batter, Some("Person D".into()));
+        assert_eq!(state.pitcher, Some("Person A".into()));
+
+        let state = simulator.step().unwrap().unwrap();
+        assert_eq!(state.first, None);
+        assert_eq!(state.away_team_score, 2);
+
+        let state = simulator.step().unwrap().unwrap();
+        assert_eq!(state.outs, 1);
+        assert_eq!(state.batter, Some("Person F".into()));
+
+        assert!(simulator.step().unwrap().is_none());
+    }
+}
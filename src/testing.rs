@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use pyo3::{prelude::{pyfunction, PyResult}, exceptions::PyValueError};
+use rand::{distr::weighted::WeightedIndex, prelude::Distribution, rngs::StdRng, Rng, SeedableRng};
+
+use crate::parser::Parser;
+use crate::parser::options::ParserOptions;
+use crate::parser::game::{Game, PlayType, Position};
+
+// every fielding position, in the order players are assigned to them
+const POSITIONS: [Position; 9] = [
+    Position::Pitcher,
+    Position::Catcher,
+    Position::FirstBase,
+    Position::SecondBase,
+    Position::ThirdBase,
+    Position::Shortstop,
+    Position::LeftField,
+    Position::CenterField,
+    Position::RightField,
+];
+
+/// Parameters for generating random games.
+#[derive(Clone, Debug)]
+pub struct GeneratorOptions {
+    pub innings: u64,
+    /// The number of players on each team, every one of whom bats.
+    pub roster_size: usize,
+    /// The relative weight of each play type. Only plate appearances are supported.
+    pub play_mix: Vec<(PlayType, u32)>,
+    /// Generate the same game every time for the same seed.
+    pub seed: Option<u64>,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            innings: 9,
+            roster_size: 9,
+            play_mix: vec![
+                (PlayType::Single, 15),
+                (PlayType::Double, 5),
+                (PlayType::Triple, 1),
+                (PlayType::HomeRun, 3),
+                (PlayType::Walk, 8),
+                (PlayType::HitByPitch, 1),
+                (PlayType::Strikeout, 22),
+                (PlayType::Groundout, 20),
+                (PlayType::Flyout, 15),
+                (PlayType::Lineout, 6),
+                (PlayType::PopOut, 4),
+            ],
+            seed: None,
+        }
+    }
+}
+
+impl GeneratorOptions {
+    fn check(&self) -> Result<(), String> {
+        if self.innings == 0 {
+            return Err("innings must be at least 1".to_string());
+        }
+        if self.roster_size == 0 || self.roster_size > 26 * 26 {
+            return Err(format!("roster_size must be between 1 and {}", 26 * 26));
+        }
+        if let Some((play_type, _)) = self.play_mix.iter().find(|(play_type, _)| !is_supported(*play_type)) {
+            return Err(format!("{} can't be generated", play_type.to_string()));
+        }
+        if self.play_mix.iter().all(|(_, weight)| *weight == 0) {
+            return Err("play_mix needs at least one play type with a positive weight".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn is_supported(play_type: PlayType) -> bool {
+    matches!(
+        play_type,
+        PlayType::Single |
+        PlayType::Double |
+        PlayType::Triple |
+        PlayType::HomeRun |
+        PlayType::Walk |
+        PlayType::HitByPitch |
+        PlayType::Strikeout |
+        PlayType::Groundout |
+        PlayType::Flyout |
+        PlayType::Lineout |
+        PlayType::PopOut
+    )
+}
+
+/// A generated team: its roster, in batting order, and where each player fields.
+struct Roster {
+    team_id: u64,
+    players: Vec<(Position, String)>,
+}
+
+impl Roster {
+    fn new(team_id: u64, prefix: &str, size: usize) -> Self {
+        // names can't contain digits, so tell players apart with letters, e.g. "Home Ab"
+        let players = (0..size)
+            .map(|index| {
+                let position = POSITIONS.get(index).copied().unwrap_or(Position::Utility);
+                let name = format!("{} {}{}", prefix, (b'A' + (index / 26) as u8) as char, (b'a' + (index % 26) as u8) as char);
+                (position, name)
+            })
+            .collect();
+
+        Self { team_id, players }
+    }
+
+    fn pitcher(&self) -> &str {
+        &self.players[0].1
+    }
+
+    /// Return a random fielder other than the pitcher, or the pitcher if they're the only player.
+    fn fielder(&self, rng: &mut StdRng) -> &str {
+        let index = if self.players.len() > 1 { rng.random_range(1..self.players.len().min(POSITIONS.len())) } else { 0 };
+        &self.players[index].1
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("[TEAM] {}\n", self.team_id);
+        for (position, name) in &self.players {
+            text.push_str(&format!("[{}] {}\n", position.to_string(), name));
+        }
+
+        text
+    }
+}
+
+/// Generate the text of a random, valid game. Every half-inning is played out to three outs.
+pub fn random_game_text(options: &GeneratorOptions) -> Result<String, String> {
+    options.check()?;
+
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+    let weights = WeightedIndex::new(options.play_mix.iter().map(|(_, weight)| *weight)).map_err(|e| e.to_string())?;
+
+    let home = Roster::new(1, "Home", options.roster_size);
+    let away = Roster::new(2, "Away", options.roster_size);
+    let mut text = format!(
+        "[GAME] {} [DATE] 2024-04-01 [VENUE] Example Park [WEATHER] Clear 70 5\n\n{}\n{}\n[GAME_START]\n",
+        rng.random_range(1..1_000_000),
+        home.to_text(),
+        away.to_text(),
+    );
+
+    let mut next_batter = [0, 0];
+    for number in 1..=options.innings {
+        for (half, top_bottom) in ["top", "bottom"].iter().enumerate() {
+            let (batting, fielding) = if half == 0 { (&away, &home) } else { (&home, &away) };
+            // the runners on first, second and third
+            let mut bases: [Option<&str>; 3] = [None, None, None];
+            let mut outs = 0;
+            while outs < 3 {
+                let batter = &batting.players[next_batter[half] % batting.players.len()].1;
+                next_batter[half] += 1;
+
+                let play_type = options.play_mix[weights.sample(&mut rng)].0;
+                let mut play = format!("[INNING] {} {} [PLAY] {} [BATTER] {} [PITCHER] {}", number, top_bottom, play_type.to_string(), batter, fielding.pitcher());
+                if play_type.requires_fielders() {
+                    play.push_str(&format!(" [FIELDERS] {}", fielding.fielder(&mut rng)));
+                }
+
+                let movements = match play_type {
+                    PlayType::Single => advance(&mut bases, batter, 1),
+                    PlayType::Double => advance(&mut bases, batter, 2),
+                    PlayType::Triple => advance(&mut bases, batter, 3),
+                    PlayType::HomeRun => advance(&mut bases, batter, 4),
+                    PlayType::Walk | PlayType::HitByPitch => force(&mut bases, batter),
+                    _ => {
+                        outs += 1;
+                        vec![format!("{} home -> home [out]", batter)]
+                    },
+                };
+                text.push_str(&format!("{} [MOVEMENTS] {};\n", play, movements.join(", ")));
+            }
+        }
+    }
+    text.push_str("[GAME_END]");
+
+    Ok(text)
+}
+
+/// Move every runner and the batter forward `bases` bases, returning the movements.
+fn advance<'a>(runners: &mut [Option<&'a str>; 3], batter: &'a str, bases: usize) -> Vec<String> {
+    let mut movements = Vec::new();
+    let mut moved = [None; 3];
+    for (from, runner) in runners.iter().enumerate().rev().filter_map(|(from, runner)| runner.map(|runner| (from, runner))) {
+        movements.push(format!("{} {} -> {}", runner, from + 1, base_name(from + 1 + bases)));
+        if from + bases < 3 {
+            moved[from + bases] = Some(runner);
+        }
+    }
+
+    movements.push(format!("{} home -> {}", batter, base_name(bases)));
+    if bases < 4 {
+        moved[bases - 1] = Some(batter);
+    }
+    *runners = moved;
+
+    movements
+}
+
+/// Put the batter on first, moving on only the runners forced to advance.
+fn force<'a>(runners: &mut [Option<&'a str>; 3], batter: &'a str) -> Vec<String> {
+    // the runners in front of the batter, up to the first empty base
+    let forced = runners.iter().take_while(|runner| runner.is_some()).count();
+    let mut movements = Vec::new();
+    for from in (0..forced).rev() {
+        let runner = runners[from].unwrap();
+        movements.push(format!("{} {} -> {}", runner, from + 1, base_name(from + 2)));
+        if from + 1 < 3 {
+            runners[from + 1] = Some(runner);
+        }
+    }
+
+    movements.push(format!("{} home -> 1", batter));
+    runners[0] = Some(batter);
+
+    movements
+}
+
+fn base_name(base: usize) -> String {
+    if base >= 4 { "home".to_string() } else { base.to_string() }
+}
+
+/// Generate a random, valid game, parsed from the text `random_game_text` generates.
+pub fn random_game(options: &GeneratorOptions) -> Result<Game, String> {
+    let text = random_game_text(options)?;
+    let mut parser = Parser::with_options(ParserOptions { strict: true, ..Default::default() });
+    // the generated text is always valid, so there's nothing more useful to report
+    parser.parse_input(&text).map_err(|_| "Generated a game that doesn't parse".to_string())?;
+
+    parser.complete().ok_or("Generated an incomplete game".to_string())
+}
+
+fn py_options(innings: u64, roster_size: usize, play_mix: Option<HashMap<String, u32>>, seed: Option<u64>) -> PyResult<GeneratorOptions> {
+    let mut options = GeneratorOptions { innings, roster_size, seed, ..Default::default() };
+    if let Some(play_mix) = play_mix {
+        options.play_mix = play_mix.into_iter()
+            .map(|(play_type, weight)| Ok((play_type.parse::<PlayType>().map_err(PyValueError::new_err)?, weight)))
+            .collect::<PyResult<_>>()?;
+        // a map has no order, so sort to keep seeded games the same from run to run
+        options.play_mix.sort_by_key(|(play_type, _)| play_type.to_string());
+    }
+
+    Ok(options)
+}
+
+/// Generate the text of a random, valid game for property-based testing, e.g. with a Hypothesis strategy that draws
+/// the seed. `play_mix` maps play types such as `"Home Run"` to relative weights; only plate appearances are supported.
+#[pyfunction(name = "random_game_text")]
+#[pyo3(signature = (innings=9, roster_size=9, play_mix=None, seed=None))]
+pub fn py_random_game_text(innings: u64, roster_size: usize, play_mix: Option<HashMap<String, u32>>, seed: Option<u64>) -> PyResult<String> {
+    random_game_text(&py_options(innings, roster_size, play_mix, seed)?).map_err(PyValueError::new_err)
+}
+
+/// Generate a random, valid `Game`, with the same parameters as `random_game_text`.
+#[pyfunction(name = "random_game")]
+#[pyo3(signature = (innings=9, roster_size=9, play_mix=None, seed=None))]
+pub fn py_random_game(innings: u64, roster_size: usize, play_mix: Option<HashMap<String, u32>>, seed: Option<u64>) -> PyResult<Game> {
+    random_game(&py_options(innings, roster_size, play_mix, seed)?).map_err(PyValueError::new_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_games_parse() {
+        for seed in 0..20 {
+            let options = GeneratorOptions { seed: Some(seed), ..Default::default() };
+            let game = random_game(&options).unwrap();
+            assert_eq!(game.plays.last().unwrap().inning.number, 9);
+            game.replay().unwrap();
+        }
+    }
+
+    #[test]
+    fn options_control_the_game() {
+        let options = GeneratorOptions {
+            innings: 3,
+            roster_size: 12,
+            play_mix: vec![(PlayType::HomeRun, 1), (PlayType::Strikeout, 1)],
+            seed: Some(7),
+        };
+        assert_eq!(random_game_text(&options).unwrap(), random_game_text(&options).unwrap());
+
+        let game = random_game(&options).unwrap();
+        assert_eq!(game.home_team.players.len(), 12);
+        assert_eq!(game.plays.last().unwrap().inning.number, 3);
+        assert!(game.plays.iter().all(|play| matches!(play.play_content.play_type(), PlayType::HomeRun | PlayType::Strikeout)));
+
+        let options = GeneratorOptions { play_mix: vec![(PlayType::StolenBase, 1)], ..Default::default() };
+        assert!(random_game_text(&options).is_err());
+    }
+}